@@ -27,7 +27,7 @@ async fn main() -> Result<()> {
 }
 
 /// Creates a paste synchronously.
-fn call_create_paste() -> Result<(), reqwest::Error> /*PasteResult<()>*/ {
+fn call_create_paste() -> Result<()> {
     let pasties: Vec<PastyObject> = vec![
         PastyObject {
             _id: str!(""),
@@ -44,14 +44,14 @@ fn call_create_paste() -> Result<(), reqwest::Error> /*PasteResult<()>*/ {
     ];
     let data: CreateObject = CreateObject {
         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-        expiresIn: String::from("1d"),
+        expiresIn: pastemyst::time::ExpiresIn::OneDay,
         isPrivate: false,
         isPublic: false,
-        tags: String::from(""),
-        pasties: pasties,
+        tags: Vec::new(),
+        pasties,
     };
     let paste = create_paste(data)?;
-    println!("https://paste.myst.rs/{}", paste._id);
+    println!("{}", paste.url());
     Ok(())
 }
 
@@ -73,14 +73,14 @@ async fn call_create_paste_async() -> Result<()> {
     ];
     let data: CreateObject = CreateObject {
         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-        expiresIn: String::from("1d"),
+        expiresIn: pastemyst::time::ExpiresIn::OneDay,
         isPrivate: false,
         isPublic: false,
-        tags: String::from(""),
+        tags: Vec::new(),
         pasties,
     };
     let paste = create_paste_async(data).await?;
-    println!("https://paste.myst.rs/{}", paste._id);
+    println!("{}", paste.url());
     Ok(())
 }
 
@@ -103,17 +103,17 @@ fn call_create_private_paste(auth_token: &str) -> PasteResult<()> {
     ];
     let data: CreateObject = CreateObject {
         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-        expiresIn: String::from("1d"),
+        expiresIn: pastemyst::time::ExpiresIn::OneDay,
         isPrivate: false,
         isPublic: false,
-        tags: String::from(""),
+        tags: Vec::new(),
         pasties,
     };
     let paste = create_private_paste(
         data,
         auth_token,
     )?;
-    println!("https://paste.myst.rs/{}", paste._id);
+    println!("{}", paste.url());
     Ok(())
 }
 
@@ -136,31 +136,35 @@ async fn call_create_private_paste_async(auth_token: &str) -> PasteResult<()> {
     ];
     let data: CreateObject = CreateObject {
         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-        expiresIn: String::from("1d"),
+        expiresIn: pastemyst::time::ExpiresIn::OneDay,
         isPrivate: false,
         isPublic: false,
-        tags: String::from(""),
+        tags: Vec::new(),
         pasties,
     };
     let paste = create_private_paste(
         data,
         auth_token,
     )?;
-    println!("https://paste.myst.rs/{}", paste._id);
+    println!("{}", paste.url());
     Ok(())
 }
 
 /// Gets a paste from pastemyst synchronously.
 fn call_get_paste() -> PasteResult<()> {
     let paste: PasteObject = get_paste("hipfqanx")?;
-    println!("{:#?}", paste.pasties[1].language);
+    for pasty in paste.iter_pasties() {
+        println!("{}: {}", pasty.title, pasty.language);
+    }
     Ok(())
 }
 
 /// Gets a paste from pastemyst asynchronously.
 async fn call_get_paste_async() -> PasteResult<()> {
     let paste: PasteObject = get_paste_async("hipfqanx").await?;
-    println!("{:#?}", paste.pasties[0].language);
+    if let Some(pasty) = paste.pasties_with_language(pastemyst::data::Language::Rust).next() {
+        println!("{:#?}", pasty.code);
+    }
     Ok(())
 }
 
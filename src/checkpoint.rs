@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::PasteMystError;
+
+/// Records which ids a bulk operation is done with in
+/// a file, one id per line, so that an interrupted run
+/// can pick up where it stopped instead of starting
+/// over. Every id is written as soon as it's done.
+///
+/// The bulk operations of `PasteMystClient` take the
+/// path of a checkpoint file and open it themselves,
+/// this type is public so that your own bulk work
+/// can be made resumable the same way.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::checkpoint::Checkpoint;
+///
+/// fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let mut checkpoint = Checkpoint::open("backup.checkpoint")?;
+///     for id in ["hipfqanx", "cys6ypb4"] {
+///         if checkpoint.is_done(id) { continue; }
+///         // ...
+///         checkpoint.mark_done(id)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Checkpoint {
+    done: HashSet<String>,
+    file: Option<File>,
+}
+
+impl Checkpoint {
+    /// Opens (or creates) a checkpoint
+    /// file and reads the ids that were
+    /// done in earlier runs.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PasteMystError> {
+        let path = path.as_ref();
+        let done = match std::fs::read_to_string(path) {
+            Ok(contents) => contents.lines().map(str::trim).filter(|id| !id.is_empty()).map(String::from).collect(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Checkpoint { done, file: Some(file) })
+    }
+
+    /// A checkpoint that is only kept in
+    /// memory, for when no file is given.
+    pub fn in_memory() -> Self { Checkpoint { done: HashSet::new(), file: None } }
+
+    /// Opens the file at the path if there is
+    /// one, otherwise keeps it in memory.
+    #[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+    pub(crate) fn open_optional(path: Option<&Path>) -> Result<Self, PasteMystError> {
+        match path {
+            Some(path) => Checkpoint::open(path),
+            None => Ok(Checkpoint::in_memory()),
+        }
+    }
+
+    /// Whether the id was done
+    /// in this run or an earlier one.
+    pub fn is_done(&self, id: &str) -> bool { self.done.contains(id) }
+
+    /// Records that the id is done and
+    /// writes it to the file right away.
+    pub fn mark_done(&mut self, id: &str) -> Result<(), PasteMystError> {
+        if !self.done.insert(id.to_string()) { return Ok(()); }
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{}", id)?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    /// How many ids are done.
+    pub fn len(&self) -> usize { self.done.len() }
+
+    /// Whether no id is done yet.
+    pub fn is_empty(&self) -> bool { self.done.is_empty() }
+}
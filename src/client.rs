@@ -0,0 +1,1785 @@
+use futures::stream::{self, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::{Method, Request, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+use crate::checkpoint::Checkpoint;
+pub use crate::connection::{set_default_options, ClientOptions, DEFAULT_USER_AGENT};
+use crate::error::{self, PasteMystError, Violation};
+use crate::paste::{self, CreateObject, DeleteOutcome, EditObject, PasteObject};
+use crate::search::{ListOptions, Query};
+use crate::token::ApiToken;
+use crate::transport::{self, Transport};
+#[cfg(feature = "data")]
+use crate::data::{self, DataObject};
+#[cfg(feature = "time")]
+use crate::time::{self, TimeObject};
+use crate::user;
+#[cfg(feature = "user")]
+use crate::user::{SelfUserObject, UserObject};
+
+/// How many requests the batch operations of a
+/// client have in flight at once unless
+/// `with_concurrency` is called.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// The size of the chunks bodies are uploaded
+/// in when the progress is reported.
+const UPLOAD_CHUNK_SIZE: usize = 16 * 1024;
+
+/// The instance requests are sent to
+/// unless `with_instance` is called.
+pub const DEFAULT_INSTANCE: &str = paste::BASE_URL;
+
+/// The type provided by the pastemyst lib. It takes
+/// a type `T` and evalutates to that type and a
+/// `Result` like so: `Result<T, E>` where `E` has
+/// the default value of `PasteMystError`. Keep note
+/// that `E` can be overriden.
+pub type ClientResult<T, E = PasteMystError> = Result<T, E>;
+
+/// A reusable client to talk to [pastemyst](https://paste.myst.rs)
+/// asynchronously. Unlike the free functions in the `paste`, `user`,
+/// `data` and `time` modules, the underlying http client (and its
+/// connections) is shared by all the calls made through it, and the
+/// authorization token only has to be given once. Use
+/// `PasteMystBlockingClient` for the same from synchronous code.
+///
+/// The client can be constructed from your own `reqwest::Client`,
+/// or, with the `middleware` feature, from a
+/// `reqwest_middleware::ClientWithMiddleware` so that your existing
+/// middleware stack (retries, tracing, caching, etc.) applies to
+/// every request sent by this crate.
+///
+/// Cloning the client is cheap, the connection pool is shared.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::client::*;
+///
+/// #[tokio::main]
+/// async fn main() -> ClientResult<()> {
+///     let client = PasteMystClient::new()
+///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+///     let paste = client.get_paste("hipfqanx").await?;
+///     println!("{}", paste.title);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PasteMystClient {
+    http: Http,
+    token: Option<ApiToken>,
+    instance: Option<String>,
+    rate_limit_retries: u32,
+    concurrency: usize,
+    max_response_size: Option<usize>,
+    cache: Arc<Mutex<HashMap<String, PasteObject>>>,
+    #[cfg(feature = "rate-limit")]
+    limiter: Option<Arc<governor::DefaultDirectRateLimiter>>,
+    slots: Option<Arc<PrioritySlots>>,
+    priority: Priority,
+    stats: Arc<Stats>,
+    hooks: Arc<Vec<PreUploadHook>>,
+    interceptors: Arc<Vec<Interceptor>>,
+    last_response: Arc<Mutex<Option<ResponseMeta>>>,
+}
+
+/// What pastemyst sent along with a response, besides
+/// its body, returned by `PasteMystClient::last_response_meta`.
+/// Callers can use it to pace themselves according to
+/// what the server reports.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// How long pastemyst took to respond.
+    pub duration: Duration,
+}
+
+impl ResponseMeta {
+    /// The number of requests left in the current
+    /// window, from the `X-RateLimit-Remaining`
+    /// header, if it was sent.
+    pub fn rate_limit_remaining(&self) -> Option<u64> { self.header_number("x-ratelimit-remaining") }
+
+    /// The number of requests allowed per window,
+    /// from the `X-RateLimit-Limit` header, if it
+    /// was sent.
+    pub fn rate_limit_limit(&self) -> Option<u64> { self.header_number("x-ratelimit-limit") }
+
+    /// How long to wait before sending another
+    /// request, from the `Retry-After` header,
+    /// if it was sent in seconds.
+    pub fn retry_after(&self) -> Option<Duration> { self.header_number("retry-after").map(Duration::from_secs) }
+
+    /// Parses a header holding a number.
+    fn header_number(&self, name: &str) -> Option<u64> {
+        self.headers.get(name)?.to_str().ok()?.trim().parse().ok()
+    }
+}
+
+/// The request a client would send to create a paste,
+/// returned by `PasteMystClient::dry_run` instead of
+/// sending it. The headers are the ones set by the
+/// client and its interceptors, the ones the http
+/// client adds on its own (like `User-Agent`) aren't
+/// there. The authorization header is marked as
+/// sensitive so it isn't printed.
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    /// The method of the request.
+    pub method: Method,
+    /// Where the request would be sent.
+    pub url: Url,
+    /// The headers of the request.
+    pub headers: HeaderMap,
+    /// The json body of the request.
+    pub body: String,
+}
+
+/// Closures that see every request the client sends
+/// and every response it gets back, a lightweight
+/// extension point when the `middleware` and `tower`
+/// features are more than you need. Both closures
+/// are optional.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::client::*;
+///
+/// let interceptor = Interceptor::new()
+///     .on_request(|request| {
+///         request.headers_mut().insert("X-Request-Source", "my-bot".parse().unwrap());
+///     })
+///     .on_response(|response, elapsed| {
+///         println!("{} {} in {:?}", response.status(), response.url(), elapsed);
+///     });
+/// let client = PasteMystClient::new().with_interceptor(interceptor);
+/// ```
+#[derive(Clone, Default)]
+pub struct Interceptor {
+    request: Option<RequestCallback>,
+    response: Option<ResponseCallback>,
+}
+
+/// Called by an `Interceptor`
+/// before a request is sent.
+type RequestCallback = Arc<dyn Fn(&mut Request) + Send + Sync>;
+
+/// Called by an `Interceptor`
+/// once a response arrived.
+type ResponseCallback = Arc<dyn Fn(&Response, Duration) + Send + Sync>;
+
+impl Interceptor {
+    /// Creates an interceptor
+    /// that does nothing yet.
+    pub fn new() -> Self { Interceptor::default() }
+
+    /// Sets the closure called with every request right
+    /// before it's sent, it may change the request, e.g.
+    /// to add headers.
+    pub fn on_request(mut self, callback: impl Fn(&mut Request) + Send + Sync + 'static) -> Self {
+        self.request = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the closure called with every response
+    /// along with how long pastemyst took to send it.
+    pub fn on_response(mut self, callback: impl Fn(&Response, Duration) + Send + Sync + 'static) -> Self {
+        self.response = Some(Arc::new(callback));
+        self
+    }
+}
+
+/// A check run on every paste before it's created or
+/// edited, see `PasteMystClient::add_pre_upload_hook`.
+pub type PreUploadHook = Arc<dyn Fn(&mut CreateObject) -> Result<(), Violation> + Send + Sync>;
+
+/// The usage of a client since it was created, shared
+/// by all of its clones and returned by `PasteMystClient::stats`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::client::*;
+///
+/// let client = PasteMystClient::new();
+/// let stats = client.stats();
+/// assert_eq!(stats.attempts, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Requests sent, retries included.
+    pub attempts: u64,
+    /// Requests that were sent again, by the
+    /// client or by a worker using it.
+    pub retries: u64,
+    /// Requests that were held back by the rate
+    /// limiter of the client or answered with
+    /// `429 Too Many Requests`.
+    pub rate_limited: u64,
+    /// How long pastemyst took to respond
+    /// on average, `0` if nothing was sent.
+    pub average_latency: Duration,
+    /// Bytes sent in request bodies.
+    pub bytes_sent: u64,
+    /// Bytes read from response bodies.
+    pub bytes_received: u64,
+}
+
+/// The counters behind `ClientStats`.
+#[derive(Default)]
+struct Stats {
+    attempts: AtomicU64,
+    retries: AtomicU64,
+    rate_limited: AtomicU64,
+    latency_micros: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+/// The priority requests of a client are sent with,
+/// see `PasteMystClient::with_priority`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// User facing work, like answering
+    /// a command of a bot.
+    High,
+    /// Everything else, the default.
+    #[default]
+    Normal,
+    /// Bulk work, like backups, that
+    /// can wait for its turn.
+    Background,
+}
+
+/// How many requests of each priority may be in
+/// flight at once. Each priority has slots of its
+/// own, so background work can never take the slots
+/// of user facing requests. These are only caps on
+/// concurrency, a waiting high priority request
+/// doesn't jump ahead of background ones that are
+/// already in flight.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::num::NonZeroUsize;
+/// use pastemyst::client::*;
+///
+/// let caps = PriorityCaps { background: NonZeroUsize::new(1).unwrap(), ..Default::default() };
+/// let client = PasteMystClient::new().with_priority_caps(&caps);
+/// let backups = client.with_priority(Priority::Background);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PriorityCaps {
+    /// Slots for `Priority::High` requests.
+    pub high: NonZeroUsize,
+    /// Slots for `Priority::Normal` requests.
+    pub normal: NonZeroUsize,
+    /// Slots for `Priority::Background` requests.
+    pub background: NonZeroUsize,
+}
+
+impl Default for PriorityCaps {
+    fn default() -> Self {
+        PriorityCaps {
+            high: NonZeroUsize::new(8).unwrap(),
+            normal: NonZeroUsize::new(4).unwrap(),
+            background: NonZeroUsize::new(2).unwrap(),
+        }
+    }
+}
+
+/// The slots of each priority, shared
+/// by every clone of a client.
+struct PrioritySlots {
+    high: Semaphore,
+    normal: Semaphore,
+    background: Semaphore,
+}
+
+impl PrioritySlots {
+    fn get(&self, priority: Priority) -> &Semaphore {
+        match priority {
+            Priority::High => &self.high,
+            Priority::Normal => &self.normal,
+            Priority::Background => &self.background,
+        }
+    }
+}
+
+/// The http client that requests
+/// are dispatched through.
+#[derive(Clone)]
+enum Http {
+    Reqwest(reqwest::Client),
+    #[cfg(feature = "middleware")]
+    Middleware(reqwest_middleware::ClientWithMiddleware),
+    Transport(Arc<dyn Transport>),
+}
+
+impl Default for PasteMystClient {
+    fn default() -> Self { PasteMystClient::new() }
+}
+
+impl PasteMystClient {
+    /// Creates a new client with a `reqwest::Client`
+    /// built from the default `ClientOptions` and
+    /// no token.
+    pub fn new() -> Self {
+        let client = ClientOptions::default().build_http().expect("[pastemyst] Failed to build the http client");
+        PasteMystClient::from_reqwest(client)
+    }
+
+    /// Creates a new client that sends all
+    /// of its requests through the given
+    /// `reqwest::Client`.
+    pub fn from_reqwest(client: reqwest::Client) -> Self {
+        PasteMystClient::from_http(Http::Reqwest(client))
+    }
+
+    /// Creates a new client that sends all of its requests
+    /// through the given `ClientWithMiddleware`, which means
+    /// that every middleware you've registered on it runs
+    /// for each call made to pastemyst.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// let middleware_client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+    /// let client = PasteMystClient::from_middleware(middleware_client);
+    /// ```
+    #[cfg(feature = "middleware")]
+    pub fn from_middleware(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        PasteMystClient::from_http(Http::Middleware(client))
+    }
+
+    /// Creates a new client that sends all of its requests
+    /// through the given `Transport`, so that it can be used
+    /// without a tokio runtime.
+    ///
+    /// Bodies sent with progress reporting are streamed,
+    /// which transports don't support, the `_with_progress`
+    /// variants of `create_paste` and `edit_paste` fail
+    /// with `PasteMystError::Transport` on such a client.
+    pub fn from_transport(transport: impl Transport + 'static) -> Self {
+        PasteMystClient::from_http(Http::Transport(Arc::new(transport)))
+    }
+
+    /// Creates a new client around the http client
+    /// with every option left at its default.
+    fn from_http(http: Http) -> Self {
+        PasteMystClient {
+            http,
+            token: None,
+            instance: None,
+            rate_limit_retries: 0,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_response_size: None,
+            cache: Default::default(),
+            #[cfg(feature = "rate-limit")]
+            limiter: None,
+            slots: None,
+            priority: Priority::Normal,
+            stats: Default::default(),
+            hooks: Default::default(),
+            interceptors: Default::default(),
+            last_response: Default::default(),
+        }
+    }
+
+    /// Creates a new client with a `reqwest::Client`
+    /// built from the given options and no token.
+    pub fn with_options(options: &ClientOptions) -> ClientResult<Self> {
+        let mut client = PasteMystClient::from_reqwest(options.build_http()?);
+        client.max_response_size = options.max_response_size;
+        client.rate_limit_retries = options.rate_limit_retries;
+        Ok(client)
+    }
+
+    /// Sets the largest response body, in bytes, that is
+    /// read before failing with `PasteMystError::ResponseTooLarge`.
+    /// Unlike `with_options`, this works with clients built
+    /// from your own `reqwest::Client` as well.
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = Some(max_response_size);
+        self
+    }
+
+    /// Sets how many requests the batch operations of the
+    /// client (`get_pastes`, `fetch_pastes`, `delete_pastes`,
+    /// the searches and so on) have in flight at once,
+    /// `DEFAULT_CONCURRENCY` by default. `0` is treated
+    /// as `1`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::client::*;
+    ///
+    /// let client = PasteMystClient::new().with_concurrency(2);
+    /// assert_eq!(client.concurrency(), 2);
+    /// ```
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// How many requests the batch operations
+    /// of the client have in flight at once.
+    pub fn concurrency(&self) -> usize { self.concurrency }
+
+    /// Sends a request answered with `429 Too Many Requests`
+    /// again up to `retries` times, waiting for as long as
+    /// its `Retry-After` header asks (a second if it doesn't
+    /// say) before each attempt. Once they're used up the
+    /// call fails with `PasteMystError::RateLimited`. A
+    /// client built with `from_transport` waits with
+    /// `Transport::sleep`, the others with tokio.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use pastemyst::client::*;
+    /// use pastemyst::error::PasteMystError;
+    /// use pastemyst::transport::*;
+    ///
+    /// /// Refuses every other request.
+    /// struct Throttled(AtomicBool);
+    ///
+    /// impl Transport for Throttled {
+    ///     fn send(&self, _: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+    ///         let limited = !self.0.fetch_xor(true, Ordering::SeqCst);
+    ///         Box::pin(async move {
+    ///             let status = if limited { 429 } else { 200 };
+    ///             Ok(http::Response::builder().status(status).header("Retry-After", "0").body(b"[]".to_vec())?)
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = PasteMystClient::from_transport(Throttled(AtomicBool::new(false))).with_token("token");
+    ///     let error = client.list_self_pastes().await.unwrap_err();
+    ///     assert!(matches!(error, PasteMystError::RateLimited { retry_after: Some(_) }));
+    ///
+    ///     let client = PasteMystClient::from_transport(Throttled(AtomicBool::new(false)))
+    ///         .with_token("token")
+    ///         .with_rate_limit_retries(1);
+    ///     assert!(client.list_self_pastes().await.unwrap().is_empty());
+    ///     assert_eq!(client.stats().retries, 1);
+    /// }
+    /// ```
+    pub fn with_rate_limit_retries(mut self, retries: u32) -> Self {
+        self.rate_limit_retries = retries;
+        self
+    }
+
+    /// Limits the client to `per_second` requests per second,
+    /// allowing bursts of up to `burst` requests, so that an
+    /// application can't exceed the limits of pastemyst by
+    /// accident. Requests over the limit wait until they're
+    /// allowed instead of failing. The limiter is shared by
+    /// every clone of the client, even across threads. The
+    /// free functions are limited with
+    /// `connection::set_default_rate_limit` instead.
+    ///
+    /// This method is only available with
+    /// the `rate-limit` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::num::NonZeroU32;
+    /// use pastemyst::client::*;
+    ///
+    /// let client = PasteMystClient::new()
+    ///     .with_rate_limit(NonZeroU32::new(5).unwrap(), NonZeroU32::new(10).unwrap());
+    /// ```
+    #[cfg(feature = "rate-limit")]
+    pub fn with_rate_limit(mut self, per_second: std::num::NonZeroU32, burst: std::num::NonZeroU32) -> Self {
+        let quota = governor::Quota::per_second(per_second).allow_burst(burst);
+        self.limiter = Some(Arc::new(governor::RateLimiter::direct(quota)));
+        self
+    }
+
+    /// Caps how many requests of each priority the client
+    /// (and its clones) have in flight at once. Requests
+    /// over the cap wait for a slot of their priority.
+    pub fn with_priority_caps(mut self, caps: &PriorityCaps) -> Self {
+        self.slots = Some(Arc::new(PrioritySlots {
+            high: Semaphore::new(caps.high.get()),
+            normal: Semaphore::new(caps.normal.get()),
+            background: Semaphore::new(caps.background.get()),
+        }));
+        self
+    }
+
+    /// Returns a clone of the client whose requests take
+    /// the slots of the given priority. It shares the
+    /// slots, cache and connections of the client it
+    /// was cloned from. Without `with_priority_caps`
+    /// the priority has no effect.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new().with_priority_caps(&PriorityCaps::default());
+    ///     let interactive = client.with_priority(Priority::High);
+    ///     let paste = interactive.get_paste("hipfqanx").await?;
+    ///     println!("{}", paste.title);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_priority(&self, priority: Priority) -> Self {
+        let mut client = self.clone();
+        client.priority = priority;
+        client
+    }
+
+    /// Adds an interceptor that sees every request sent by
+    /// the client and every response it gets. Interceptors
+    /// run in the order they were added, after the priority
+    /// slots and rate limiter let the request through.
+    pub fn with_interceptor(mut self, interceptor: Interceptor) -> Self {
+        Arc::make_mut(&mut self.interceptors).push(interceptor);
+        self
+    }
+
+    /// Registers a hook that runs before every paste is
+    /// created or edited through the client, in the order
+    /// they were added. A hook can change the paste (add a
+    /// mandatory tag, redact something) or refuse it with a
+    /// `Violation`, in which case nothing is sent and
+    /// `PasteMystError::Violation` is returned. This is
+    /// where policies like "no secrets" or size limits can
+    /// be enforced for a whole application.
+    ///
+    /// Edits are handed to the hooks as a `CreateObject`
+    /// with the expiry of the paste being edited, changes
+    /// to the expiry are ignored as it can't be edited.
+    /// Clones made before the hook was added don't run it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::client::*;
+    /// use pastemyst::error::Violation;
+    ///
+    /// let mut client = PasteMystClient::new();
+    /// client.add_pre_upload_hook(|paste| {
+    ///     if paste.pasties.iter().any(|pasty| pasty.code.contains("BEGIN RSA PRIVATE KEY")) {
+    ///         return Err(Violation::new("no-secrets", "The paste contains a private key"));
+    ///     }
+    ///     if !paste.tags.iter().any(|tag| tag == "internal") {
+    ///         paste.tags.push(String::from("internal"));
+    ///     }
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn add_pre_upload_hook(
+        &mut self,
+        hook: impl Fn(&mut CreateObject) -> Result<(), Violation> + Send + Sync + 'static,
+    ) {
+        Arc::make_mut(&mut self.hooks).push(Arc::new(hook));
+    }
+
+    /// The status, headers and duration of the last response
+    /// received by the client or any of its clones, `None`
+    /// if nothing was sent yet.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     client.get_paste("hipfqanx").await?;
+    ///     if let Some(meta) = client.last_response_meta() {
+    ///         println!("{} in {:?}, {:?} requests left", meta.status, meta.duration, meta.rate_limit_remaining());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn last_response_meta(&self) -> Option<ResponseMeta> { self.last_response.lock().unwrap().clone() }
+
+    /// Reports how the client (and its clones) has been
+    /// used since it was created, so that you can monitor
+    /// your usage of pastemyst without instrumenting the
+    /// calls yourself.
+    pub fn stats(&self) -> ClientStats {
+        let attempts = self.stats.attempts.load(Ordering::Relaxed);
+        let latency_micros = self.stats.latency_micros.load(Ordering::Relaxed);
+        ClientStats {
+            attempts,
+            retries: self.stats.retries.load(Ordering::Relaxed),
+            rate_limited: self.stats.rate_limited.load(Ordering::Relaxed),
+            average_latency: Duration::from_micros(latency_micros.checked_div(attempts).unwrap_or(0)),
+            bytes_sent: self.stats.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.stats.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Counts a request that is about
+    /// to be sent again.
+    pub(crate) fn record_retry(&self) { self.stats.retries.fetch_add(1, Ordering::Relaxed); }
+
+    /// Sets the authorization token that is sent with
+    /// every request (`get_paste` only sends it when
+    /// it's needed). You can get it from your user
+    /// settings on [pastemyst](https://paste.myst.rs/user/settings).
+    pub fn with_token(mut self, auth_token: impl AsRef<str>) -> Self {
+        self.token = Some(ApiToken::new(auth_token.as_ref()));
+        self
+    }
+
+    /// Sends every request to another PasteMyst
+    /// instance instead of `DEFAULT_INSTANCE`,
+    /// e.g. `https://paste.example.com`.
+    pub fn with_instance(mut self, instance: &str) -> Self {
+        self.instance = Some(instance.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Uses the token and instance
+    /// of the config when they're set.
+    #[cfg(feature = "config")]
+    pub fn with_config(mut self, config: &crate::config::Config) -> Self {
+        if let Some(token) = &config.token { self = self.with_token(token); }
+        if let Some(instance) = &config.instance { self = self.with_instance(instance); }
+        self
+    }
+
+    /// Gets a paste from pastemyst, whether it's public
+    /// or private, so you don't need to know which one
+    /// it is ahead of time. The paste is fetched without
+    /// the token first, and if pastemyst refuses (or
+    /// hides) it and the client has a token, the fetch
+    /// is retried with it. This replaces both
+    /// `paste::get_paste` and `paste::get_private_paste`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new()
+    ///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+    ///     let public = client.get_paste("hipfqanx").await?;
+    ///     let private = client.get_paste("PrivatePasteID").await?;
+    ///     println!("{} and {}", public.title, private.title);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_paste(&self, id: &str) -> ClientResult<PasteObject> {
+        let response = self.fetch_paste(id).await?;
+        self.read_json(response).await
+    }
+
+    /// The same as `get_paste` except that `on_progress` is
+    /// called with the bytes read so far and the size of the
+    /// paste, if pastemyst sent it, as the paste is read.
+    /// This is meant for progress bars when fetching large
+    /// pastes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     let paste = client
+    ///         .get_paste_with_progress("hipfqanx", |done, total| match total {
+    ///             Some(total) => println!("{}/{} bytes", done, total),
+    ///             None => println!("{} bytes", done),
+    ///         })
+    ///         .await?;
+    ///     println!("{}", paste.title);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_paste_with_progress(
+        &self,
+        id: &str,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> ClientResult<PasteObject> {
+        let response = self.fetch_paste(id).await?;
+        self.read_json_with_progress(response, on_progress).await
+    }
+
+    /// Checks whether a paste exists without decoding
+    /// it, the same as `paste::paste_exists`. The token
+    /// of the client is sent, so its private pastes
+    /// are found too.
+    pub async fn paste_exists(&self, id: &str) -> ClientResult<bool> {
+        let request = self.request(Method::GET, &paste::parse_url(id)?)?;
+        let response = self.execute(request).await?;
+        if response.status() == StatusCode::NOT_FOUND { return Ok(false); }
+        error::check_status_async(response).await?;
+        Ok(true)
+    }
+
+    /// The link to a paste on the instance of
+    /// the client, see `PasteObject::url_on`.
+    pub fn paste_url(&self, paste: &PasteObject) -> String {
+        paste.url_on(self.instance.as_deref().unwrap_or(DEFAULT_INSTANCE))
+    }
+
+    /// Gets the contents of a single pasty as plain text,
+    /// the same as `paste::get_pasty_raw`. Private
+    /// pastes can't be read this way.
+    pub async fn get_pasty_raw(&self, paste_id: &str, pasty_id: &str) -> ClientResult<String> {
+        let request = self.anonymous_request(Method::GET, &paste::parse_raw_url(paste_id, pasty_id)?)?;
+        let response = error::check_status_async(self.execute(request).await?).await?;
+        let body = self.read_body(response, |_, _| {}).await?;
+        String::from_utf8(body).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error).into())
+    }
+
+    /// Sends the request behind `get_paste`,
+    /// retrying it with the token if needed.
+    async fn fetch_paste(&self, id: &str) -> ClientResult<Response> {
+        let url = paste::parse_url(id)?;
+        let mut response = self.execute(self.anonymous_request(Method::GET, &url)?).await?;
+        let refused = matches!(
+            response.status(),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND
+        );
+        if refused && self.token.is_some() {
+            self.record_retry();
+            response = self.execute(self.request(Method::GET, &url)?).await?;
+        }
+        error::check_status_async(response).await
+    }
+
+    /// Creates a paste on pastemyst, owned by the account
+    /// of the given token or by no one when it's `None`.
+    /// The token of the client is not used, this is the
+    /// call behind `create_paste` for when a single
+    /// client creates pastes for several accounts.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    /// use pastemyst::paste::*;
+    /// use pastemyst::token::ApiToken;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     let contents = PasteBuilder::new()
+    ///         .title("Notes")
+    ///         .pasty(PastyBuilder::new().code("Hello"))
+    ///         .build()?;
+    ///     let token = ApiToken::from_env().ok();
+    ///     let paste = client.create(&contents, token.as_ref()).await?;
+    ///     println!("{}", paste._id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create(&self, contents: &CreateObject, auth_token: Option<&ApiToken>) -> ClientResult<PasteObject> {
+        let request = self.create_request(contents, auth_token)?;
+        let response = error::check_status_async(self.execute(request).await?).await?;
+        self.read_json(response).await
+    }
+
+    /// Goes through everything `create_paste` does short of
+    /// sending the request: the pre-upload hooks, validation
+    /// and the request interceptors. The request is returned
+    /// instead, so that CI can check the pastes it would
+    /// create without reaching PasteMyst.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::client::*;
+    /// use pastemyst::paste::*;
+    ///
+    /// let client = PasteMystClient::new().with_token("my-secret-token");
+    /// let contents = PasteBuilder::new()
+    ///     .title("Notes")
+    ///     .pasty(PastyBuilder::new().code("Hello"))
+    ///     .build()
+    ///     .unwrap();
+    /// let request = client.dry_run(&contents).unwrap();
+    /// assert_eq!(request.url.as_str(), "https://paste.myst.rs/api/v2/paste");
+    /// assert_eq!(request.headers["content-type"], "application/json");
+    /// assert!(request.headers["authorization"].is_sensitive());
+    /// assert_eq!(request.body, contents.to_request_json().unwrap());
+    /// ```
+    pub fn dry_run(&self, contents: &CreateObject) -> ClientResult<DryRun> {
+        let mut request = self.create_request(contents, self.token.as_ref())?;
+        self.intercept(&mut request);
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+        Ok(DryRun {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: String::from_utf8_lossy(body).into_owned(),
+        })
+    }
+
+    /// Builds the request behind `create`, after
+    /// running the hooks and validating the paste.
+    fn create_request(&self, contents: &CreateObject, auth_token: Option<&ApiToken>) -> ClientResult<Request> {
+        let contents = self.run_hooks(contents.clone())?;
+        let body = contents.to_request_json()?;
+        let mut request = self.request_with_token(Method::POST, paste::SEND_ENDPOINT, auth_token)?;
+        set_json(&mut request, body);
+        Ok(request)
+    }
+
+    /// Creates a paste on pastemyst. If the client
+    /// has a token, the paste is owned by the
+    /// account the token belongs to.
+    pub async fn create_paste(&self, contents: &CreateObject) -> ClientResult<PasteObject> {
+        self.create(contents, self.token.as_ref()).await
+    }
+
+    /// The same as `create_paste` except that the paste is
+    /// uploaded in chunks and `on_progress` is called with
+    /// the bytes sent so far and the size of the whole body,
+    /// so that large pastes sent over slow links show some
+    /// movement. It's called with `0` sent once the paste
+    /// has been serialized. A client built with
+    /// `from_transport` hands the transport the whole body,
+    /// so it's only called once more, with every byte sent.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    /// use pastemyst::paste::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     let contents = CreateObject {
+    ///         title: String::from("Build log"),
+    ///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
+    ///         isPrivate: false,
+    ///         isPublic: false,
+    ///         tags: Vec::new(),
+    ///         pasties: vec![PastyObject {
+    ///             _id: String::from(""),
+    ///             language: String::from(pastemyst::data::language::PLAIN),
+    ///             title: String::from("build.log"),
+    ///             code: std::fs::read_to_string("build.log")?,
+    ///         }],
+    ///     };
+    ///     let paste = client
+    ///         .create_paste_with_progress(&contents, |sent, total| println!("{}/{} bytes", sent, total))
+    ///         .await?;
+    ///     println!("{}", paste.url());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_paste_with_progress(
+        &self,
+        contents: &CreateObject,
+        on_progress: impl FnMut(u64, u64) + Send + Sync + 'static,
+    ) -> ClientResult<PasteObject> {
+        let contents = self.run_hooks(contents.clone())?;
+        let body = contents.to_request_json()?;
+        let mut request = self.request(Method::POST, paste::SEND_ENDPOINT)?;
+        self.set_json_with_progress(&mut request, body, on_progress);
+        let response = error::check_status_async(self.execute(request).await?).await?;
+        self.read_json(response).await
+    }
+
+    /// Edits a paste owned by the account
+    /// of the client's token. Refer to
+    /// `paste::edit_paste` for the details, the
+    /// pasty ids are checked against the current
+    /// paste before it's edited.
+    pub async fn edit_paste(&self, edit_info: &EditObject, id: &str) -> ClientResult<PasteObject> {
+        let current = self.get_paste(id).await?;
+        edit_info.validate_against(&current)?;
+        let edit_info = self.run_edit_hooks(edit_info, &current)?;
+        let mut request = self.request(Method::PATCH, &paste::parse_url(id)?)?;
+        set_json(&mut request, serde_json::to_string(&edit_info)?);
+        self.send_edit(request).await
+    }
+
+    /// The same as `edit_paste` except that the edit is
+    /// uploaded in chunks and `on_progress` is called with
+    /// the bytes sent so far and the size of the whole body,
+    /// see `create_paste_with_progress`.
+    pub async fn edit_paste_with_progress(
+        &self,
+        edit_info: &EditObject,
+        id: &str,
+        on_progress: impl FnMut(u64, u64) + Send + Sync + 'static,
+    ) -> ClientResult<PasteObject> {
+        let current = self.get_paste(id).await?;
+        edit_info.validate_against(&current)?;
+        let edit_info = self.run_edit_hooks(edit_info, &current)?;
+        let mut request = self.request(Method::PATCH, &paste::parse_url(id)?)?;
+        self.set_json_with_progress(&mut request, serde_json::to_string(&edit_info)?, on_progress);
+        self.send_edit(request).await
+    }
+
+    /// Runs the pre-upload hooks on a paste
+    /// that is about to be created.
+    fn run_hooks(&self, mut contents: CreateObject) -> ClientResult<CreateObject> {
+        for hook in self.hooks.iter() { hook(&mut contents)?; }
+        Ok(contents)
+    }
+
+    /// Runs the pre-upload hooks on an edit of
+    /// the given paste, see `add_pre_upload_hook`.
+    fn run_edit_hooks(&self, edit_info: &EditObject, current: &PasteObject) -> ClientResult<EditObject> {
+        if self.hooks.is_empty() { return Ok(edit_info.clone()); }
+        let contents = self.run_hooks(CreateObject {
+            title: edit_info.title.clone(),
+            expiresIn: current.expiresIn.parse().unwrap_or_default(),
+            isPrivate: edit_info.isPrivate,
+            isPublic: edit_info.isPublic,
+            tags: edit_info.tags.clone(),
+            pasties: edit_info.pasties.clone(),
+        })?;
+        Ok(EditObject {
+            title: contents.title,
+            isPrivate: contents.isPrivate,
+            isPublic: contents.isPublic,
+            tags: contents.tags,
+            pasties: contents.pasties,
+        })
+    }
+
+    /// Sends an edit request and
+    /// caches the edited paste.
+    async fn send_edit(&self, request: Request) -> ClientResult<PasteObject> {
+        let response = error::check_status_async(self.execute(request).await?).await?;
+        let paste: PasteObject = self.read_json(response).await?;
+        self.cache.lock().unwrap().insert(paste._id.clone(), paste.clone());
+        Ok(paste)
+    }
+
+    /// Deletes a paste owned by the account of
+    /// the client's token and returns whether
+    /// it was deleted, and if not, why.
+    pub async fn delete_paste(&self, id: &str) -> ClientResult<DeleteOutcome> {
+        let request = self.request(Method::DELETE, &paste::parse_url(id)?)?;
+        let response = self.execute(request).await?;
+        let outcome = DeleteOutcome::from_status(error::check_status_async(response).await.map(|_| ()))?;
+        if outcome.is_deleted() { self.cache.lock().unwrap().remove(id); }
+        Ok(outcome)
+    }
+
+    /// Gets a user from pastemyst. The same as
+    /// `user::get_user`, a user that does not exist
+    /// is returned as an empty `UserObject` and a
+    /// warning is emitted.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let user = PasteMystClient::new().get_user("ANF-Studios").await?;
+    ///     println!("{}", user.avatarUrl);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "user")]
+    pub async fn get_user(&self, username: &str) -> ClientResult<UserObject> {
+        let response = self.execute(self.request(Method::GET, &user::parse_user(username))?).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            crate::connection::warn(format_args!("The user '{}' does not exist and an empty object is returned.", username));
+            return Ok(UserObject::default());
+        }
+        self.read_json(error::check_status_async(response).await?).await
+    }
+
+    /// Gets a user, `None` is returned if the user
+    /// doesn't exist, see `user::try_get_user`.
+    #[cfg(feature = "user")]
+    pub async fn try_get_user(&self, username: &str) -> ClientResult<Option<UserObject>> {
+        let response = self.execute(self.request(Method::GET, &user::parse_user(username))?).await?;
+        if response.status() == StatusCode::NOT_FOUND { return Ok(None); }
+        Ok(Some(self.read_json(error::check_status_async(response).await?).await?))
+    }
+
+    /// Gets the account of the client's token, see
+    /// `user::get_self_user`. `PasteMystError::MissingToken`
+    /// is returned if the client has no token.
+    #[cfg(feature = "user")]
+    pub async fn get_self_user(&self) -> ClientResult<SelfUserObject> {
+        if self.token.is_none() { return Err(PasteMystError::MissingToken); }
+        let response = self.execute(self.request(Method::GET, &user::parse_user(user::SELF))?).await?;
+        self.read_json(error::check_status_async(response).await?).await
+    }
+
+    /// Checks whether a user exists on pastemyst.
+    #[cfg(feature = "user")]
+    pub async fn user_exists(&self, username: &str) -> ClientResult<bool> {
+        let request = self.anonymous_request(Method::GET, &user::parse_user_get(username))?;
+        let response = self.execute(request).await?;
+        if response.status() == StatusCode::NOT_FOUND { return Ok(false); }
+        error::check_status_async(response).await?;
+        Ok(true)
+    }
+
+    /// Gets information on a language
+    /// *supported by PasteMyst* by its name.
+    #[cfg(feature = "data")]
+    pub async fn get_language_by_name(&self, language_name: &str) -> ClientResult<DataObject> {
+        let request = self.anonymous_request(Method::GET, &data::parse_url(language_name, data::LookupBy::Name))?;
+        let response = self.execute(request).await?;
+        self.read_json(error::check_status_async(response).await?).await
+    }
+
+    /// Gets information on a language *supported
+    /// by PasteMyst* by one of its extensions.
+    #[cfg(feature = "data")]
+    pub async fn get_language_by_extension(&self, lang_extension: &str) -> ClientResult<DataObject> {
+        let request = self.anonymous_request(Method::GET, &data::parse_url(lang_extension, data::LookupBy::Extension))?;
+        let response = self.execute(request).await?;
+        self.read_json(error::check_status_async(response).await?).await
+    }
+
+    /// Gets information on every language *supported by
+    /// PasteMyst* that uses an editor mode, the same as
+    /// `data::get_language_by_mode`.
+    #[cfg(feature = "data")]
+    pub async fn get_language_by_mode(&self, mode: &str) -> ClientResult<Vec<DataObject>> {
+        stream::iter(data::languages_for_mode(mode))
+            .map(|name| self.get_language_by_name(name))
+            .buffered(self.concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Converts the expiry of a paste created at
+    /// `created_at` to the unix time it expires at,
+    /// the same as `time::expires_into_unix`.
+    #[cfg(feature = "time")]
+    pub async fn expires_into_unix(&self, created_at: u64, expires_in: impl AsRef<str>) -> ClientResult<u64> {
+        let expires_in: time::ExpiresIn = expires_in.as_ref().parse()?;
+        let request = self.anonymous_request(Method::GET, &time::parse_time(created_at, expires_in))?;
+        let response = self.execute(request).await?;
+        let time: TimeObject = self.read_json(error::check_status_async(response).await?).await?;
+        Ok(time.result)
+    }
+
+    /// Gets the ids of all the pastes owned
+    /// by the account of the client's token,
+    /// in the order PasteMyst lists them in.
+    /// Use `get_self_pastes` to sort them.
+    /// Returns `PasteMystError::MissingToken`
+    /// if the client has no token.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new()
+    ///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+    ///     for id in client.list_self_pastes().await? {
+    ///         println!("https://paste.myst.rs/{}", id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_self_pastes(&self) -> ClientResult<Vec<String>> {
+        if self.token.is_none() { return Err(PasteMystError::MissingToken); }
+        let request = self.request(Method::GET, &format!("{}self/pastes", user::USER_ENDPOINT))?;
+        let response = error::check_status_async(self.execute(request).await?).await?;
+        self.read_json(response).await
+    }
+
+    /// Gets all the pastes owned by the account of the
+    /// client's token, sorted, filtered and paged through
+    /// according to the given `ListOptions`. Every paste
+    /// has to be fetched to be sorted, which is done a few
+    /// at a time and cached the same way `search_self_pastes`
+    /// does.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    /// use pastemyst::search::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new()
+    ///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+    ///     let options = ListOptions { sort_by: SortBy::Stars, limit: Some(5), ..Default::default() };
+    ///     for paste in client.get_self_pastes(&options).await? {
+    ///         println!("{} ({} stars)", paste.title, paste.stars);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_self_pastes(&self, options: &ListOptions) -> ClientResult<Vec<PasteObject>> {
+        Ok(options.apply(self.fetch_self_pastes().await?))
+    }
+
+    /// Searches through the pastes owned by the account
+    /// of the client's token and returns the ones matching
+    /// the `Query`, newest first. PasteMyst has no server
+    /// side search, so every paste is fetched, a few at a
+    /// time, and filtered locally.
+    ///
+    /// Fetched pastes are cached by the client (and its
+    /// clones) so that searching again only fetches the
+    /// pastes it hasn't seen yet. Pastes edited or deleted
+    /// through the client are kept up to date, use
+    /// `clear_cache` if they were changed elsewhere.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    /// use pastemyst::search::Query;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new()
+    ///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+    ///     let query = Query {
+    ///         language: Some(String::from(pastemyst::data::language::RUST)),
+    ///         ..Default::default()
+    ///     };
+    ///     for paste in client.search_self_pastes(&query).await? {
+    ///         println!("{}", paste.title);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn search_self_pastes(&self, query: &Query) -> ClientResult<Vec<PasteObject>> {
+        self.search_self_pastes_with(query, &ListOptions::default()).await
+    }
+
+    /// The same as `search_self_pastes` except that
+    /// the matching pastes are sorted, filtered and
+    /// paged through according to the `ListOptions`.
+    pub async fn search_self_pastes_with(
+        &self,
+        query: &Query,
+        options: &ListOptions,
+    ) -> ClientResult<Vec<PasteObject>> {
+        let mut pastes = self.fetch_self_pastes().await?;
+        pastes.retain(|paste| query.matches(paste));
+        Ok(options.apply(pastes))
+    }
+
+    /// Gets many pastes, with at most `concurrency`
+    /// requests in flight at once, and returns them
+    /// in the order of the ids. Fails as soon as one
+    /// of them can't be fetched.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new().with_concurrency(4);
+    ///     let ids = vec![String::from("hipfqanx"), String::from("cys6ypb4")];
+    ///     for paste in client.get_pastes(&ids).await? {
+    ///         println!("{}", paste.title);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_pastes(&self, ids: &[String]) -> ClientResult<Vec<PasteObject>> {
+        stream::iter(ids).map(|id| self.get_paste(id)).buffered(self.concurrency).try_collect().await
+    }
+
+    /// Fetches many pastes, a few at a time, and hands
+    /// each one to `on_paste` in the order of the ids.
+    /// With a checkpoint file, every paste `on_paste`
+    /// succeeded with is recorded in it and skipped when
+    /// the same call is made again, so an interrupted
+    /// run doesn't start from scratch.
+    ///
+    /// Returns how many pastes were handed
+    /// to `on_paste` in this run.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     let ids = vec![String::from("hipfqanx"), String::from("cys6ypb4")];
+    ///     client
+    ///         .fetch_pastes(&ids, Some(Path::new("fetch.checkpoint")), |paste| {
+    ///             println!("{}", paste.title);
+    ///             Ok(())
+    ///         })
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn fetch_pastes(
+        &self,
+        ids: &[String],
+        checkpoint: Option<&Path>,
+        mut on_paste: impl FnMut(PasteObject) -> ClientResult<()>,
+    ) -> ClientResult<usize> {
+        let mut checkpoint = Checkpoint::open_optional(checkpoint)?;
+        let pending: Vec<&String> = ids.iter().filter(|id| !checkpoint.is_done(id)).collect();
+        let mut pastes = stream::iter(pending)
+            .map(|id| async move { self.get_paste(id).await.map(|paste| (id, paste)) })
+            .buffered(self.concurrency);
+        let mut fetched: usize = 0;
+        while let Some((id, paste)) = pastes.try_next().await? {
+            on_paste(paste)?;
+            checkpoint.mark_done(id)?;
+            fetched += 1;
+        }
+        Ok(fetched)
+    }
+
+    /// Backs up many pastes to a directory, each one to
+    /// a `<id>.json` file in the format pastemyst sends
+    /// it in. With a checkpoint file, pastes backed up
+    /// in an earlier run are skipped, see `fetch_pastes`.
+    ///
+    /// Returns how many pastes were
+    /// backed up in this run.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new()
+    ///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+    ///     let ids = client.list_self_pastes().await?;
+    ///     let count = client
+    ///         .backup_pastes(&ids, Path::new("backup"), Some(Path::new("backup.checkpoint")))
+    ///         .await?;
+    ///     println!("Backed up {} pastes", count);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn backup_pastes(&self, ids: &[String], dir: &Path, checkpoint: Option<&Path>) -> ClientResult<usize> {
+        std::fs::create_dir_all(dir)?;
+        self.fetch_pastes(ids, checkpoint, |paste| {
+            std::fs::write(dir.join(format!("{}.json", paste._id)), serde_json::to_string_pretty(&paste)?)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Gets a paste and writes each of its pasties to a
+    /// file in the directory, see `PasteObject::save_to_dir`.
+    /// Returns the paths written.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use pastemyst::client::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> ClientResult<()> {
+    ///     let client = PasteMystClient::new();
+    ///     for path in client.download_paste("hipfqanx", Path::new("hipfqanx")).await? {
+    ///         println!("{}", path.display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn download_paste(&self, id: &str, dir: &Path) -> ClientResult<Vec<PathBuf>> {
+        self.get_paste(id).await?.save_to_dir(dir)
+    }
+
+    /// Deletes many pastes owned by the account of the
+    /// client's token, a few at a time, and returns the
+    /// outcome of each one deleted in this run. With a
+    /// checkpoint file, pastes that were deleted (or
+    /// already gone) in an earlier run are skipped.
+    pub async fn delete_pastes(
+        &self,
+        ids: &[String],
+        checkpoint: Option<&Path>,
+    ) -> ClientResult<Vec<(String, DeleteOutcome)>> {
+        let mut checkpoint = Checkpoint::open_optional(checkpoint)?;
+        let pending: Vec<&String> = ids.iter().filter(|id| !checkpoint.is_done(id)).collect();
+        let mut deletions = stream::iter(pending)
+            .map(|id| async move { self.delete_paste(id).await.map(|outcome| (id, outcome)) })
+            .buffered(self.concurrency);
+        let mut outcomes: Vec<(String, DeleteOutcome)> = Vec::new();
+        while let Some((id, outcome)) = deletions.try_next().await? {
+            if matches!(outcome, DeleteOutcome::Deleted | DeleteOutcome::NotFound) { checkpoint.mark_done(id)?; }
+            outcomes.push((id.clone(), outcome));
+        }
+        Ok(outcomes)
+    }
+
+    /// Clears the pastes cached by
+    /// `search_self_pastes`.
+    pub fn clear_cache(&self) { self.cache.lock().unwrap().clear(); }
+
+    /// Fetches every paste owned by the account
+    /// of the client's token, going through the
+    /// cache.
+    async fn fetch_self_pastes(&self) -> ClientResult<Vec<PasteObject>> {
+        let ids = self.list_self_pastes().await?;
+        stream::iter(ids)
+            .map(|id| async move { self.get_paste_cached(&id).await })
+            .buffered(self.concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Gets a paste from the cache, or
+    /// fetches and caches it if it's
+    /// not there yet.
+    async fn get_paste_cached(&self, id: &str) -> ClientResult<PasteObject> {
+        if let Some(paste) = self.cache.lock().unwrap().get(id) {
+            return Ok(paste.clone());
+        }
+        let paste = self.get_paste(id).await?;
+        self.cache.lock().unwrap().insert(id.to_string(), paste.clone());
+        Ok(paste)
+    }
+
+    /// Creates a request with the authorization
+    /// header set when the client has a token.
+    fn request(&self, method: Method, url: &str) -> ClientResult<Request> {
+        self.request_with_token(method, url, self.token.as_ref())
+    }
+
+    /// Creates a request with the authorization
+    /// header set to the given token, if any.
+    fn request_with_token(&self, method: Method, url: &str, auth_token: Option<&ApiToken>) -> ClientResult<Request> {
+        let mut request = self.anonymous_request(method, url)?;
+        if let Some(token) = auth_token {
+            let mut value = HeaderValue::from_str(token.expose()).map_err(|_| PasteMystError::InvalidToken)?;
+            value.set_sensitive(true);
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+        Ok(request)
+    }
+
+    /// Creates a request without
+    /// the authorization header.
+    fn anonymous_request(&self, method: Method, url: &str) -> ClientResult<Request> {
+        let url = match &self.instance {
+            Some(instance) => url.replacen(DEFAULT_INSTANCE, instance, 1),
+            None => url.to_string(),
+        };
+        let url = Url::parse(&url).map_err(|_| PasteMystError::InvalidUrl(url.clone()))?;
+        Ok(Request::new(method, url))
+    }
+
+    /// Sets the body of a request to the given json
+    /// string, sent in chunks that are reported to
+    /// `on_progress` as they're handed to the
+    /// connection. A transport only takes whole
+    /// bodies, so the progress is reported at once.
+    fn set_json_with_progress(
+        &self,
+        request: &mut Request,
+        body: String,
+        mut on_progress: impl FnMut(u64, u64) + Send + Sync + 'static,
+    ) {
+        let total = body.len() as u64;
+        if let Http::Transport(_) = &self.http {
+            on_progress(0, total);
+            set_json(request, body);
+            on_progress(total, total);
+            return;
+        }
+        let chunks: Vec<Vec<u8>> = body.as_bytes().chunks(UPLOAD_CHUNK_SIZE).map(<[u8]>::to_vec).collect();
+        let mut sent: u64 = 0;
+        on_progress(sent, total);
+        let chunks = stream::iter(chunks).map(move |chunk| {
+            sent += chunk.len() as u64;
+            on_progress(sent, total);
+            Ok::<Vec<u8>, std::io::Error>(chunk)
+        });
+        self.stats.bytes_sent.fetch_add(total, Ordering::Relaxed);
+        request.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        request.headers_mut().insert(CONTENT_LENGTH, HeaderValue::from(total));
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(chunks));
+    }
+
+    /// Reads a json response body, failing as soon as
+    /// it's larger than `max_response_size` instead of
+    /// reading it into memory first.
+    async fn read_json<T: DeserializeOwned>(&self, response: Response) -> ClientResult<T> {
+        self.read_json_with_progress(response, |_, _| {}).await
+    }
+
+    /// The same as `read_json`, reporting the
+    /// bytes read after every chunk.
+    async fn read_json_with_progress<T: DeserializeOwned>(
+        &self,
+        response: Response,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> ClientResult<T> {
+        Ok(serde_json::from_slice(&self.read_body(response, on_progress).await?)?)
+    }
+
+    /// Reads a response body, failing as soon
+    /// as it's larger than `max_response_size`.
+    async fn read_body(
+        &self,
+        mut response: Response,
+        mut on_progress: impl FnMut(u64, Option<u64>),
+    ) -> ClientResult<Vec<u8>> {
+        let total = response.content_length();
+        let limit = self.max_response_size.unwrap_or(usize::MAX);
+        if total.is_some_and(|length| length > limit as u64) {
+            return Err(PasteMystError::ResponseTooLarge { limit });
+        }
+        let mut body: Vec<u8> = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            self.stats.bytes_received.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+            if body.len() + chunk.len() > limit { return Err(PasteMystError::ResponseTooLarge { limit }); }
+            body.extend_from_slice(&chunk);
+            on_progress(body.len() as u64, total);
+        }
+        Ok(body)
+    }
+
+    /// Sends a request through the underlying http
+    /// client, once there is a slot for its priority,
+    /// and sends it again while it's rate limited and
+    /// `rate_limit_retries` allows it.
+    async fn execute(&self, mut request: Request) -> ClientResult<Response> {
+        let _permit = match &self.slots {
+            Some(slots) => Some(slots.get(self.priority).acquire().await.expect("[pastemyst] Priority slots closed")),
+            None => None,
+        };
+        let mut retries = self.rate_limit_retries;
+        loop {
+            let retry = if retries > 0 { request.try_clone() } else { None };
+            let response = self.execute_once(request).await?;
+            match retry {
+                Some(next) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    self.record_retry();
+                    self.wait(crate::connection::rate_limit_delay(response.headers())).await;
+                    request = next;
+                    retries -= 1;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    /// Waits before a rate limited request is sent again,
+    /// with the timer of the transport if the client has
+    /// one so that it doesn't need a tokio runtime.
+    async fn wait(&self, delay: Duration) {
+        match &self.http {
+            Http::Transport(transport) => transport.sleep(delay).await,
+            _ => tokio::time::sleep(delay).await,
+        }
+    }
+
+    /// Hands the request to the `on_request`
+    /// closure of every interceptor.
+    fn intercept(&self, request: &mut Request) {
+        for callback in self.interceptors.iter().filter_map(|interceptor| interceptor.request.as_ref()) {
+            callback(request);
+        }
+    }
+
+    /// Sends a request once, as soon
+    /// as the rate limiter allows it.
+    async fn execute_once(&self, mut request: Request) -> ClientResult<Response> {
+        #[cfg(feature = "rate-limit")]
+        if let Some(limiter) = &self.limiter {
+            if limiter.check().is_err() {
+                self.stats.rate_limited.fetch_add(1, Ordering::Relaxed);
+                limiter.until_ready().await;
+            }
+        }
+        self.intercept(&mut request);
+        let body_size = request.body().and_then(|body| body.as_bytes()).map_or(0, |body| body.len());
+        self.stats.attempts.fetch_add(1, Ordering::Relaxed);
+        self.stats.bytes_sent.fetch_add(body_size as u64, Ordering::Relaxed);
+        #[cfg(feature = "tracing")]
+        let span = crate::connection::request_span(request.method(), request.url());
+        let started = Instant::now();
+        let sending = async {
+            Ok::<Response, PasteMystError>(match &self.http {
+                Http::Reqwest(client) => client.execute(request).await?,
+                #[cfg(feature = "middleware")]
+                Http::Middleware(client) => client.execute(request).await?,
+                Http::Transport(transport) => transport::send(transport.as_ref(), request).await?,
+            })
+        };
+        #[cfg(feature = "tracing")]
+        let sending = tracing::Instrument::instrument(sending, span.clone());
+        let response = sending.await;
+        let elapsed = started.elapsed();
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &response { tracing::warn!(parent: &span, %error, "request failed"); }
+        let response = response?;
+        #[cfg(feature = "tracing")]
+        crate::connection::record_response(&span, Ok(response.status()), elapsed);
+        self.stats.latency_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        for callback in self.interceptors.iter().filter_map(|interceptor| interceptor.response.as_ref()) {
+            callback(&response, elapsed);
+        }
+        *self.last_response.lock().unwrap() = Some(ResponseMeta {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            duration: elapsed,
+        });
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            self.stats.rate_limited.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::ClientWithMiddleware> for PasteMystClient {
+    fn from(client: reqwest_middleware::ClientWithMiddleware) -> Self {
+        PasteMystClient::from_middleware(client)
+    }
+}
+
+impl From<reqwest::Client> for PasteMystClient {
+    fn from(client: reqwest::Client) -> Self { PasteMystClient::from_reqwest(client) }
+}
+
+/// A reusable client to talk to [pastemyst](https://paste.myst.rs)
+/// synchronously. It wraps a `PasteMystClient` and runs its calls
+/// on a runtime of its own, so the connections are reused across
+/// calls the same way, and everything configured on the wrapped
+/// client (token, hooks, interceptors, limits, etc.) applies.
+///
+/// Calling its methods from within an async runtime panics,
+/// use `PasteMystClient` there instead. Cloning the client
+/// is cheap, the runtime and the connection pool are shared.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::client::*;
+///
+/// fn main() -> ClientResult<()> {
+///     let client = PasteMystBlockingClient::new()?
+///         .with_token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+///     let paste = client.get_paste("hipfqanx")?;
+///     let user = client.get_user("ANF-Studios")?;
+///     println!("{} by {}", paste.title, user.username);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "blocking")]
+#[derive(Clone)]
+pub struct PasteMystBlockingClient {
+    client: PasteMystClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+#[cfg(feature = "blocking")]
+impl PasteMystBlockingClient {
+    /// Creates a new client with a default
+    /// `reqwest::Client` and no token.
+    pub fn new() -> ClientResult<Self> { PasteMystBlockingClient::from_client(PasteMystClient::new()) }
+
+    /// Creates a new client that sends all of its
+    /// requests through the given `PasteMystClient`.
+    pub fn from_client(client: PasteMystClient) -> ClientResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(PasteMystBlockingClient { client, runtime: Arc::new(runtime) })
+    }
+
+    /// Sets the authorization token that
+    /// is sent with every request.
+    pub fn with_token(mut self, auth_token: impl AsRef<str>) -> Self {
+        self.client = self.client.with_token(auth_token);
+        self
+    }
+
+    /// Sets how many requests batch operations have in
+    /// flight, see `PasteMystClient::with_concurrency`.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.client = self.client.with_concurrency(concurrency);
+        self
+    }
+
+    /// Sends every request to another PasteMyst
+    /// instance, see `PasteMystClient::with_instance`.
+    pub fn with_instance(mut self, instance: &str) -> Self {
+        self.client = self.client.with_instance(instance);
+        self
+    }
+
+    /// Uses the token and instance of the config,
+    /// see `PasteMystClient::with_config`.
+    #[cfg(feature = "config")]
+    pub fn with_config(mut self, config: &crate::config::Config) -> Self {
+        self.client = self.client.with_config(config);
+        self
+    }
+
+    /// The asynchronous client the
+    /// calls are made through.
+    pub fn client(&self) -> &PasteMystClient { &self.client }
+
+    /// Gets a paste, whether it's public or
+    /// private, see `PasteMystClient::get_paste`.
+    pub fn get_paste(&self, id: &str) -> ClientResult<PasteObject> { self.runtime.block_on(self.client.get_paste(id)) }
+
+    /// Gets many pastes a few at a time, see
+    /// `PasteMystClient::get_pastes`.
+    pub fn get_pastes(&self, ids: &[String]) -> ClientResult<Vec<PasteObject>> {
+        self.runtime.block_on(self.client.get_pastes(ids))
+    }
+
+    /// Checks whether a paste exists, see
+    /// `PasteMystClient::paste_exists`.
+    pub fn paste_exists(&self, id: &str) -> ClientResult<bool> { self.runtime.block_on(self.client.paste_exists(id)) }
+
+    /// The link to a paste on the instance of the
+    /// client, see `PasteMystClient::paste_url`.
+    pub fn paste_url(&self, paste: &PasteObject) -> String { self.client.paste_url(paste) }
+
+    /// Builds the request that would create a paste
+    /// without sending it, see `PasteMystClient::dry_run`.
+    pub fn dry_run(&self, contents: &CreateObject) -> ClientResult<DryRun> { self.client.dry_run(contents) }
+
+    /// Gets the contents of a pasty, see
+    /// `PasteMystClient::get_pasty_raw`.
+    pub fn get_pasty_raw(&self, paste_id: &str, pasty_id: &str) -> ClientResult<String> {
+        self.runtime.block_on(self.client.get_pasty_raw(paste_id, pasty_id))
+    }
+
+    /// Downloads the pasties of a paste, see
+    /// `PasteMystClient::download_paste`.
+    pub fn download_paste(&self, id: &str, dir: &Path) -> ClientResult<Vec<PathBuf>> {
+        self.runtime.block_on(self.client.download_paste(id, dir))
+    }
+
+    /// Creates a paste for the given token, see
+    /// `PasteMystClient::create`.
+    pub fn create(&self, contents: &CreateObject, auth_token: Option<&ApiToken>) -> ClientResult<PasteObject> {
+        self.runtime.block_on(self.client.create(contents, auth_token))
+    }
+
+    /// Creates a paste, see
+    /// `PasteMystClient::create_paste`.
+    pub fn create_paste(&self, contents: &CreateObject) -> ClientResult<PasteObject> {
+        self.runtime.block_on(self.client.create_paste(contents))
+    }
+
+    /// Edits a paste, see
+    /// `PasteMystClient::edit_paste`.
+    pub fn edit_paste(&self, edit_info: &EditObject, id: &str) -> ClientResult<PasteObject> {
+        self.runtime.block_on(self.client.edit_paste(edit_info, id))
+    }
+
+    /// Deletes a paste, see
+    /// `PasteMystClient::delete_paste`.
+    pub fn delete_paste(&self, id: &str) -> ClientResult<DeleteOutcome> {
+        self.runtime.block_on(self.client.delete_paste(id))
+    }
+
+    /// Gets the ids of the pastes of the token's
+    /// account, see `PasteMystClient::list_self_pastes`.
+    pub fn list_self_pastes(&self) -> ClientResult<Vec<String>> { self.runtime.block_on(self.client.list_self_pastes()) }
+
+    /// Gets a user, see
+    /// `PasteMystClient::get_user`.
+    #[cfg(feature = "user")]
+    pub fn get_user(&self, username: &str) -> ClientResult<UserObject> {
+        self.runtime.block_on(self.client.get_user(username))
+    }
+
+    /// Gets a user, or `None` if it doesn't
+    /// exist, see `PasteMystClient::try_get_user`.
+    #[cfg(feature = "user")]
+    pub fn try_get_user(&self, username: &str) -> ClientResult<Option<UserObject>> {
+        self.runtime.block_on(self.client.try_get_user(username))
+    }
+
+    /// Gets the account of the client's token,
+    /// see `PasteMystClient::get_self_user`.
+    #[cfg(feature = "user")]
+    pub fn get_self_user(&self) -> ClientResult<SelfUserObject> { self.runtime.block_on(self.client.get_self_user()) }
+
+    /// Checks whether a user exists, see
+    /// `PasteMystClient::user_exists`.
+    #[cfg(feature = "user")]
+    pub fn user_exists(&self, username: &str) -> ClientResult<bool> {
+        self.runtime.block_on(self.client.user_exists(username))
+    }
+
+    /// Gets a language by its name, see
+    /// `PasteMystClient::get_language_by_name`.
+    #[cfg(feature = "data")]
+    pub fn get_language_by_name(&self, language_name: &str) -> ClientResult<DataObject> {
+        self.runtime.block_on(self.client.get_language_by_name(language_name))
+    }
+
+    /// Gets a language by its extension, see
+    /// `PasteMystClient::get_language_by_extension`.
+    #[cfg(feature = "data")]
+    pub fn get_language_by_extension(&self, lang_extension: &str) -> ClientResult<DataObject> {
+        self.runtime.block_on(self.client.get_language_by_extension(lang_extension))
+    }
+
+    /// Gets the languages of an editor mode, see
+    /// `PasteMystClient::get_language_by_mode`.
+    #[cfg(feature = "data")]
+    pub fn get_language_by_mode(&self, mode: &str) -> ClientResult<Vec<DataObject>> {
+        self.runtime.block_on(self.client.get_language_by_mode(mode))
+    }
+
+    /// Converts an expiry to a unix time, see
+    /// `PasteMystClient::expires_into_unix`.
+    #[cfg(feature = "time")]
+    pub fn expires_into_unix(&self, created_at: u64, expires_in: impl AsRef<str>) -> ClientResult<u64> {
+        self.runtime.block_on(self.client.expires_into_unix(created_at, expires_in))
+    }
+}
+
+/// Sets the body of a request
+/// to the given json string.
+fn set_json(request: &mut Request, body: String) {
+    request.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    *request.body_mut() = Some(body.into());
+}
@@ -0,0 +1,209 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::PasteMystError;
+use crate::paste::CreateObject;
+use crate::time::ExpiresIn;
+use crate::token::ApiToken;
+
+/// The environment variable that points
+/// `Config::load` to another file.
+pub const CONFIG_ENV_VAR: &str = "PASTEMYST_CONFIG";
+
+/// Who can see a paste created
+/// with the defaults of a `Config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Anyone with the link.
+    Unlisted,
+    /// Only the owner.
+    Private,
+    /// Anyone with the link, and it's
+    /// listed on the owner's profile.
+    Public,
+}
+
+/// The settings shared by the tools built on this crate,
+/// read from `~/.config/pastemyst/config.toml` (or the
+/// file `PASTEMYST_CONFIG` points to). Every field is
+/// optional, a missing file is the same as an empty one.
+///
+/// ```toml
+/// token = "Your PasteMyst Token"
+/// expires_in = "1w"
+/// visibility = "private"
+/// instance = "https://paste.example.com"
+/// ```
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::client::*;
+/// use pastemyst::config::Config;
+///
+/// #[tokio::main]
+/// async fn main() -> ClientResult<()> {
+///     let config = Config::load()?;
+///     let client = PasteMystClient::new().with_config(&config);
+///     for id in client.list_self_pastes().await? {
+///         println!("{}", id);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// The authorization token
+    /// of the account to use.
+    pub token: Option<ApiToken>,
+    /// How long new pastes last.
+    pub expires_in: Option<ExpiresIn>,
+    /// Who can see new pastes.
+    pub visibility: Option<Visibility>,
+    /// The url of the PasteMyst instance to use
+    /// instead of `https://paste.myst.rs`, only
+    /// `PasteMystClient` talks to it.
+    pub instance: Option<String>,
+}
+
+impl Config {
+    /// Where the config is read from: the file
+    /// `PASTEMYST_CONFIG` points to if it's set,
+    /// `pastemyst/config.toml` in the config
+    /// directory of the user otherwise (`None`
+    /// when that directory can't be found).
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR) { return Some(PathBuf::from(path)); }
+        let directory = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+        Some(directory.join("pastemyst").join("config.toml"))
+    }
+
+    /// Reads the config from `Config::path`,
+    /// an empty config is returned if there
+    /// is no file.
+    pub fn load() -> Result<Config, PasteMystError> {
+        match Config::path() {
+            Some(path) => Config::load_from(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Reads the config from the given file, an
+    /// empty config is returned if it doesn't exist.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::config::{Config, Visibility};
+    /// use pastemyst::time::ExpiresIn;
+    ///
+    /// let path = std::env::temp_dir().join("pastemyst-doctest-config.toml");
+    /// std::fs::write(&path, "expires_in = \"1d\"\nvisibility = \"private\"\n").unwrap();
+    /// let config = Config::load_from(&path).unwrap();
+    /// assert_eq!(config.expires_in, Some(ExpiresIn::OneDay));
+    /// assert_eq!(config.visibility, Some(Visibility::Private));
+    /// assert!(config.token.is_none());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Config, PasteMystError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => return Err(error.into()),
+        };
+        toml::from_str(&contents).map_err(|error| PasteMystError::Config(error.into()))
+    }
+
+    /// Writes the config to `Config::path`,
+    /// creating its directory if needed.
+    pub fn save(&self) -> Result<(), PasteMystError> {
+        let path = Config::path().ok_or_else(|| {
+            PasteMystError::Io(std::io::Error::new(std::io::ErrorKind::NotFound, "no config directory was found"))
+        })?;
+        self.save_to(path)
+    }
+
+    /// Writes the config to the given file, creating
+    /// its directory if needed. On unix the file is
+    /// only readable by its owner since it may hold
+    /// a token, its mode is set before anything is
+    /// written to it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::config::Config;
+    ///
+    /// let path = std::env::temp_dir().join("pastemyst-doctest-saved-config.toml");
+    /// std::fs::write(&path, "").unwrap();
+    /// Config::default().save_to(&path).unwrap();
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+    /// }
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), PasteMystError> {
+        let path = path.as_ref();
+        let contents = toml::to_string(self).map_err(|error| PasteMystError::Config(error.into()))?;
+        if let Some(directory) = path.parent() { std::fs::create_dir_all(directory)?; }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        // The mode above only applies to new files,
+        // an existing one may still be readable by others.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+
+    /// An empty paste with the expiry and
+    /// visibility of the config, the fields
+    /// that aren't set are left at the
+    /// defaults of pastemyst.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::config::{Config, Visibility};
+    /// use pastemyst::paste::*;
+    /// use pastemyst::time::ExpiresIn;
+    ///
+    /// let config = Config {
+    ///     expires_in: Some(ExpiresIn::OneWeek),
+    ///     visibility: Some(Visibility::Public),
+    ///     ..Default::default()
+    /// };
+    /// let contents = CreateObject {
+    ///     title: String::from("Notes"),
+    ///     ..config.new_paste()
+    /// };
+    /// assert_eq!(contents.expiresIn, ExpiresIn::OneWeek);
+    /// assert!(contents.isPublic && !contents.isPrivate);
+    /// ```
+    pub fn new_paste(&self) -> CreateObject {
+        let visibility = self.visibility.unwrap_or(Visibility::Unlisted);
+        CreateObject {
+            title: String::new(),
+            expiresIn: self.expires_in.unwrap_or_default(),
+            isPrivate: visibility == Visibility::Private,
+            isPublic: visibility == Visibility::Public,
+            tags: Vec::new(),
+            pasties: Vec::new(),
+        }
+    }
+}
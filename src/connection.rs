@@ -0,0 +1,530 @@
+#[cfg(all(feature = "endpoints", not(target_arch = "wasm32")))]
+use reqwest::redirect::Policy;
+#[cfg(any(feature = "rate-limit", all(feature = "async", feature = "endpoints", not(target_arch = "wasm32"))))]
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use crate::error::PasteMystError;
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+use crate::transport::Transport;
+
+/// The `User-Agent` sent when none is
+/// set in the `ClientOptions`.
+pub const DEFAULT_USER_AGENT: &str = concat!("pastemyst-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Options used to build the `reqwest::Client` of a
+/// `PasteMystClient` with `PasteMystClient::with_options`,
+/// or the ones of the free functions with
+/// `set_default_options`.
+/// They mostly matter for bots running with little memory,
+/// where a huge paste or a misbehaving proxy shouldn't be
+/// able to take the whole process down.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::client::*;
+///
+/// let options = ClientOptions {
+///     max_redirects: 0,
+///     max_response_size: Some(512 * 1024),
+///     proxy: Some(String::from("http://proxy.example.com:8080")),
+///     user_agent: String::from("my-bot/1.0 (+https://example.com/bot)"),
+///     ..Default::default()
+/// };
+/// let client = PasteMystClient::with_options(&options).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// How many redirects are followed
+    /// before giving up, `0` disables
+    /// redirects entirely.
+    pub max_redirects: usize,
+    /// The largest response body, in bytes,
+    /// that is read before failing with
+    /// `PasteMystError::ResponseTooLarge`.
+    /// `None` reads bodies of any size.
+    pub max_response_size: Option<usize>,
+    /// The proxy every request is sent through, e.g.
+    /// `http://proxy.example.com:8080`. `socks5://`
+    /// urls work once the `socks` feature of reqwest
+    /// is enabled in your own `Cargo.toml`.
+    pub proxy: Option<String>,
+    /// Whether the `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `NO_PROXY` environment variables are honored
+    /// when no `proxy` is set.
+    pub env_proxy: bool,
+    /// The `User-Agent` header sent with every
+    /// request, set it to something that
+    /// identifies your application so that the
+    /// operators of the instance can tell its
+    /// traffic apart.
+    pub user_agent: String,
+    /// How many times a request answered with `429 Too
+    /// Many Requests` is sent again, after waiting for as
+    /// long as its `Retry-After` header asks (a second if
+    /// it doesn't say). Once they're used up the call
+    /// fails with `PasteMystError::RateLimited`. The
+    /// asynchronous free functions need a tokio runtime
+    /// to wait and never retry on wasm.
+    pub rate_limit_retries: u32,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        ClientOptions {
+            max_redirects: 10,
+            max_response_size: None,
+            proxy: None,
+            env_proxy: true,
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            rate_limit_retries: 0,
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Builds an asynchronous
+    /// `reqwest::Client` from the options.
+    #[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+    pub(crate) fn build_http(&self) -> Result<reqwest::Client, reqwest::Error> {
+        let mut builder = reqwest::Client::builder().redirect(self.redirect_policy()).user_agent(&self.user_agent);
+        match &self.proxy {
+            Some(proxy) => builder = builder.proxy(reqwest::Proxy::all(proxy)?),
+            None if !self.env_proxy => builder = builder.no_proxy(),
+            None => {}
+        }
+        builder.build()
+    }
+
+    /// Builds a blocking
+    /// `reqwest::Client` from the options.
+    #[cfg(all(feature = "blocking", feature = "endpoints"))]
+    pub(crate) fn build_blocking_http(&self) -> Result<reqwest::blocking::Client, reqwest::Error> {
+        let mut builder = reqwest::blocking::Client::builder()
+            .redirect(self.redirect_policy())
+            .user_agent(&self.user_agent);
+        match &self.proxy {
+            Some(proxy) => builder = builder.proxy(reqwest::Proxy::all(proxy)?),
+            None if !self.env_proxy => builder = builder.no_proxy(),
+            None => {}
+        }
+        builder.build()
+    }
+
+    #[cfg(all(feature = "endpoints", not(target_arch = "wasm32")))]
+    fn redirect_policy(&self) -> Policy {
+        match self.max_redirects {
+            0 => Policy::none(),
+            max => Policy::limited(max),
+        }
+    }
+}
+
+/// The options set with `set_default_options`.
+static DEFAULT_OPTIONS: RwLock<Option<ClientOptions>> = RwLock::new(None);
+
+/// The asynchronous client of the free functions, built
+/// on first use and dropped by `set_default_options`.
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+static DEFAULT_HTTP: RwLock<Option<reqwest::Client>> = RwLock::new(None);
+
+/// The blocking client of the free functions, built
+/// on first use and dropped by `set_default_options`.
+#[cfg(all(feature = "blocking", feature = "endpoints"))]
+static DEFAULT_BLOCKING_HTTP: RwLock<Option<reqwest::blocking::Client>> = RwLock::new(None);
+
+/// Sets the options the free functions of the `paste`, `user`,
+/// `data` and `time` modules build their http client with,
+/// so that a proxy (or any other option) applies to every
+/// request sent by the crate, not only to the ones sent
+/// through a `PasteMystClient`. `max_response_size` only
+/// applies to a `PasteMystClient`.
+///
+/// The clients of the free functions are built once and
+/// reused, they're only built again after the options
+/// are changed through this function.
+///
+/// Fails without changing anything if the
+/// options can't be used to build a client,
+/// e.g. when the proxy isn't a valid url.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::connection::*;
+///
+/// let options = ClientOptions {
+///     proxy: Some(String::from("http://proxy.example.com:8080")),
+///     ..Default::default()
+/// };
+/// set_default_options(options).unwrap();
+/// ```
+pub fn set_default_options(options: ClientOptions) -> Result<(), PasteMystError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(proxy) = &options.proxy { reqwest::Proxy::all(proxy)?; }
+    let mut default_options = DEFAULT_OPTIONS.write().unwrap();
+    *default_options = Some(options);
+    #[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+    { *DEFAULT_HTTP.write().unwrap() = None; }
+    #[cfg(all(feature = "blocking", feature = "endpoints"))]
+    { *DEFAULT_BLOCKING_HTTP.write().unwrap() = None; }
+    Ok(())
+}
+
+/// The transport set with `set_default_transport`.
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+static DEFAULT_TRANSPORT: RwLock<Option<Arc<dyn Transport>>> = RwLock::new(None);
+
+/// Sends the requests of the asynchronous free functions of
+/// the `paste`, `user`, `data` and `time` modules through
+/// the given transport instead of reqwest, so that they can
+/// be used from async-std, smol or any other executor. A
+/// `PasteMystClient` has its own, see
+/// `PasteMystClient::from_transport`.
+///
+/// Rate limited requests are retried with the timer of
+/// the transport, see `Transport::sleep`. The blocking
+/// functions aren't affected.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::connection::*;
+/// use pastemyst::transport::*;
+///
+/// struct MyTransport;
+///
+/// impl Transport for MyTransport {
+///     fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+///         Box::pin(async move {
+///             // Send `request` with the http client of your executor.
+///             let response = http::Response::builder().status(200).body(Vec::new())?;
+///             Ok(response)
+///         })
+///     }
+/// }
+///
+/// set_default_transport(MyTransport);
+/// ```
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+pub fn set_default_transport(transport: impl Transport + 'static) {
+    *DEFAULT_TRANSPORT.write().unwrap() = Some(Arc::new(transport));
+}
+
+/// The transport set with `set_default_transport`, if any.
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+fn default_transport() -> Option<Arc<dyn Transport>> { DEFAULT_TRANSPORT.read().unwrap().clone() }
+
+/// Waits for `duration` with the timer of the transport
+/// set with `set_default_transport`, or tokio's if none
+/// was set.
+#[cfg(all(feature = "async", feature = "paste", not(target_arch = "wasm32")))]
+pub(crate) async fn sleep(duration: std::time::Duration) {
+    match default_transport() {
+        Some(transport) => transport.sleep(duration).await,
+        None => tokio::time::sleep(duration).await,
+    }
+}
+
+/// The limiter set with `set_default_rate_limit`.
+#[cfg(feature = "rate-limit")]
+static DEFAULT_LIMITER: RwLock<Option<Arc<governor::DefaultDirectRateLimiter>>> = RwLock::new(None);
+
+/// Limits the free functions of the `paste`, `user`, `data`
+/// and `time` modules to `per_second` requests per second,
+/// allowing bursts of up to `burst` requests. Requests over
+/// the limit wait until they're allowed instead of failing.
+/// The limit is shared by every thread, a `PasteMystClient`
+/// has its own, see `PasteMystClient::with_rate_limit`.
+///
+/// This function is only available with
+/// the `rate-limit` feature.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::num::NonZeroU32;
+/// use pastemyst::connection::*;
+///
+/// set_default_rate_limit(NonZeroU32::new(5).unwrap(), NonZeroU32::new(10).unwrap());
+/// ```
+#[cfg(feature = "rate-limit")]
+pub fn set_default_rate_limit(per_second: std::num::NonZeroU32, burst: std::num::NonZeroU32) {
+    let quota = governor::Quota::per_second(per_second).allow_burst(burst);
+    *DEFAULT_LIMITER.write().unwrap() = Some(Arc::new(governor::RateLimiter::direct(quota)));
+}
+
+/// The limiter set with `set_default_rate_limit`, if any.
+#[cfg(feature = "rate-limit")]
+fn default_limiter() -> Option<Arc<governor::DefaultDirectRateLimiter>> { DEFAULT_LIMITER.read().unwrap().clone() }
+
+/// Calls `build` with the options set with
+/// `set_default_options`, or the default
+/// options if none were set.
+#[cfg(feature = "endpoints")]
+fn with_default_options<T>(build: impl FnOnce(&ClientOptions) -> T) -> T {
+    match &*DEFAULT_OPTIONS.read().unwrap() {
+        Some(options) => build(options),
+        None => build(&ClientOptions::default()),
+    }
+}
+
+/// Returns the client in `cache`, building it from the
+/// options set with `set_default_options` if there is
+/// none yet. The options stay locked while it's built
+/// so that a client of older options isn't cached.
+#[cfg(all(feature = "endpoints", any(feature = "async", feature = "blocking"), not(target_arch = "wasm32")))]
+fn cached<T: Clone>(
+    cache: &RwLock<Option<T>>,
+    build: impl FnOnce(&ClientOptions) -> Result<T, reqwest::Error>,
+) -> Result<T, reqwest::Error> {
+    if let Some(client) = &*cache.read().unwrap() { return Ok(client.clone()); }
+    let options = DEFAULT_OPTIONS.read().unwrap();
+    let mut cache = cache.write().unwrap();
+    if let Some(client) = &*cache { return Ok(client.clone()); }
+    let client = match &*options {
+        Some(options) => build(options)?,
+        None => build(&ClientOptions::default())?,
+    };
+    *cache = Some(client.clone());
+    Ok(client)
+}
+
+/// An asynchronous client for the free functions, built
+/// from the options set with `set_default_options`.
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+pub(crate) fn http() -> Result<reqwest::Client, reqwest::Error> { cached(&DEFAULT_HTTP, ClientOptions::build_http) }
+
+/// An asynchronous client for the free functions. The
+/// browser's fetch can't be configured beyond the
+/// `User-Agent`, the other options are ignored.
+#[cfg(all(feature = "async", feature = "endpoints", target_arch = "wasm32"))]
+pub(crate) fn http() -> Result<reqwest::Client, reqwest::Error> {
+    with_default_options(|options| reqwest::Client::builder().user_agent(&options.user_agent).build())
+}
+
+/// A blocking client for the free functions, built from
+/// the options set with `set_default_options`.
+#[cfg(all(feature = "blocking", feature = "endpoints"))]
+pub(crate) fn blocking_http() -> Result<reqwest::blocking::Client, reqwest::Error> {
+    cached(&DEFAULT_BLOCKING_HTTP, ClientOptions::build_blocking_http)
+}
+
+/// Sends the requests of the free functions. A request
+/// answered with `429 Too Many Requests` is sent again
+/// as many times as `rate_limit_retries` of the default
+/// options allows. With the `tracing` feature each
+/// attempt runs in a `pastemyst.request` span that
+/// records its method, endpoint, status code and
+/// latency.
+#[cfg(feature = "endpoints")]
+pub(crate) trait SendRequest {
+    type Output;
+    fn send_request(self) -> Self::Output;
+}
+
+/// The future of an asynchronous
+/// request sent with `send_request`.
+#[cfg(all(feature = "async", feature = "endpoints", not(target_arch = "wasm32")))]
+pub(crate) type Sending = futures::future::BoxFuture<'static, Result<reqwest::Response, PasteMystError>>;
+
+/// The future of an asynchronous request sent
+/// with `send_request`, the browser's fetch
+/// can't be sent to another thread.
+#[cfg(all(feature = "async", feature = "endpoints", target_arch = "wasm32"))]
+pub(crate) type Sending = futures::future::LocalBoxFuture<'static, Result<reqwest::Response, PasteMystError>>;
+
+#[cfg(all(feature = "async", feature = "endpoints"))]
+impl SendRequest for reqwest::RequestBuilder {
+    type Output = Sending;
+
+    fn send_request(self) -> Sending {
+        // There is no timer to wait with on wasm
+        // without tokio, so nothing is retried there.
+        let mut retries = if cfg!(target_arch = "wasm32") { 0 } else { default_rate_limit_retries() };
+        #[cfg(not(target_arch = "wasm32"))]
+        let transport = default_transport();
+        let mut builder = self;
+        Box::pin(async move {
+            loop {
+                let retry = if retries > 0 { builder.try_clone() } else { None };
+                #[cfg(feature = "rate-limit")]
+                if let Some(limiter) = default_limiter() { limiter.until_ready().await; }
+                #[cfg(not(target_arch = "wasm32"))]
+                let response = match &transport {
+                    Some(transport) => crate::transport::send(transport.as_ref(), builder.build()?).await?,
+                    None => send_once(builder).await?,
+                };
+                #[cfg(target_arch = "wasm32")]
+                let response = send_once(builder).await?;
+                match retry {
+                    Some(next) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        match &transport {
+                            Some(transport) => transport.sleep(rate_limit_delay(response.headers())).await,
+                            None => tokio::time::sleep(rate_limit_delay(response.headers())).await,
+                        }
+                        builder = next;
+                        retries -= 1;
+                    }
+                    _ => return Ok(response),
+                }
+            }
+        })
+    }
+}
+
+/// Sends an asynchronous request once.
+#[cfg(all(feature = "async", feature = "endpoints", not(all(feature = "tracing", not(target_arch = "wasm32")))))]
+async fn send_once(builder: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> { builder.send().await }
+
+/// Sends an asynchronous request once,
+/// within its `pastemyst.request` span.
+#[cfg(all(feature = "async", feature = "tracing", feature = "endpoints", not(target_arch = "wasm32")))]
+async fn send_once(builder: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    use tracing::Instrument;
+    let span = builder_span(builder.try_clone().and_then(|builder| builder.build().ok()));
+    let started = std::time::Instant::now();
+    let response = builder.send().instrument(span.clone()).await;
+    record_response(&span, response.as_ref().map(reqwest::Response::status), started.elapsed());
+    response
+}
+
+#[cfg(all(feature = "blocking", feature = "endpoints"))]
+impl SendRequest for reqwest::blocking::RequestBuilder {
+    type Output = reqwest::Result<reqwest::blocking::Response>;
+
+    fn send_request(self) -> Self::Output {
+        let mut retries = default_rate_limit_retries();
+        let mut builder = self;
+        loop {
+            let retry = if retries > 0 { builder.try_clone() } else { None };
+            #[cfg(feature = "rate-limit")]
+            if let Some(limiter) = default_limiter() { futures::executor::block_on(limiter.until_ready()); }
+            let response = send_blocking_once(builder)?;
+            match retry {
+                Some(next) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    std::thread::sleep(rate_limit_delay(response.headers()));
+                    builder = next;
+                    retries -= 1;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
+/// Sends a blocking request once.
+#[cfg(all(feature = "blocking", feature = "endpoints", not(feature = "tracing")))]
+fn send_blocking_once(builder: reqwest::blocking::RequestBuilder) -> reqwest::Result<reqwest::blocking::Response> {
+    builder.send()
+}
+
+/// Sends a blocking request once,
+/// within its `pastemyst.request` span.
+#[cfg(all(feature = "blocking", feature = "tracing", feature = "endpoints"))]
+fn send_blocking_once(builder: reqwest::blocking::RequestBuilder) -> reqwest::Result<reqwest::blocking::Response> {
+    let span = builder_span(builder.try_clone().and_then(|builder| builder.build().ok()));
+    let started = std::time::Instant::now();
+    let response = span.in_scope(|| builder.send());
+    record_response(&span, response.as_ref().map(reqwest::blocking::Response::status), started.elapsed());
+    response
+}
+
+/// How many times the free functions send a
+/// rate limited request again, from the
+/// options set with `set_default_options`.
+#[cfg(feature = "endpoints")]
+fn default_rate_limit_retries() -> u32 { with_default_options(|options| options.rate_limit_retries) }
+
+/// How long to wait before sending a rate limited
+/// request again: what its `Retry-After` header
+/// asks for, or a second when there's none.
+#[cfg(feature = "endpoints")]
+pub(crate) fn rate_limit_delay(headers: &reqwest::header::HeaderMap) -> std::time::Duration {
+    crate::error::retry_after(headers).unwrap_or(std::time::Duration::from_secs(1))
+}
+
+/// The span of a request of the free functions, the
+/// builder is cloned to read its method and url so
+/// the fields are left empty when its body can't be.
+#[cfg(all(feature = "tracing", feature = "endpoints"))]
+fn builder_span(request: Option<impl Described>) -> tracing::Span {
+    match request {
+        Some(request) => request_span(request.method(), request.url()),
+        None => tracing::debug_span!(
+            "pastemyst.request",
+            method = tracing::field::Empty,
+            endpoint = tracing::field::Empty,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        ),
+    }
+}
+
+/// The blocking and asynchronous requests
+/// of reqwest, for `builder_span`.
+#[cfg(all(feature = "tracing", feature = "endpoints"))]
+trait Described {
+    fn method(&self) -> &reqwest::Method;
+    fn url(&self) -> &reqwest::Url;
+}
+
+#[cfg(all(feature = "tracing", feature = "async", feature = "endpoints"))]
+impl Described for reqwest::Request {
+    fn method(&self) -> &reqwest::Method { reqwest::Request::method(self) }
+    fn url(&self) -> &reqwest::Url { reqwest::Request::url(self) }
+}
+
+#[cfg(all(feature = "tracing", feature = "blocking", feature = "endpoints"))]
+impl Described for reqwest::blocking::Request {
+    fn method(&self) -> &reqwest::Method { reqwest::blocking::Request::method(self) }
+    fn url(&self) -> &reqwest::Url { reqwest::blocking::Request::url(self) }
+}
+
+/// Opens the `pastemyst.request` span of a request, the
+/// endpoint is the path of the url so that the query
+/// (and whatever it holds) isn't recorded.
+#[cfg(all(feature = "tracing", feature = "endpoints"))]
+pub(crate) fn request_span(method: &reqwest::Method, url: &reqwest::Url) -> tracing::Span {
+    tracing::debug_span!(
+        "pastemyst.request",
+        method = %method,
+        endpoint = url.path(),
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    )
+}
+
+/// Records how a request went
+/// on its span once it's done.
+#[cfg(all(feature = "tracing", feature = "endpoints"))]
+pub(crate) fn record_response(
+    span: &tracing::Span,
+    status: Result<reqwest::StatusCode, &reqwest::Error>,
+    latency: std::time::Duration,
+) {
+    span.record("latency_ms", latency.as_millis() as u64);
+    match status {
+        Ok(status) => {
+            span.record("status", status.as_u16());
+            tracing::debug!(parent: span, status = status.as_u16(), latency_ms = latency.as_millis() as u64, "request done");
+        }
+        Err(error) => tracing::warn!(parent: span, %error, "request failed"),
+    }
+}
+
+/// Emits a warning, as a `tracing` event or a `log`
+/// record when one of those features is on (`tracing`
+/// wins if both are). Without either it is dropped,
+/// a library has no business writing to stdout.
+#[cfg(feature = "user")]
+pub(crate) fn warn(message: std::fmt::Arguments<'_>) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!("{}", message);
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    log::warn!(target: "pastemyst", "{}", message);
+    #[cfg(not(any(feature = "tracing", feature = "log")))]
+    let _ = message;
+}
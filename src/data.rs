@@ -1,16 +1,32 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-pub type DataResult<T, E = reqwest::Error> = Result<T, E>;
+use crate::error::PasteMystError;
 
+#[cfg(all(feature = "data", feature = "network"))]
+use crate::connection::{self, SendRequest};
+#[cfg(all(feature = "data", feature = "network"))]
+use crate::error;
+
+/// The type provided by the pastemyst lib. It takes
+/// a type `T` and evalutates to that type and a
+/// `Result` like so: `Result<T, E>` where `E` has
+/// the default value of `PasteMystError`. Keep note
+/// that `E` can be overriden.
+#[cfg(all(feature = "data", feature = "network"))]
+pub type DataResult<T, E = PasteMystError> = Result<T, E>;
+
+#[cfg(all(feature = "data", feature = "network"))]
 const DATA_ENDPOINT: &str = "https://paste.myst.rs/api/v2/data/";
 
 /// Get information on a specific language *supported by PasteMyst*.
 /// You are recommened to only use the language names provided within
-/// `pastemyst::data::language` to prevent panicking. This method is
-/// synchronous.
+/// `pastemyst::data::language` so that the language is found. This
+/// method is synchronous.
 ///
-/// This method does and will panic if a language is not found. The
-/// simplest solution to this is to use the language name you know - as
+/// A language that is not found returns `PasteMystError::Api` with
+/// the status PasteMyst responded with, and `PasteMystError::RateLimited`
+/// is returned when too many requests were sent. The simplest way to
+/// avoid the former is to use the language name you know - as
 /// mentioned earlier, it is recommended to use that specific module.
 ///
 /// Some fields, namely `color and `ext` may not be provided and will
@@ -18,7 +34,7 @@ const DATA_ENDPOINT: &str = "https://paste.myst.rs/api/v2/data/";
 /// by PasteMyst which means that they do not exist or specified anywhere.
 ///
 /// ## Examples
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::data::*;
 ///
 /// fn main() -> DataResult<()> {
@@ -27,17 +43,21 @@ const DATA_ENDPOINT: &str = "https://paste.myst.rs/api/v2/data/";
 ///     Ok(())
 /// }
 /// ```
-pub fn get_language_by_name(language_name: &str) -> DataResult<DataObject, reqwest::Error> {
-    Ok(reqwest::blocking::get(&parse_url(language_name, "name"))?.json()?)
+#[cfg(all(feature = "data", feature = "blocking"))]
+pub fn get_language_by_name(language_name: &str) -> DataResult<DataObject> {
+    let result = connection::blocking_http()?.get(parse_url(language_name, LookupBy::Name)).send_request()?;
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// Get information on a specific language *supported by PasteMyst*.
 /// You are recommened to only use the language names provided within
-/// `pastemyst::data::language` to prevent panicking. This method is
-/// asynchronous.
+/// `pastemyst::data::language` so that the language is found. This
+/// method is asynchronous.
 ///
-/// This method does and will panic if a language is not found. The
-/// simplest solution to this is to use the language name you know - as
+/// A language that is not found returns `PasteMystError::Api` with
+/// the status PasteMyst responded with, and `PasteMystError::RateLimited`
+/// is returned when too many requests were sent. The simplest way to
+/// avoid the former is to use the language name you know - as
 /// mentioned earlier, it is recommended to use that specific module.
 ///
 /// Some fields, namely `color` and `ext` may not be provided and will
@@ -45,7 +65,7 @@ pub fn get_language_by_name(language_name: &str) -> DataResult<DataObject, reqwe
 /// by PasteMyst which means that they do not exist or specified anywhere.
 ///
 /// ## Examples
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::data::*;
 ///
 /// #[tokio::main]
@@ -55,17 +75,20 @@ pub fn get_language_by_name(language_name: &str) -> DataResult<DataObject, reqwe
 ///     Ok(())
 /// }
 /// ```
-pub async fn get_language_by_name_async(language_name: &str) -> DataResult<DataObject, reqwest::Error> {
-    Ok(reqwest::get(&parse_url(language_name, "name")).await?.json().await?)
+#[cfg(all(feature = "data", feature = "async"))]
+pub async fn get_language_by_name_async(language_name: &str) -> DataResult<DataObject> {
+    let result = connection::http()?.get(parse_url(language_name, LookupBy::Name)).send_request().await?;
+    Ok(error::check_status_async(result).await?.json().await?)
 }
 
 /// The same thing as getting a language by a name, except that it is by
 /// extension, of a given language. This is a synchronous method.
 ///
-/// This method will also panic if a language extension is not found. The
-/// simplest solution to this is to use the language extension that PasteMyst
-/// has. The easiest way to confirm so is to check if your desired language
-/// exists in `pastemyst::data::langauge`.
+/// An extension that is not found returns `PasteMystError::Api`, and
+/// `PasteMystError::RateLimited` is returned when too many requests were
+/// sent. The simplest way to avoid the former is to use the language
+/// extension that PasteMyst has. The easiest way to confirm so is to check
+/// if your desired language exists in `pastemyst::data::langauge`.
 ///
 /// This will return a `DataObject`. Some fields, namely `color` and `ext` may
 /// not be provided and will have the value of `None`. This is because they are
@@ -73,7 +96,7 @@ pub async fn get_language_by_name_async(language_name: &str) -> DataResult<DataO
 /// anywhere.
 ///
 /// ## Examples
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::data::*;
 ///
 /// fn main() -> DataResult<()> {
@@ -82,17 +105,20 @@ pub async fn get_language_by_name_async(language_name: &str) -> DataResult<DataO
 ///     Ok(())
 /// }
 /// ```
-pub fn get_language_by_extension(lang_extension: &str) -> DataResult<DataObject, reqwest::Error> {
-    Ok(reqwest::blocking::get(&parse_url(lang_extension, "ext"))?.json()?)
+#[cfg(all(feature = "data", feature = "blocking"))]
+pub fn get_language_by_extension(lang_extension: &str) -> DataResult<DataObject> {
+    let result = connection::blocking_http()?.get(parse_url(lang_extension, LookupBy::Extension)).send_request()?;
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// The same thing as getting a language by a name, except that it is by
 /// extension, of a given language. This is an asynchronous method.
 ///
-/// This method will also panic if a language extension is not found. The
-/// simplest solution to this is to use the language extension that PasteMyst
-/// has. The easiest way to confirm so is to check if your desired language
-/// exists in `pastemyst::data::langauge`.
+/// An extension that is not found returns `PasteMystError::Api`, and
+/// `PasteMystError::RateLimited` is returned when too many requests were
+/// sent. The simplest way to avoid the former is to use the language
+/// extension that PasteMyst has. The easiest way to confirm so is to check
+/// if your desired language exists in `pastemyst::data::langauge`.
 ///
 /// This will return a `DataObject`. Some fields, namely `color` and `ext` may
 /// not be provided and will have the value of `None`. This is because they are
@@ -100,7 +126,7 @@ pub fn get_language_by_extension(lang_extension: &str) -> DataResult<DataObject,
 /// anywhere.
 ///
 /// ## Examples
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::data::*;
 ///
 /// #[tokio::main]
@@ -110,22 +136,37 @@ pub fn get_language_by_extension(lang_extension: &str) -> DataResult<DataObject,
 ///     Ok(())
 /// }
 /// ```
-pub async fn get_language_by_extension_async(lang_extension: &str) -> DataResult<DataObject, reqwest::Error> {
-    Ok(reqwest::get(&parse_url(lang_extension, "ext")).await?.json().await?)
+#[cfg(all(feature = "data", feature = "async"))]
+pub async fn get_language_by_extension_async(lang_extension: &str) -> DataResult<DataObject> {
+    let result = connection::http()?.get(parse_url(lang_extension, LookupBy::Extension)).send_request().await?;
+    Ok(error::check_status_async(result).await?.json().await?)
 }
 
-#[derive(Deserialize)]
+/// A language as PasteMyst describes it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::DataObject;
+///
+/// let body = r##"{"name":"Rust","mode":"rust","mimes":["text/x-rustsrc"],"ext":["rs"],"color":"#dea584"}"##;
+/// let language: DataObject = serde_json::from_str(body).unwrap();
+/// assert_eq!(serde_json::to_string(&language).unwrap(), body);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct DataObject {
     /// The name of the language.
     pub name: String,
     /// The mode of a language used
     /// in a pasty editor.
+    #[serde(default)]
     pub mode: String,
     /// A vector of the data types
     /// of each language that is
     /// meant to be used by official
     /// standards.
+    #[serde(default)]
     pub mimes: Vec<String>,
     /// The extension(s) of a language
     pub ext: Option<Vec<String>>,
@@ -144,12 +185,338 @@ pub struct DataObject {
     // Might be implemented, might not. I'll let it stay here like so for now.
 }
 
-fn parse_url(value: &str, req_type: &str) -> String {
-    let parsed_url: String;
-    if req_type == "name" { parsed_url = format!("{}language?name={}", DATA_ENDPOINT, &value); }
-    else if req_type == "ext" { parsed_url = format!("{}languageExt?extension={}", DATA_ENDPOINT, &value); }
-    else { panic!("[pastemyst] Invalid valid: `req_type` provided. Report the developer about this."); }
-    return parsed_url;
+/// Returns the usual file extension (without the
+/// dot) of a language *supported by PasteMyst*,
+/// or `None` if it has none, e.g. `Dockerfile`.
+/// The lookup uses a table embedded in the crate
+/// so no request is sent.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::*;
+///
+/// assert_eq!(extension_for_language(language::RUST), Some("rs"));
+/// assert_eq!(extension_for_language(language::CPP), Some("cpp"));
+/// assert_eq!(extension_for_language(language::DOCKER), None);
+/// ```
+pub fn extension_for_language(language_name: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|language| language.name == language_name)
+        .and_then(|language| language.extensions.first().copied())
+}
+
+/// Returns the name of the language a file
+/// extension (with or without the dot) belongs
+/// to, or `None` if it is not known. The lookup
+/// uses a table embedded in the crate so no
+/// request is sent.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::*;
+///
+/// assert_eq!(language_for_extension("rs"), Some(language::RUST));
+/// assert_eq!(language_for_extension(".hpp"), Some(language::CPP));
+/// assert_eq!(language_for_extension("nope"), None);
+/// ```
+pub fn language_for_extension(extension: &str) -> Option<&'static str> {
+    let extension = extension.strip_prefix('.').unwrap_or(extension);
+    LANGUAGES
+        .iter()
+        .find(|language| language.extensions.iter().any(|ext| ext.eq_ignore_ascii_case(extension)))
+        .map(|language| language.name)
+}
+
+/// Returns the name of the language of a file by
+/// its name, or `None` if it is not known. Files
+/// known by their whole name, like `Dockerfile`,
+/// are recognized before the extension is looked
+/// at with `language_for_extension`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::*;
+///
+/// assert_eq!(language_for_filename("src/main.rs"), Some(language::RUST));
+/// assert_eq!(language_for_filename("Dockerfile"), Some(language::DOCKER));
+/// assert_eq!(language_for_filename("README"), None);
+/// ```
+pub fn language_for_filename(filename: &str) -> Option<&'static str> {
+    let path = std::path::Path::new(filename);
+    match path.file_name()?.to_str()? {
+        "Dockerfile" => return Some(language::DOCKER),
+        "CMakeLists.txt" => return Some(language::CMAKE),
+        "nginx.conf" => return Some(language::NGINX),
+        _ => {}
+    }
+    language_for_extension(path.extension()?.to_str()?)
+}
+
+/// Returns the names of the languages that use an
+/// editor mode (the `mode` field of `DataObject`),
+/// e.g. `clike` is used by C, C++, Java and others.
+/// The lookup uses a table embedded in the crate so
+/// no request is sent. An unknown mode returns an
+/// empty vector.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::*;
+///
+/// assert!(languages_for_mode("clike").contains(&language::CSHARP));
+/// assert_eq!(languages_for_mode("rust"), vec![language::RUST]);
+/// assert!(languages_for_mode("nope").is_empty());
+/// ```
+pub fn languages_for_mode(mode: &str) -> Vec<&'static str> {
+    LANGUAGES
+        .iter()
+        .filter(|language| language.mode == mode)
+        .map(|language| language.name)
+        .collect()
+}
+
+/// Get information on every language *supported by
+/// PasteMyst* that uses an editor mode. PasteMyst
+/// can't be searched by mode so the languages are
+/// found with `languages_for_mode` and each of them
+/// is then requested by name. This method is
+/// synchronous.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use pastemyst::data::*;
+///
+/// fn main() -> DataResult<()> {
+///     for language in get_language_by_mode("clike")? {
+///         println!("{}: {:?}", language.name, language.ext);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "data", feature = "blocking"))]
+pub fn get_language_by_mode(mode: &str) -> DataResult<Vec<DataObject>> {
+    languages_for_mode(mode).into_iter().map(get_language_by_name).collect()
+}
+
+/// Get information on every language *supported by
+/// PasteMyst* that uses an editor mode. PasteMyst
+/// can't be searched by mode so the languages are
+/// found with `languages_for_mode` and each of them
+/// is then requested by name. This method is
+/// asynchronous.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use pastemyst::data::*;
+///
+/// #[tokio::main]
+/// async fn main() -> DataResult<()> {
+///     for language in get_language_by_mode_async("clike").await? {
+///         println!("{}: {:?}", language.name, language.ext);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "data", feature = "async"))]
+pub async fn get_language_by_mode_async(mode: &str) -> DataResult<Vec<DataObject>> {
+    let requests = languages_for_mode(mode).into_iter().map(get_language_by_name_async);
+    futures::future::try_join_all(requests).await
+}
+
+/// A language as pastemyst knows it, the
+/// subset of `DataObject` that is embedded.
+struct LanguageInfo {
+    name: &'static str,
+    mode: &'static str,
+    extensions: &'static [&'static str],
+}
+
+/// The languages supported by pastemyst, with the first
+/// extension being the usual one. An extension shared by
+/// several languages (like `m`) is only listed once.
+const LANGUAGES: &[LanguageInfo] = &[
+    LanguageInfo { name: language::PLAIN, mode: "text", extensions: &["txt", "text", "conf", "def", "list", "log"] },
+    LanguageInfo { name: language::APL, mode: "apl", extensions: &["dyalog", "apl"] },
+    LanguageInfo { name: language::PGP, mode: "asciiarmor", extensions: &["asc", "pgp", "sig"] },
+    LanguageInfo { name: language::ASN1, mode: "asn.1", extensions: &["asn", "asn1"] },
+    LanguageInfo { name: language::ASTERISK, mode: "asterisk", extensions: &[] },
+    LanguageInfo { name: language::BRAINFUCK, mode: "brainfuck", extensions: &["b", "bf"] },
+    LanguageInfo { name: language::C, mode: "clike", extensions: &["c", "h", "ino"] },
+    LanguageInfo { name: language::CPP, mode: "clike", extensions: &["cpp", "c++", "cc", "cxx", "hpp", "h++", "hh", "hxx"] },
+    LanguageInfo { name: language::COBOL, mode: "cobol", extensions: &["cob", "cpy", "cbl"] },
+    LanguageInfo { name: language::CSHARP, mode: "clike", extensions: &["cs"] },
+    LanguageInfo { name: language::CLOJURE, mode: "clojure", extensions: &["clj", "cljc", "cljx"] },
+    LanguageInfo { name: language::CLOJURE_SCRIPT, mode: "clojure", extensions: &["cljs"] },
+    LanguageInfo { name: language::GSS, mode: "css", extensions: &["gss"] },
+    LanguageInfo { name: language::CMAKE, mode: "cmake", extensions: &["cmake", "cmake.in"] },
+    LanguageInfo { name: language::COFFEE_SCRIPT, mode: "coffeescript", extensions: &["coffee"] },
+    LanguageInfo { name: language::LISP, mode: "commonlisp", extensions: &["lisp", "cl", "el"] },
+    LanguageInfo { name: language::CYPHER, mode: "cypher", extensions: &["cyp", "cypher"] },
+    LanguageInfo { name: language::CYTHON, mode: "python", extensions: &["pyx", "pxd", "pxi"] },
+    LanguageInfo { name: language::CRYSTAL, mode: "crystal", extensions: &["cr"] },
+    LanguageInfo { name: language::CSS, mode: "css", extensions: &["css"] },
+    LanguageInfo { name: language::CQL, mode: "sql", extensions: &["cql"] },
+    LanguageInfo { name: language::D, mode: "d", extensions: &["d"] },
+    LanguageInfo { name: language::DART, mode: "dart", extensions: &["dart"] },
+    LanguageInfo { name: language::DIFF, mode: "diff", extensions: &["diff", "patch"] },
+    LanguageInfo { name: language::DJANGO, mode: "django", extensions: &[] },
+    LanguageInfo { name: language::DOCKER, mode: "dockerfile", extensions: &[] },
+    LanguageInfo { name: language::DTD, mode: "dtd", extensions: &["dtd"] },
+    LanguageInfo { name: language::DYLAN, mode: "dylan", extensions: &["dylan", "dyl", "intr"] },
+    LanguageInfo { name: language::EBNF, mode: "ebnf", extensions: &[] },
+    LanguageInfo { name: language::ECL, mode: "ecl", extensions: &["ecl"] },
+    LanguageInfo { name: language::EDN, mode: "clojure", extensions: &["edn"] },
+    LanguageInfo { name: language::EIFFEL, mode: "eiffel", extensions: &["e"] },
+    LanguageInfo { name: language::ELM, mode: "elm", extensions: &["elm"] },
+    LanguageInfo { name: language::EJS, mode: "htmlembedded", extensions: &["ejs"] },
+    LanguageInfo { name: language::ERB, mode: "htmlembedded", extensions: &["erb"] },
+    LanguageInfo { name: language::ERLANG, mode: "erlang", extensions: &["erl"] },
+    LanguageInfo { name: language::ESPER, mode: "sql", extensions: &[] },
+    LanguageInfo { name: language::FACTOR, mode: "factor", extensions: &["factor"] },
+    LanguageInfo { name: language::FCL, mode: "fcl", extensions: &[] },
+    LanguageInfo { name: language::FORTH, mode: "forth", extensions: &["forth", "fth", "4th"] },
+    LanguageInfo { name: language::FORTRAN, mode: "fortran", extensions: &["f90", "f", "for", "f77", "f95"] },
+    LanguageInfo { name: language::FSHARP, mode: "mllike", extensions: &["fs"] },
+    LanguageInfo { name: language::GAS, mode: "gas", extensions: &["s"] },
+    LanguageInfo { name: language::GHERKIN, mode: "gherkin", extensions: &["feature"] },
+    LanguageInfo { name: language::GFM, mode: "gfm", extensions: &[] },
+    LanguageInfo { name: language::GO, mode: "go", extensions: &["go"] },
+    LanguageInfo { name: language::GROOVY, mode: "groovy", extensions: &["groovy", "gradle"] },
+    LanguageInfo { name: language::HAML, mode: "haml", extensions: &["haml"] },
+    LanguageInfo { name: language::HASKELL, mode: "haskell", extensions: &["hs"] },
+    LanguageInfo { name: language::HASKELL_LITERATE, mode: "haskell-literate", extensions: &["lhs"] },
+    LanguageInfo { name: language::HAXE, mode: "haxe", extensions: &["hx"] },
+    LanguageInfo { name: language::HXML, mode: "haxe", extensions: &["hxml"] },
+    LanguageInfo { name: language::ASP_NET, mode: "htmlembedded", extensions: &["aspx"] },
+    LanguageInfo { name: language::HTML, mode: "htmlmixed", extensions: &["html", "htm", "handlebars", "hbs"] },
+    LanguageInfo { name: language::HTTP, mode: "http", extensions: &[] },
+    LanguageInfo { name: language::IDL, mode: "idl", extensions: &["pro"] },
+    LanguageInfo { name: language::PUG, mode: "pug", extensions: &["pug", "jade"] },
+    LanguageInfo { name: language::JAVA, mode: "clike", extensions: &["java"] },
+    LanguageInfo { name: language::JSP, mode: "htmlembedded", extensions: &["jsp"] },
+    LanguageInfo { name: language::JAVASCRIPT, mode: "javascript", extensions: &["js", "mjs", "cjs"] },
+    LanguageInfo { name: language::JSON, mode: "javascript", extensions: &["json", "map"] },
+    LanguageInfo { name: language::JSON_LD, mode: "javascript", extensions: &["jsonld"] },
+    LanguageInfo { name: language::JSX, mode: "jsx", extensions: &["jsx"] },
+    LanguageInfo { name: language::JINJA2, mode: "jinja2", extensions: &["j2", "jinja", "jinja2"] },
+    LanguageInfo { name: language::JULIA, mode: "julia", extensions: &["jl"] },
+    LanguageInfo { name: language::KOTLIN, mode: "clike", extensions: &["kt", "kts"] },
+    LanguageInfo { name: language::LESS, mode: "css", extensions: &["less"] },
+    LanguageInfo { name: language::LIVESCRIPT, mode: "livescript", extensions: &["ls"] },
+    LanguageInfo { name: language::LUA, mode: "lua", extensions: &["lua"] },
+    LanguageInfo { name: language::MARKDOWN, mode: "markdown", extensions: &["md", "markdown", "mkd"] },
+    LanguageInfo { name: language::MIRC, mode: "mirc", extensions: &[] },
+    LanguageInfo { name: language::MARIA_DB, mode: "sql", extensions: &[] },
+    LanguageInfo { name: language::MATHEMATICA, mode: "mathematica", extensions: &["nb", "wl", "wls"] },
+    LanguageInfo { name: language::MODELICA, mode: "modelica", extensions: &["mo"] },
+    LanguageInfo { name: language::MUMPS, mode: "mumps", extensions: &["mps"] },
+    LanguageInfo { name: language::MS_SQL, mode: "sql", extensions: &[] },
+    LanguageInfo { name: language::MBOX, mode: "mbox", extensions: &["mbox"] },
+    LanguageInfo { name: language::MYSQL, mode: "sql", extensions: &[] },
+    LanguageInfo { name: language::NGINX, mode: "nginx", extensions: &[] },
+    LanguageInfo { name: language::NSIS, mode: "nsis", extensions: &["nsi", "nsh"] },
+    LanguageInfo { name: language::NTRIPLES, mode: "ntriples", extensions: &["nt", "nq"] },
+    LanguageInfo { name: language::OBJ_C, mode: "clike", extensions: &["m", "mm"] },
+    LanguageInfo { name: language::OCAML, mode: "mllike", extensions: &["ml", "mli"] },
+    LanguageInfo { name: language::OCTAVE, mode: "octave", extensions: &[] },
+    LanguageInfo { name: language::OZ, mode: "oz", extensions: &["oz"] },
+    LanguageInfo { name: language::PASCAL, mode: "pascal", extensions: &["pas", "p"] },
+    LanguageInfo { name: language::PEG_JS, mode: "pegjs", extensions: &["pegjs"] },
+    LanguageInfo { name: language::PERL, mode: "perl", extensions: &["pl", "pm"] },
+    LanguageInfo { name: language::PHP, mode: "php", extensions: &["php", "php3", "php4", "php5", "php7", "phtml"] },
+    LanguageInfo { name: language::PIG, mode: "pig", extensions: &["pig"] },
+    LanguageInfo { name: language::PLSQL, mode: "sql", extensions: &["pls"] },
+    LanguageInfo { name: language::POWERSHELL, mode: "powershell", extensions: &["ps1", "psd1", "psm1"] },
+    LanguageInfo { name: language::INI, mode: "properties", extensions: &["properties", "ini", "in"] },
+    LanguageInfo { name: language::PROTOBUF, mode: "protobuf", extensions: &["proto"] },
+    LanguageInfo { name: language::PYTHON, mode: "python", extensions: &["py", "pyw", "bzl"] },
+    LanguageInfo { name: language::PUPPET, mode: "puppet", extensions: &["pp"] },
+    LanguageInfo { name: language::QLANG, mode: "q", extensions: &["q"] },
+    LanguageInfo { name: language::RSCRIPT, mode: "r", extensions: &["r"] },
+    LanguageInfo { name: language::RST, mode: "rst", extensions: &["rst"] },
+    LanguageInfo { name: language::RPM_CHANGES, mode: "rpm", extensions: &[] },
+    LanguageInfo { name: language::RPM_SPEC, mode: "rpm", extensions: &["spec"] },
+    LanguageInfo { name: language::RUBY, mode: "ruby", extensions: &["rb"] },
+    LanguageInfo { name: language::RUST, mode: "rust", extensions: &["rs"] },
+    LanguageInfo { name: language::SAS, mode: "sas", extensions: &["sas"] },
+    LanguageInfo { name: language::SASS, mode: "sass", extensions: &["sass"] },
+    LanguageInfo { name: language::SCALA, mode: "clike", extensions: &["scala", "sc"] },
+    LanguageInfo { name: language::SCHEME, mode: "scheme", extensions: &["scm", "ss"] },
+    LanguageInfo { name: language::SCSS, mode: "css", extensions: &["scss"] },
+    LanguageInfo { name: language::SHELL, mode: "shell", extensions: &["sh", "ksh", "bash"] },
+    LanguageInfo { name: language::SIEVE, mode: "sieve", extensions: &["siv", "sieve"] },
+    LanguageInfo { name: language::SLIM, mode: "slim", extensions: &["slim"] },
+    LanguageInfo { name: language::SMALLTALK, mode: "smalltalk", extensions: &["st"] },
+    LanguageInfo { name: language::SMARTY, mode: "smarty", extensions: &["tpl"] },
+    LanguageInfo { name: language::SOLR, mode: "solr", extensions: &[] },
+    LanguageInfo { name: language::SML, mode: "mllike", extensions: &["sml", "fun", "smackspec"] },
+    LanguageInfo { name: language::SOY, mode: "soy", extensions: &["soy"] },
+    LanguageInfo { name: language::SPARQL, mode: "sparql", extensions: &["rq", "sparql"] },
+    LanguageInfo { name: language::SPREADSHEET, mode: "spreadsheet", extensions: &[] },
+    LanguageInfo { name: language::SQL, mode: "sql", extensions: &["sql"] },
+    LanguageInfo { name: language::SQLITE, mode: "sql", extensions: &["sqlite"] },
+    LanguageInfo { name: language::SQUIRREL, mode: "clike", extensions: &["nut"] },
+    LanguageInfo { name: language::STYLUS, mode: "stylus", extensions: &["styl"] },
+    LanguageInfo { name: language::SWIFT, mode: "swift", extensions: &["swift"] },
+    LanguageInfo { name: language::STEX, mode: "stex", extensions: &[] },
+    LanguageInfo { name: language::LATEX, mode: "stex", extensions: &["tex", "ltx"] },
+    LanguageInfo { name: language::SYSTEM_VERILOG, mode: "verilog", extensions: &["sv", "svh"] },
+    LanguageInfo { name: language::TCL, mode: "tcl", extensions: &["tcl"] },
+    LanguageInfo { name: language::TEXTILE, mode: "textile", extensions: &["textile"] },
+    LanguageInfo { name: language::TIDDLYWIKI, mode: "tiddlywiki", extensions: &[] },
+    LanguageInfo { name: language::TIKI_WIKI, mode: "tiki", extensions: &[] },
+    LanguageInfo { name: language::TOML, mode: "toml", extensions: &["toml"] },
+    LanguageInfo { name: language::TORNADO, mode: "tornado", extensions: &[] },
+    LanguageInfo { name: language::TROFF, mode: "troff", extensions: &["1", "2", "3", "4", "5", "6", "7", "8", "9"] },
+    LanguageInfo { name: language::TTCN, mode: "ttcn", extensions: &["ttcn", "ttcn3", "ttcnpp"] },
+    LanguageInfo { name: language::TTCN_CFG, mode: "ttcn-cfg", extensions: &["cfg"] },
+    LanguageInfo { name: language::TURTLE, mode: "turtle", extensions: &["ttl"] },
+    LanguageInfo { name: language::TYPESCRIPT, mode: "javascript", extensions: &["ts", "mts", "cts"] },
+    LanguageInfo { name: language::TYPESCRIPT_JSX, mode: "jsx", extensions: &["tsx"] },
+    LanguageInfo { name: language::TWIG, mode: "twig", extensions: &[] },
+    LanguageInfo { name: language::WEB_IDL, mode: "webidl", extensions: &["webidl"] },
+    LanguageInfo { name: language::VB_NET, mode: "vb", extensions: &["vb"] },
+    LanguageInfo { name: language::VBSCRIPT, mode: "vbscript", extensions: &["vbs"] },
+    LanguageInfo { name: language::VELOCITY, mode: "velocity", extensions: &["vtl"] },
+    LanguageInfo { name: language::VERILOG, mode: "verilog", extensions: &["v"] },
+    LanguageInfo { name: language::VHDL, mode: "vhdl", extensions: &["vhd", "vhdl"] },
+    LanguageInfo { name: language::VUE, mode: "vue", extensions: &["vue"] },
+    LanguageInfo { name: language::XML, mode: "xml", extensions: &["xml", "xsl", "xsd", "svg"] },
+    LanguageInfo { name: language::XQUERY, mode: "xquery", extensions: &["xquery", "xy"] },
+    LanguageInfo { name: language::YACAS, mode: "yacas", extensions: &["ys"] },
+    LanguageInfo { name: language::YAML, mode: "yaml", extensions: &["yaml", "yml"] },
+    LanguageInfo { name: language::Z80, mode: "z80", extensions: &["z80"] },
+    LanguageInfo { name: language::MSCGEN, mode: "mscgen", extensions: &["mscgen", "mscin", "msc"] },
+    LanguageInfo { name: language::XU, mode: "mscgen", extensions: &["xu"] },
+    LanguageInfo { name: language::MSGENNY, mode: "mscgen", extensions: &["msgenny"] },
+];
+
+/// What a language is looked up by.
+#[cfg(all(feature = "data", feature = "network"))]
+#[derive(Clone, Copy)]
+pub(crate) enum LookupBy {
+    Name,
+    Extension,
+}
+
+/// Builds the url used to look up a language. The
+/// value is percent-encoded so that names like
+/// `C++`, `C#` or `Common Lisp` reach pastemyst
+/// as they are.
+#[cfg(all(feature = "data", feature = "network"))]
+pub(crate) fn parse_url(value: &str, lookup_by: LookupBy) -> String {
+    let (path, key) = match lookup_by {
+        LookupBy::Name => ("language", "name"),
+        LookupBy::Extension => ("languageExt", "extension"),
+    };
+    let mut url = reqwest::Url::parse(&format!("{}{}", DATA_ENDPOINT, path)).expect("[pastemyst] Invalid data endpoint");
+    url.query_pairs_mut().append_pair(key, value);
+    url.to_string()
 }
 
 /// An enum of PasteMyt language constants.
@@ -178,7 +545,7 @@ pub mod language {
     pub const CRYSTAL: &str = "Crystal";
     pub const CSS: &str = "CSS";
     pub const CQL: &str = "CQL";
-    pub const DLANG: &'static str = "D";
+    pub const DLANG: &str = "D";
     pub const D: &str = "D";
     pub const DART: &str = "Dart";
     pub const DIFF: &str = "diff";
@@ -314,3 +681,342 @@ pub mod language {
     pub const XU: &str = "xu";
     pub const MSGENNY: &str = "msgenny";
 }
+
+/// Generates `Language` from pairs of a
+/// variant and its constant in `language`.
+macro_rules! languages {
+    ($($variant:ident => $constant:ident,)*) => {
+        /// The languages in `data::language` as a type, so
+        /// that a misspelled language is caught when compiling
+        /// instead of being sent to PasteMyst. Each variant is
+        /// sent as the name of its constant.
+        ///
+        /// ## Examples
+        ///
+        /// ```rust
+        /// use pastemyst::data::{language, Language};
+        ///
+        /// assert_eq!(Language::Cpp.as_str(), language::CPP);
+        /// assert_eq!("c#".parse::<Language>().unwrap(), Language::CSharp);
+        /// assert_eq!(Language::Rust.extension(), Some("rs"));
+        /// assert_eq!(Language::from_extension(".py"), Some(Language::Python));
+        /// assert_eq!(Language::from_filename("Dockerfile"), Some(Language::Docker));
+        /// assert!("Klingon".parse::<Language>().is_err());
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Language {
+            $($variant,)*
+        }
+
+        impl Language {
+            /// Every variant, in the
+            /// order of `data::language`.
+            pub const ALL: &'static [Language] = &[$(Language::$variant,)*];
+
+            /// The name PasteMyst knows it by.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Language::$variant => language::$constant,)*
+                }
+            }
+        }
+    };
+}
+
+languages! {
+    Autodetect => AUTODETECT,
+    PlainText => PLAIN,
+    Apl => APL,
+    Pgp => PGP,
+    Asn1 => ASN1,
+    Asterisk => ASTERISK,
+    Brainfuck => BRAINFUCK,
+    C => C,
+    Cpp => CPP,
+    Cobol => COBOL,
+    CSharp => CSHARP,
+    Clojure => CLOJURE,
+    ClojureScript => CLOJURE_SCRIPT,
+    Gss => GSS,
+    Cmake => CMAKE,
+    CoffeeScript => COFFEE_SCRIPT,
+    Lisp => LISP,
+    Cypher => CYPHER,
+    Cython => CYTHON,
+    Crystal => CRYSTAL,
+    Css => CSS,
+    Cql => CQL,
+    D => D,
+    Dart => DART,
+    Diff => DIFF,
+    Django => DJANGO,
+    Docker => DOCKER,
+    Dtd => DTD,
+    Dylan => DYLAN,
+    Ebnf => EBNF,
+    Ecl => ECL,
+    Edn => EDN,
+    Eiffel => EIFFEL,
+    Elm => ELM,
+    Ejs => EJS,
+    Erb => ERB,
+    Erlang => ERLANG,
+    Esper => ESPER,
+    Factor => FACTOR,
+    Fcl => FCL,
+    Forth => FORTH,
+    Fortran => FORTRAN,
+    FSharp => FSHARP,
+    Gas => GAS,
+    Gherkin => GHERKIN,
+    GithubMarkdown => GFM,
+    Go => GO,
+    Groovy => GROOVY,
+    Haml => HAML,
+    Haskell => HASKELL,
+    HaskellLiterate => HASKELL_LITERATE,
+    Haxe => HAXE,
+    Hxml => HXML,
+    AspNet => ASP_NET,
+    Html => HTML,
+    Http => HTTP,
+    Idl => IDL,
+    Pug => PUG,
+    Java => JAVA,
+    Jsp => JSP,
+    JavaScript => JAVASCRIPT,
+    Json => JSON,
+    JsonLd => JSON_LD,
+    Jsx => JSX,
+    Jinja2 => JINJA2,
+    Julia => JULIA,
+    Kotlin => KOTLIN,
+    Less => LESS,
+    LiveScript => LIVESCRIPT,
+    Lua => LUA,
+    Markdown => MARKDOWN,
+    Mirc => MIRC,
+    MariaDb => MARIA_DB,
+    Mathematica => MATHEMATICA,
+    Modelica => MODELICA,
+    Mumps => MUMPS,
+    MsSql => MS_SQL,
+    Mbox => MBOX,
+    MySql => MYSQL,
+    Nginx => NGINX,
+    Nsis => NSIS,
+    NTriples => NTRIPLES,
+    ObjectiveC => OBJ_C,
+    Ocaml => OCAML,
+    Octave => OCTAVE,
+    Oz => OZ,
+    Pascal => PASCAL,
+    PegJs => PEG_JS,
+    Perl => PERL,
+    Php => PHP,
+    Pig => PIG,
+    PlSql => PLSQL,
+    PowerShell => POWERSHELL,
+    Ini => INI,
+    Protobuf => PROTOBUF,
+    Python => PYTHON,
+    Puppet => PUPPET,
+    Q => QLANG,
+    R => RSCRIPT,
+    Rst => RST,
+    RpmChanges => RPM_CHANGES,
+    RpmSpec => RPM_SPEC,
+    Ruby => RUBY,
+    Rust => RUST,
+    Sas => SAS,
+    Sass => SASS,
+    Scala => SCALA,
+    Scheme => SCHEME,
+    Scss => SCSS,
+    Shell => SHELL,
+    Sieve => SIEVE,
+    Slim => SLIM,
+    Smalltalk => SMALLTALK,
+    Smarty => SMARTY,
+    Solr => SOLR,
+    Sml => SML,
+    Soy => SOY,
+    Sparql => SPARQL,
+    Spreadsheet => SPREADSHEET,
+    Sql => SQL,
+    Sqlite => SQLITE,
+    Squirrel => SQUIRREL,
+    Stylus => STYLUS,
+    Swift => SWIFT,
+    STex => STEX,
+    LaTex => LATEX,
+    SystemVerilog => SYSTEM_VERILOG,
+    Tcl => TCL,
+    Textile => TEXTILE,
+    TiddlyWiki => TIDDLYWIKI,
+    TikiWiki => TIKI_WIKI,
+    Toml => TOML,
+    Tornado => TORNADO,
+    Troff => TROFF,
+    Ttcn => TTCN,
+    TtcnCfg => TTCN_CFG,
+    Turtle => TURTLE,
+    TypeScript => TYPESCRIPT,
+    TypeScriptJsx => TYPESCRIPT_JSX,
+    Twig => TWIG,
+    WebIdl => WEB_IDL,
+    VbNet => VB_NET,
+    VbScript => VBSCRIPT,
+    Velocity => VELOCITY,
+    Verilog => VERILOG,
+    Vhdl => VHDL,
+    Vue => VUE,
+    Xml => XML,
+    Xquery => XQUERY,
+    Yacas => YACAS,
+    Yaml => YAML,
+    Z80 => Z80,
+    Mscgen => MSCGEN,
+    Xu => XU,
+    Msgenny => MSGENNY,
+}
+
+impl Language {
+    /// The usual file extension of the language
+    /// (without the dot), see `extension_for_language`.
+    pub fn extension(&self) -> Option<&'static str> { extension_for_language(self.as_str()) }
+
+    /// The editor mode of the language, the
+    /// `mode` field of `DataObject`. `Autodetect`
+    /// has none.
+    pub fn mode(&self) -> Option<&'static str> {
+        LANGUAGES.iter().find(|language| language.name == self.as_str()).map(|language| language.mode)
+    }
+
+    /// The language a file extension (with or without
+    /// the dot) belongs to, see `language_for_extension`.
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        language_for_extension(extension).and_then(|name| name.parse().ok())
+    }
+
+    /// The language of a file by its name,
+    /// see `language_for_filename`.
+    pub fn from_filename(filename: &str) -> Option<Language> {
+        language_for_filename(filename).and_then(|name| name.parse().ok())
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.as_str()) }
+}
+
+impl std::str::FromStr for Language {
+    type Err = PasteMystError;
+
+    /// Finds the language by its name, ignoring case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .iter()
+            .copied()
+            .find(|language| language.as_str().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| PasteMystError::UnknownLanguage(name.to_string()))
+    }
+}
+
+impl AsRef<str> for Language {
+    fn as_ref(&self) -> &str { self.as_str() }
+}
+
+impl Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The language of a pasty, either one this crate
+/// knows as a `Language` or any other name, for
+/// languages PasteMyst adds later. The functions
+/// taking a language take `impl Into<LanguageSpec>`
+/// so that a `Language`, one of the constants in
+/// `data::language` or a plain string can be given.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::{Language, LanguageSpec};
+///
+/// assert_eq!(LanguageSpec::from("rust"), LanguageSpec::Known(Language::Rust));
+/// assert_eq!(LanguageSpec::from("Carbon"), LanguageSpec::Other(String::from("Carbon")));
+/// assert_eq!(LanguageSpec::from(Language::Cpp).as_str(), "C++");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LanguageSpec {
+    /// A language this crate knows.
+    Known(Language),
+    /// Any other name, sent as is.
+    Other(String),
+}
+
+impl LanguageSpec {
+    /// The name sent to PasteMyst.
+    pub fn as_str(&self) -> &str {
+        match self {
+            LanguageSpec::Known(language) => language.as_str(),
+            LanguageSpec::Other(name) => name,
+        }
+    }
+
+    /// The language, if this crate knows it.
+    pub fn language(&self) -> Option<Language> {
+        match self {
+            LanguageSpec::Known(language) => Some(*language),
+            LanguageSpec::Other(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for LanguageSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.as_str()) }
+}
+
+impl From<Language> for LanguageSpec {
+    fn from(language: Language) -> Self { LanguageSpec::Known(language) }
+}
+
+impl From<&str> for LanguageSpec {
+    fn from(name: &str) -> Self {
+        match name.parse() {
+            Ok(language) => LanguageSpec::Known(language),
+            Err(_) => LanguageSpec::Other(name.to_string()),
+        }
+    }
+}
+
+impl From<&String> for LanguageSpec {
+    fn from(name: &String) -> Self { LanguageSpec::from(name.as_str()) }
+}
+
+impl From<String> for LanguageSpec {
+    fn from(name: String) -> Self {
+        match name.parse() {
+            Ok(language) => LanguageSpec::Known(language),
+            Err(_) => LanguageSpec::Other(name),
+        }
+    }
+}
+
+impl From<LanguageSpec> for String {
+    fn from(language: LanguageSpec) -> Self {
+        match language {
+            LanguageSpec::Known(language) => language.as_str().to_string(),
+            LanguageSpec::Other(name) => name,
+        }
+    }
+}
@@ -0,0 +1,336 @@
+/// The error type used by the `PasteMystClient`
+/// and the helpers built on top of it. Each
+/// variant wraps whatever went wrong so that
+/// you can match on it or simply print it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::error::PasteMystError;
+///
+/// let error = PasteMystError::InvalidUrl(String::from("not a url"));
+/// println!("{}", error);
+/// ```
+#[derive(Debug)]
+pub enum PasteMystError {
+    /// The request could not be sent or
+    /// the response could not be read.
+    #[cfg(feature = "network")]
+    Request(reqwest::Error),
+    /// PasteMyst responded with an error
+    /// status code, e.g. when it refuses to
+    /// create or edit a paste. The message is
+    /// the one sent by the server, or the
+    /// reason of the status if it sent none.
+    Api { status: u16, message: String },
+    /// PasteMyst refused the request because too
+    /// many were sent, `retry_after` is how long it
+    /// asked to wait from the `Retry-After` header.
+    RateLimited { retry_after: Option<std::time::Duration> },
+    /// A middleware of the client returned
+    /// an error before (or after) the
+    /// request was sent.
+    Middleware(Box<dyn std::error::Error + Send + Sync>),
+    /// The custom transport of the client
+    /// failed to send the request.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// A url could not be constructed
+    /// from the given value.
+    InvalidUrl(String),
+    /// The authorization token contains
+    /// characters that are not allowed
+    /// in a header.
+    InvalidToken,
+    /// The call requires an authorization
+    /// token but none was provided.
+    MissingToken,
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+    /// A value could not be converted
+    /// to or from json.
+    Json(serde_json::Error),
+    /// No template was registered
+    /// under the given name.
+    TemplateNotFound(String),
+    /// A template uses a placeholder no
+    /// value was provided for.
+    MissingVariable(String),
+    /// An edit contains a pasty whose id
+    /// does not belong to the paste.
+    UnknownPasty(String),
+    /// The pasty is the only one of the paste,
+    /// which can't be left without pasties.
+    LastPasty(String),
+    /// The expiry is not one of the values
+    /// in `pastemyst::time::expires_in`.
+    InvalidExpiry(String),
+    /// The name is not one of the languages
+    /// in `pastemyst::data::language`.
+    UnknownLanguage(String),
+    /// The paste id contains characters
+    /// a paste id can't have, such as
+    /// whitespace or slashes.
+    InvalidId(String),
+    /// The paste can't be sent as it is, the
+    /// message tells what is wrong with it.
+    InvalidPaste(String),
+    /// A file pattern could not be parsed,
+    /// the message tells what is wrong.
+    InvalidPattern(String),
+    /// A file pattern did not match
+    /// any file.
+    NoMatches(String),
+    /// The response body was larger than
+    /// the limit set on the client, in
+    /// bytes.
+    ResponseTooLarge { limit: usize },
+    /// A pre-upload hook of the client
+    /// refused the paste.
+    Violation(Violation),
+    /// A paste was created but removing the pastes
+    /// it replaces failed afterwards. The new paste
+    /// is kept here so that its id isn't lost.
+    CleanupFailed { paste: Box<crate::paste::PasteObject>, error: Box<PasteMystError> },
+    /// Some of the pastes a paste was split into were
+    /// created before creating the next one failed.
+    /// The ids of the created ones are kept here so
+    /// that they aren't lost.
+    SplitFailed { created: Vec<String>, error: Box<PasteMystError> },
+    /// Some of several pastes could not be deleted,
+    /// each id is kept here along with the reason.
+    DeleteFailed(Vec<(String, PasteMystError)>),
+    /// The config file could not be
+    /// read or written as toml.
+    #[cfg(feature = "config")]
+    Config(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl PasteMystError {
+    /// The status code PasteMyst responded with,
+    /// for `Api` and `RateLimited` errors.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::error::PasteMystError;
+    ///
+    /// let error = PasteMystError::Api { status: 413, message: String::from("Payload Too Large") };
+    /// assert_eq!(error.status(), Some(413));
+    /// assert_eq!(PasteMystError::MissingToken.status(), None);
+    /// ```
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            PasteMystError::Api { status, .. } => Some(*status),
+            PasteMystError::RateLimited { .. } => Some(429),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PasteMystError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "network")]
+            PasteMystError::Request(error) => write!(f, "[pastemyst] Request failed: {}", error),
+            PasteMystError::Api { status, message } => write!(f, "[pastemyst] The server responded with {}: {}", status, message),
+            PasteMystError::RateLimited { retry_after: Some(retry_after) } => {
+                write!(f, "[pastemyst] Rate limited, retry after {} seconds", retry_after.as_secs())
+            }
+            PasteMystError::RateLimited { retry_after: None } => write!(f, "[pastemyst] Rate limited"),
+            PasteMystError::Middleware(error) => write!(f, "[pastemyst] Middleware failed: {}", error),
+            PasteMystError::Transport(error) => write!(f, "[pastemyst] Transport failed: {}", error),
+            PasteMystError::InvalidUrl(url) => write!(f, "[pastemyst] Invalid url: {}", url),
+            PasteMystError::InvalidToken => write!(f, "[pastemyst] The authorization token is not a valid header value"),
+            PasteMystError::MissingToken => write!(f, "[pastemyst] This call requires an authorization token"),
+            PasteMystError::Io(error) => write!(f, "[pastemyst] I/O error: {}", error),
+            PasteMystError::Json(error) => write!(f, "[pastemyst] Invalid json: {}", error),
+            PasteMystError::TemplateNotFound(name) => write!(f, "[pastemyst] The template '{}' does not exist", name),
+            PasteMystError::MissingVariable(name) => write!(f, "[pastemyst] No value was provided for '{{{{{}}}}}'", name),
+            PasteMystError::UnknownPasty(id) => write!(f, "[pastemyst] The pasty '{}' does not belong to the paste", id),
+            PasteMystError::LastPasty(id) => write!(f, "[pastemyst] The pasty '{}' is the last one of the paste", id),
+            PasteMystError::InvalidExpiry(expires_in) => write!(f, "[pastemyst] '{}' is not a valid expiry", expires_in),
+            PasteMystError::UnknownLanguage(name) => write!(f, "[pastemyst] '{}' is not a known language", name),
+            PasteMystError::InvalidId(id) => write!(f, "[pastemyst] '{}' is not a valid paste id", id),
+            PasteMystError::InvalidPaste(reason) => write!(f, "[pastemyst] Invalid paste: {}", reason),
+            PasteMystError::InvalidPattern(reason) => write!(f, "[pastemyst] Invalid pattern: {}", reason),
+            PasteMystError::NoMatches(pattern) => write!(f, "[pastemyst] '{}' did not match any file", pattern),
+            PasteMystError::ResponseTooLarge { limit } => write!(f, "[pastemyst] The response is larger than {} bytes", limit),
+            PasteMystError::Violation(violation) => write!(f, "[pastemyst] The paste was refused: {}", violation),
+            PasteMystError::CleanupFailed { paste, error } => {
+                write!(f, "[pastemyst] The paste '{}' was created but the one it replaces was kept: {}", paste._id, error)
+            }
+            PasteMystError::SplitFailed { created, error } => {
+                write!(f, "[pastemyst] Splitting stopped after creating {}: {}", created.join(", "), error)
+            }
+            PasteMystError::DeleteFailed(failed) => {
+                write!(f, "[pastemyst] Some pastes could not be deleted")?;
+                for (id, error) in failed { write!(f, "\n'{}': {}", id, error)?; }
+                Ok(())
+            }
+            #[cfg(feature = "config")]
+            PasteMystError::Config(error) => write!(f, "[pastemyst] Invalid config: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for PasteMystError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "network")]
+            PasteMystError::Request(error) => Some(error),
+            PasteMystError::Middleware(error) => Some(error.as_ref()),
+            PasteMystError::Transport(error) => Some(error.as_ref()),
+            PasteMystError::Io(error) => Some(error),
+            PasteMystError::Json(error) => Some(error),
+            PasteMystError::CleanupFailed { error, .. } => Some(error.as_ref()),
+            PasteMystError::SplitFailed { error, .. } => Some(error.as_ref()),
+            PasteMystError::DeleteFailed(failed) => failed.first().map(|(_, error)| error as &(dyn std::error::Error + 'static)),
+            #[cfg(feature = "config")]
+            PasteMystError::Config(error) => Some(error.as_ref()),
+            PasteMystError::Api { .. }
+            | PasteMystError::RateLimited { .. }
+            | PasteMystError::InvalidUrl(_)
+            | PasteMystError::InvalidToken
+            | PasteMystError::MissingToken
+            | PasteMystError::TemplateNotFound(_)
+            | PasteMystError::MissingVariable(_)
+            | PasteMystError::UnknownPasty(_)
+            | PasteMystError::LastPasty(_)
+            | PasteMystError::InvalidExpiry(_)
+            | PasteMystError::UnknownLanguage(_)
+            | PasteMystError::InvalidId(_)
+            | PasteMystError::InvalidPaste(_)
+            | PasteMystError::InvalidPattern(_)
+            | PasteMystError::NoMatches(_)
+            | PasteMystError::ResponseTooLarge { .. }
+            | PasteMystError::Violation(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl From<reqwest::Error> for PasteMystError {
+    fn from(error: reqwest::Error) -> Self { PasteMystError::Request(error) }
+}
+
+impl From<std::io::Error> for PasteMystError {
+    fn from(error: std::io::Error) -> Self { PasteMystError::Io(error) }
+}
+
+impl From<serde_json::Error> for PasteMystError {
+    fn from(error: serde_json::Error) -> Self { PasteMystError::Json(error) }
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for PasteMystError {
+    fn from(error: reqwest_middleware::Error) -> Self {
+        match error {
+            reqwest_middleware::Error::Reqwest(error) => PasteMystError::Request(error),
+            reqwest_middleware::Error::Middleware(error) => PasteMystError::Middleware(error.into()),
+        }
+    }
+}
+
+/// Why a pre-upload hook refused a paste, see
+/// `PasteMystClient::add_pre_upload_hook`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::error::Violation;
+///
+/// let violation = Violation::new("no-secrets", "The paste contains an AWS key");
+/// assert_eq!(violation.to_string(), "no-secrets: The paste contains an AWS key");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// A short name of the policy
+    /// that was violated.
+    pub rule: String,
+    /// What is wrong with the paste.
+    pub message: String,
+}
+
+impl Violation {
+    /// Creates a violation of the given rule.
+    pub fn new(rule: &str, message: &str) -> Self {
+        Violation { rule: rule.to_string(), message: message.to_string() }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+impl From<Violation> for PasteMystError {
+    fn from(violation: Violation) -> Self { PasteMystError::Violation(violation) }
+}
+
+/// The body pastemyst sends
+/// along with an error status.
+#[cfg(all(feature = "network", feature = "endpoints"))]
+#[derive(serde::Deserialize)]
+#[allow(non_snake_case)]
+struct ErrorBody { statusMessage: String }
+
+/// Returns the response as is if its status is a
+/// success, otherwise a `PasteMystError::RateLimited`
+/// for a 429 and a `PasteMystError::Api` with the
+/// message sent by the server for the others.
+#[cfg(all(feature = "blocking", feature = "endpoints"))]
+pub(crate) fn check_status(
+    response: reqwest::blocking::Response,
+) -> Result<reqwest::blocking::Response, PasteMystError> {
+    if response.status().is_success() { return Ok(response); }
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(PasteMystError::RateLimited { retry_after: retry_after(response.headers()) });
+    }
+    let status = response.status().as_u16();
+    Err(api_error(status, &response.text().unwrap_or_default()))
+}
+
+/// Returns the response as is if its status is a
+/// success, otherwise a `PasteMystError::RateLimited`
+/// for a 429 and a `PasteMystError::Api` with the
+/// message sent by the server for the others.
+#[cfg(all(feature = "async", feature = "endpoints"))]
+pub(crate) async fn check_status_async(response: reqwest::Response) -> Result<reqwest::Response, PasteMystError> {
+    if response.status().is_success() { return Ok(response); }
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(PasteMystError::RateLimited { retry_after: retry_after(response.headers()) });
+    }
+    let status = response.status().as_u16();
+    Err(api_error(status, &response.text().await.unwrap_or_default()))
+}
+
+/// Creates a `PasteMystError::Api` from an error
+/// response, the body is used as the message if
+/// it's not the json pastemyst usually sends and
+/// the reason of the status if it's empty.
+#[cfg(all(feature = "network", feature = "endpoints"))]
+fn api_error(status: u16, body: &str) -> PasteMystError {
+    let message = match serde_json::from_str::<ErrorBody>(body) {
+        Ok(body) => body.statusMessage,
+        Err(_) => body.trim().to_string(),
+    };
+    let message = match message.trim() {
+        "" => reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string(),
+        _ => message,
+    };
+    PasteMystError::Api { status, message }
+}
+
+/// How long the `Retry-After` header of a response
+/// asks to wait, only the number of seconds form
+/// of the header is understood.
+#[cfg(all(feature = "network", feature = "endpoints"))]
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let seconds = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(std::time::Duration::from_secs(seconds))
+}
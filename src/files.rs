@@ -0,0 +1,538 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::data::LanguageSpec;
+use crate::error::PasteMystError;
+use crate::paste::{CreateObject, PasteBuilder, PastyBuilder, PastyObject};
+#[cfg(all(feature = "paste", feature = "network"))]
+use crate::paste::PasteObject;
+use crate::time::ExpiresIn;
+use crate::token::ApiToken;
+
+/// The options used when creating a paste out of
+/// files. By default the paste is titled after the
+/// files, never expires, is unlisted and has no
+/// tags and no owner.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::files::FileOptions;
+/// use pastemyst::time::ExpiresIn;
+///
+/// let options = FileOptions {
+///     title: Some(String::from("My project")),
+///     expires_in: ExpiresIn::OneWeek,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FileOptions {
+    /// The title of the paste, if `None`
+    /// the file names are joined.
+    pub title: Option<String>,
+    /// When the paste will expire.
+    pub expires_in: ExpiresIn,
+    /// Whether only the owner can see
+    /// the paste, needs `auth_token`.
+    pub private: bool,
+    /// Whether the paste is listed on the
+    /// owner's profile, needs `auth_token`.
+    pub public: bool,
+    /// The tags of the paste.
+    pub tags: Vec<String>,
+    /// The token of the account the paste is
+    /// created on, the paste has no owner if
+    /// it's `None`.
+    pub auth_token: Option<ApiToken>,
+}
+
+/// Reads a file into a pasty titled after its name
+/// (without the directories) with the language
+/// found from it by `data::language_for_filename`,
+/// autodetected if it's not known.
+///
+/// Returns `PasteMystError::Io` if the file can't be
+/// read or isn't valid UTF-8.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::language;
+/// use pastemyst::files::pasty_from_file;
+///
+/// let path = std::env::temp_dir().join("pastemyst-doctest-pasty.rs");
+/// std::fs::write(&path, "fn main() {}").unwrap();
+/// let pasty = pasty_from_file(&path).unwrap();
+/// assert_eq!(pasty.title, "pastemyst-doctest-pasty.rs");
+/// assert_eq!(pasty.language, language::RUST);
+/// assert_eq!(pasty.code, "fn main() {}");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn pasty_from_file(path: impl AsRef<Path>) -> Result<PastyObject, PasteMystError> {
+    let path = path.as_ref();
+    let code = std::fs::read_to_string(path)?;
+    Ok(PastyBuilder::from_filename(&path.to_string_lossy()).code(code).build())
+}
+
+/// Reads the files into a `CreateObject` with one pasty
+/// per file, in the order they're given, see
+/// `pasty_from_file`. Nothing is sent, use
+/// `create_paste_from_files` for that.
+///
+/// Returns `PasteMystError::InvalidPaste` if no
+/// files are given.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::files::*;
+///
+/// let directory = std::env::temp_dir();
+/// let paths = vec![directory.join("pastemyst-doctest-a.py"), directory.join("pastemyst-doctest-b.md")];
+/// std::fs::write(&paths[0], "print('a')").unwrap();
+/// std::fs::write(&paths[1], "# b").unwrap();
+/// let contents = paste_from_files(&paths, &FileOptions::default()).unwrap();
+/// assert_eq!(contents.title, "pastemyst-doctest-a.py, pastemyst-doctest-b.md");
+/// assert_eq!(contents.pasties.len(), 2);
+/// # for path in &paths { std::fs::remove_file(path).unwrap(); }
+/// ```
+pub fn paste_from_files(paths: &[PathBuf], options: &FileOptions) -> Result<CreateObject, PasteMystError> {
+    builder_from_files(paths, options)?.build()
+}
+
+/// Reads files into a paste, one pasty per file, and
+/// creates it on [pastemyst](https://paste.myst.rs),
+/// see `paste_from_files`. This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use pastemyst::files::*;
+///
+/// fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let paths = vec![PathBuf::from("src/main.rs"), PathBuf::from("Cargo.toml")];
+///     let paste = create_paste_from_files(&paths, &FileOptions::default())?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn create_paste_from_files(paths: &[PathBuf], options: &FileOptions) -> Result<PasteObject, PasteMystError> {
+    builder_from_files(paths, options)?.send()
+}
+
+/// Reads files into a paste, one pasty per file, and
+/// creates it on [pastemyst](https://paste.myst.rs),
+/// see `paste_from_files`. This is an asynchronous
+/// method, the files are still read synchronously.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use std::path::PathBuf;
+/// use pastemyst::files::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let paths = vec![PathBuf::from("src/main.rs")];
+///     let options = FileOptions {
+///         private: true,
+///         auth_token: Some(pastemyst::token::ApiToken::from_env()?),
+///         ..Default::default()
+///     };
+///     let paste = create_paste_from_files_async(&paths, &options).await?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn create_paste_from_files_async(paths: &[PathBuf], options: &FileOptions) -> Result<PasteObject, PasteMystError> {
+    builder_from_files(paths, options)?.send_async().await
+}
+
+/// Reads everything a reader holds into a pasty with
+/// the given title and language (one of the names in
+/// `data::language`), e.g. the output of a program or
+/// stdin.
+///
+/// Returns `PasteMystError::Io` if reading fails
+/// or the contents aren't valid UTF-8.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::language;
+/// use pastemyst::files::pasty_from_reader;
+///
+/// let output = "warning: unused variable".as_bytes();
+/// let pasty = pasty_from_reader(output, "build.log", language::PLAIN).unwrap();
+/// assert_eq!(pasty.code, "warning: unused variable");
+/// ```
+pub fn pasty_from_reader(
+    mut reader: impl Read,
+    title: &str,
+    language: impl Into<LanguageSpec>,
+) -> Result<PastyObject, PasteMystError> {
+    let mut code = String::new();
+    reader.read_to_string(&mut code)?;
+    Ok(PastyBuilder::new().title(title).language(language).code(code).build())
+}
+
+/// Reads everything a reader holds into a pasty
+/// and creates a paste out of it, so that output can
+/// be piped into a paste. The paste is titled after
+/// the pasty unless the options give a title, see
+/// `pasty_from_reader`. This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::data::language;
+/// use pastemyst::files::*;
+///
+/// fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let stdin = std::io::stdin();
+///     let paste = create_paste_from_reader(stdin.lock(), "stdin", language::AUTODETECT, &FileOptions::default())?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn create_paste_from_reader(
+    reader: impl Read,
+    title: &str,
+    language: impl Into<LanguageSpec>,
+    options: &FileOptions,
+) -> Result<PasteObject, PasteMystError> {
+    let pasty = pasty_from_reader(reader, title, language)?;
+    builder_from_options(options, || title.to_string()).pasty(pasty).send()
+}
+
+/// Reads everything an `AsyncRead` holds into a pasty and
+/// creates a paste out of it, see `create_paste_from_reader`.
+/// This is an asynchronous method. The reader is a
+/// `futures::io::AsyncRead`, tokio readers can be turned into
+/// one with `tokio_util::compat`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::data::language;
+/// use pastemyst::files::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let output = futures::io::Cursor::new(b"Hello, World!".to_vec());
+///     let paste = create_paste_from_async_reader(output, "output", language::PLAIN, &FileOptions::default()).await?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn create_paste_from_async_reader(
+    mut reader: impl futures::io::AsyncRead + Unpin,
+    title: &str,
+    language: impl Into<LanguageSpec>,
+    options: &FileOptions,
+) -> Result<PasteObject, PasteMystError> {
+    use futures::io::AsyncReadExt;
+
+    let mut code = String::new();
+    reader.read_to_string(&mut code).await?;
+    let pasty = PastyBuilder::new().title(title).language(language).code(code);
+    builder_from_options(options, || title.to_string()).pasty(pasty).send_async().await
+}
+
+/// How `paste_from_dir` picks the files of a
+/// directory. By default the `.gitignore` files
+/// are followed, hidden files are left out and
+/// files over 1 MiB are skipped.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::files::DirOptions;
+///
+/// let options = DirOptions {
+///     exclude: vec![String::from("*.lock"), String::from("target/")],
+///     max_file_size: 64 * 1024,
+///     ..Default::default()
+/// };
+/// ```
+#[cfg(feature = "walk")]
+#[derive(Debug, Clone)]
+pub struct DirOptions {
+    /// Patterns of the files to leave out,
+    /// written like the lines of a `.gitignore`.
+    pub exclude: Vec<String>,
+    /// Files larger than this, in
+    /// bytes, are skipped.
+    pub max_file_size: u64,
+    /// Whether the `.gitignore` (and `.ignore`)
+    /// files of the directory are followed.
+    pub respect_gitignore: bool,
+    /// Whether hidden files and
+    /// directories are included.
+    pub hidden: bool,
+}
+
+#[cfg(feature = "walk")]
+impl Default for DirOptions {
+    fn default() -> Self {
+        DirOptions { exclude: Vec::new(), max_file_size: 1024 * 1024, respect_gitignore: true, hidden: false }
+    }
+}
+
+/// Walks a directory and reads every text file in it
+/// into a pasty titled after its path relative to the
+/// directory, sorted by that path. Files that are too
+/// large, excluded, empty or binary (they contain a nul
+/// byte or aren't valid UTF-8) are skipped. Nothing is sent,
+/// use `create_paste_from_dir` for that.
+///
+/// If no title is given the paste is titled after
+/// the directory. Returns `PasteMystError::InvalidPaste`
+/// if no file is left.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::files::*;
+///
+/// let root = std::env::temp_dir().join("pastemyst-doctest-dir");
+/// std::fs::create_dir_all(root.join("src")).unwrap();
+/// std::fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+/// std::fs::write(root.join("notes.log"), "skipped").unwrap();
+/// std::fs::write(root.join("image.png"), [0x89, b'P', b'N', b'G', 0]).unwrap();
+///
+/// let options = DirOptions { exclude: vec![String::from("*.log")], ..Default::default() };
+/// let contents = paste_from_dir(&root, &options, &FileOptions::default()).unwrap();
+/// assert_eq!(contents.title, "pastemyst-doctest-dir");
+/// assert_eq!(contents.pasties.len(), 1);
+/// assert_eq!(contents.pasties[0].title, "src/main.rs");
+/// # std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[cfg(feature = "walk")]
+pub fn paste_from_dir(
+    root: impl AsRef<Path>,
+    dir_options: &DirOptions,
+    options: &FileOptions,
+) -> Result<CreateObject, PasteMystError> {
+    builder_from_dir(root.as_ref(), dir_options, options)?.build()
+}
+
+/// Walks a directory into a paste, one pasty per text
+/// file, and creates it on [pastemyst](https://paste.myst.rs),
+/// see `paste_from_dir`. This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::files::*;
+///
+/// fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let options = DirOptions { exclude: vec![String::from("target/")], ..Default::default() };
+///     let paste = create_paste_from_dir(".", &options, &FileOptions::default())?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "walk", feature = "paste", feature = "blocking"))]
+pub fn create_paste_from_dir(
+    root: impl AsRef<Path>,
+    dir_options: &DirOptions,
+    options: &FileOptions,
+) -> Result<PasteObject, PasteMystError> {
+    builder_from_dir(root.as_ref(), dir_options, options)?.send()
+}
+
+/// Walks a directory into a paste, one pasty per text
+/// file, and creates it on [pastemyst](https://paste.myst.rs),
+/// see `paste_from_dir`. This is an asynchronous method,
+/// the directory is still read synchronously.
+#[cfg(all(feature = "walk", feature = "paste", feature = "async"))]
+pub async fn create_paste_from_dir_async(
+    root: impl AsRef<Path>,
+    dir_options: &DirOptions,
+    options: &FileOptions,
+) -> Result<PasteObject, PasteMystError> {
+    builder_from_dir(root.as_ref(), dir_options, options)?.send_async().await
+}
+
+/// Walks the directory into a `PasteBuilder`
+/// set up with the options.
+#[cfg(feature = "walk")]
+fn builder_from_dir(root: &Path, dir_options: &DirOptions, options: &FileOptions) -> Result<PasteBuilder, PasteMystError> {
+    let mut overrides = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in &dir_options.exclude {
+        overrides.add(&format!("!{}", pattern)).map_err(|error| walk_error(error, pattern))?;
+    }
+    let overrides = overrides.build().map_err(|error| walk_error(error, root.to_string_lossy()))?;
+    let walker = ignore::WalkBuilder::new(root)
+        .overrides(overrides)
+        .hidden(!dir_options.hidden)
+        .git_ignore(dir_options.respect_gitignore)
+        .git_global(dir_options.respect_gitignore)
+        .git_exclude(dir_options.respect_gitignore)
+        .ignore(dir_options.respect_gitignore)
+        .require_git(false)
+        .max_filesize(Some(dir_options.max_file_size))
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build();
+
+    let mut builder = builder_from_options(options, || {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        root.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+    });
+    for entry in walker {
+        let entry = entry.map_err(|error| walk_error(error, root.to_string_lossy()))?;
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) { continue; }
+        let bytes = std::fs::read(entry.path())?;
+        let code = match String::from_utf8(bytes) {
+            Ok(code) if !code.contains('\0') && !code.trim().is_empty() => code,
+            _ => continue,
+        };
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let title = slash_path(relative);
+        builder = builder.pasty(PastyBuilder::from_filename(&title).title(title).code(code));
+    }
+    Ok(builder)
+}
+
+/// Turns an error of the directory walk into an
+/// I/O error, or an invalid pattern naming the
+/// pattern it was about.
+#[cfg(feature = "walk")]
+fn walk_error(error: ignore::Error, what: impl std::fmt::Display) -> PasteMystError {
+    match error.into_io_error() {
+        Some(error) => PasteMystError::Io(error),
+        None => PasteMystError::InvalidPattern(what.to_string()),
+    }
+}
+
+/// Reads every file a glob pattern matches into a pasty
+/// titled after the matched path, e.g. `src/**/*.rs`.
+/// The paths are sorted and each file is read once,
+/// directories are left out. Nothing is sent, use
+/// `create_paste_from_glob` for that.
+///
+/// If no title is given the paste is titled after the
+/// pattern. Returns `PasteMystError::InvalidPattern` if
+/// the pattern can't be parsed and `PasteMystError::NoMatches`
+/// if it matches no file.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::error::PasteMystError;
+/// use pastemyst::files::*;
+///
+/// let root = std::env::temp_dir().join("pastemyst-doctest-glob");
+/// std::fs::create_dir_all(root.join("b")).unwrap();
+/// std::fs::write(root.join("b").join("two.rs"), "// two").unwrap();
+/// std::fs::write(root.join("one.rs"), "// one").unwrap();
+/// std::fs::write(root.join("three.txt"), "three").unwrap();
+///
+/// let pattern = format!("{}/**/*.rs", root.display());
+/// let contents = paste_from_glob(&pattern, &FileOptions::default()).unwrap();
+/// assert_eq!(contents.pasties.len(), 2);
+/// assert!(contents.pasties[0].title.ends_with("b/two.rs"));
+///
+/// let pattern = format!("{}/*.md", root.display());
+/// assert!(matches!(paste_from_glob(&pattern, &FileOptions::default()), Err(PasteMystError::NoMatches(_))));
+/// # std::fs::remove_dir_all(&root).unwrap();
+/// ```
+#[cfg(feature = "glob")]
+pub fn paste_from_glob(pattern: &str, options: &FileOptions) -> Result<CreateObject, PasteMystError> {
+    builder_from_glob(pattern, options)?.build()
+}
+
+/// Reads the files a glob pattern matches into a paste,
+/// one pasty per file, and creates it on
+/// [pastemyst](https://paste.myst.rs), see
+/// `paste_from_glob`. This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::files::*;
+///
+/// fn main() -> Result<(), pastemyst::error::PasteMystError> {
+///     let paste = create_paste_from_glob("src/**/*.rs", &FileOptions::default())?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "glob", feature = "paste", feature = "blocking"))]
+pub fn create_paste_from_glob(pattern: &str, options: &FileOptions) -> Result<PasteObject, PasteMystError> {
+    builder_from_glob(pattern, options)?.send()
+}
+
+/// Reads the files a glob pattern matches into a paste,
+/// one pasty per file, and creates it on
+/// [pastemyst](https://paste.myst.rs), see
+/// `paste_from_glob`. This is an asynchronous method,
+/// the files are still read synchronously.
+#[cfg(all(feature = "glob", feature = "paste", feature = "async"))]
+pub async fn create_paste_from_glob_async(pattern: &str, options: &FileOptions) -> Result<PasteObject, PasteMystError> {
+    builder_from_glob(pattern, options)?.send_async().await
+}
+
+/// Reads the files the pattern matches
+/// into a `PasteBuilder` set up with
+/// the options.
+#[cfg(feature = "glob")]
+fn builder_from_glob(pattern: &str, options: &FileOptions) -> Result<PasteBuilder, PasteMystError> {
+    let paths = glob::glob(pattern).map_err(|error| PasteMystError::InvalidPattern(format!("{}: {}", pattern, error)))?;
+    let mut files = std::collections::BTreeSet::new();
+    for path in paths {
+        let path = path.map_err(std::io::Error::from)?;
+        if path.is_file() { files.insert(path); }
+    }
+    if files.is_empty() { return Err(PasteMystError::NoMatches(pattern.to_string())); }
+
+    let mut builder = builder_from_options(options, || pattern.to_string());
+    for path in files {
+        let mut pasty = pasty_from_file(&path)?;
+        pasty.title = slash_path(&path);
+        builder = builder.pasty(pasty);
+    }
+    Ok(builder)
+}
+
+/// Reads the files into a `PasteBuilder`
+/// set up with the options.
+fn builder_from_files(paths: &[PathBuf], options: &FileOptions) -> Result<PasteBuilder, PasteMystError> {
+    let mut builder = builder_from_options(options, || {
+        paths
+            .iter()
+            .map(|path| path.file_name().map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+    for path in paths { builder = builder.pasty(pasty_from_file(path)?); }
+    Ok(builder)
+}
+
+/// A `PasteBuilder` set up with the options, the
+/// title is made by `title` when none is given.
+fn builder_from_options(options: &FileOptions, title: impl FnOnce() -> String) -> PasteBuilder {
+    let mut builder = PasteBuilder::new()
+        .title(options.title.clone().unwrap_or_else(title))
+        .expires(options.expires_in)
+        .private(options.private)
+        .public(options.public)
+        .tags(&options.tags);
+    if let Some(token) = &options.auth_token { builder = builder.token(token.clone()); }
+    builder
+}
+
+/// A path written with forward slashes
+/// whatever the platform, for titles.
+#[cfg(any(feature = "walk", feature = "glob"))]
+fn slash_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if std::path::MAIN_SEPARATOR == '/' { path.into_owned() } else { path.replace(std::path::MAIN_SEPARATOR, "/") }
+}
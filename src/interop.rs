@@ -0,0 +1,355 @@
+use serde::Deserialize;
+#[cfg(all(feature = "paste", feature = "blocking"))]
+use std::process::Command;
+
+#[cfg(all(feature = "paste", feature = "blocking"))]
+use crate::paste::{self, PasteObject, PasteResult};
+use crate::paste::{CreateObject, PastyObject};
+use crate::time::ExpiresIn;
+
+/// The options used when turning the output
+/// of cargo into a paste. The defaults are
+/// fine for most cases: the first three errors
+/// get a source excerpt, warnings are kept and
+/// the paste expires in a week.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::interop::CargoPasteOptions;
+///
+/// let options = CargoPasteOptions {
+///     max_excerpts: 5,
+///     include_warnings: false,
+///     ..Default::default()
+/// };
+/// assert_eq!(options.context_lines, 3);
+/// ```
+pub struct CargoPasteOptions {
+    /// The title of the paste, if `None`
+    /// a title is generated from the
+    /// amount of errors and warnings.
+    pub title: Option<String>,
+    /// How many errors get their own
+    /// pasty with a source excerpt.
+    pub max_excerpts: usize,
+    /// The amount of lines shown before
+    /// and after the offending line(s).
+    pub context_lines: usize,
+    /// Whether warnings are included
+    /// in the diagnostics pasty.
+    pub include_warnings: bool,
+    /// When the paste will expire.
+    pub expires_in: ExpiresIn,
+}
+
+impl Default for CargoPasteOptions {
+    fn default() -> Self {
+        CargoPasteOptions {
+            title: None,
+            max_excerpts: 3,
+            context_lines: 3,
+            include_warnings: true,
+            expires_in: ExpiresIn::OneWeek,
+        }
+    }
+}
+
+/// A single error or warning emitted
+/// by the compiler, as reported by
+/// `cargo build --message-format=json`.
+#[derive(Debug, Clone)]
+pub struct CargoDiagnostic {
+    /// The level of the diagnostic,
+    /// usually `error` or `warning`.
+    pub level: String,
+    /// The short message, i.e, the
+    /// first line of the diagnostic.
+    pub message: String,
+    /// The diagnostic as rendered by
+    /// rustc on the terminal.
+    pub rendered: String,
+    /// The file of the primary span,
+    /// relative to the workspace root.
+    pub file_name: Option<String>,
+    /// The first line of the primary span.
+    pub line_start: usize,
+    /// The last line of the primary span.
+    pub line_end: usize,
+}
+
+impl CargoDiagnostic {
+    /// Whether the diagnostic is an error,
+    /// internal compiler errors included.
+    pub fn is_error(&self) -> bool { self.level.starts_with("error") }
+}
+
+/// Parses the output of `cargo build --message-format=json`
+/// (or `check`, `clippy`, etc.) and returns all the compiler
+/// diagnostics in it. Lines which are not json or are not
+/// compiler messages are skipped, as well as the summary
+/// lines like "aborting due to previous error". Diagnostics
+/// without a location, such as linker errors, are kept with
+/// no `file_name`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::interop::parse_cargo_messages;
+///
+/// let output = r#"{"reason":"compiler-message","message":{"message":"unused variable: `x`","level":"warning","rendered":"warning: unused variable: `x`\n","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"is_primary":true}]}}
+/// {"reason":"build-finished","success":true}"#;
+/// let diagnostics = parse_cargo_messages(output);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].file_name.as_deref(), Some("src/main.rs"));
+/// assert!(!diagnostics[0].is_error());
+///
+/// let output = r#"{"reason":"compiler-message","message":{"message":"linking with `cc` failed: exit status: 1","level":"error","rendered":"error: linking with `cc` failed\n","spans":[]}}
+/// {"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","level":"error","rendered":"error: aborting due to 1 previous error\n","spans":[]}}"#;
+/// let diagnostics = parse_cargo_messages(output);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].file_name, None);
+/// assert!(diagnostics[0].is_error());
+/// ```
+pub fn parse_cargo_messages(output: &str) -> Vec<CargoDiagnostic> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|line| line.reason == "compiler-message")
+        .filter_map(|line| line.message)
+        .filter(|message| !is_summary(message))
+        .map(|message| {
+            let span = message.spans.iter().find(|span| span.is_primary);
+            CargoDiagnostic {
+                file_name: span.map(|span| span.file_name.clone()),
+                line_start: span.map_or(0, |span| span.line_start),
+                line_end: span.map_or(0, |span| span.line_end),
+                rendered: message.rendered.clone().unwrap_or_else(|| message.message.clone()),
+                level: message.level,
+                message: message.message,
+            }
+        })
+        .collect()
+}
+
+/// Turns the output of `cargo build --message-format=json`
+/// into a `CreateObject` ready to be sent to pastemyst. The
+/// first pasty holds all the rendered diagnostics, followed
+/// by a pasty with a source excerpt for each of the first
+/// `max_excerpts` errors. Source files are read relative
+/// to `root`, which should be the workspace root, excerpts
+/// of files that cannot be read are skipped. Output without
+/// any diagnostic gives a pasty saying so rather than an
+/// empty one, which pastemyst would refuse.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::interop::*;
+///
+/// let output = r#"{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","rendered":"error[E0308]: mismatched types\n","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"is_primary":true}]}}"#;
+/// let paste = cargo_output_to_paste(output, ".", &CargoPasteOptions::default());
+/// assert_eq!(paste.title, "[cargo] 1 error, 0 warnings");
+///
+/// let paste = cargo_output_to_paste("", ".", &CargoPasteOptions::default());
+/// assert!(paste.validate().is_ok());
+/// ```
+pub fn cargo_output_to_paste(
+    output: &str,
+    root: impl AsRef<std::path::Path>,
+    options: &CargoPasteOptions,
+) -> CreateObject {
+    let diagnostics: Vec<CargoDiagnostic> = parse_cargo_messages(output)
+        .into_iter()
+        .filter(|diagnostic| options.include_warnings || diagnostic.is_error())
+        .collect();
+    let errors = diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count();
+    let warnings = diagnostics.len() - errors;
+
+    let mut pasties = vec![PastyObject {
+        _id: str!(""),
+        language: str!(crate::data::language::PLAIN),
+        title: str!("Diagnostics"),
+        code: match diagnostics.is_empty() {
+            true => str!("No errors or warnings were emitted."),
+            false => diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.rendered.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        },
+    }];
+    for diagnostic in diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_error())
+        .take(options.max_excerpts)
+    {
+        if let Some(pasty) = excerpt(diagnostic, root.as_ref(), options.context_lines) {
+            pasties.push(pasty);
+        }
+    }
+
+    CreateObject {
+        title: options.title.clone().unwrap_or_else(|| format!(
+            "[cargo] {} error{}, {} warning{}",
+            errors, if errors == 1 { "" } else { "s" },
+            warnings, if warnings == 1 { "" } else { "s" },
+        )),
+        expiresIn: options.expires_in,
+        isPrivate: false,
+        isPublic: false,
+        tags: vec![str!("cargo")],
+        pasties,
+    }
+}
+
+/// Runs the given cargo `Command` with `--message-format=json`
+/// appended to it, collects the errors and warnings it emits
+/// and sends them to [pastemyst](https://paste.myst.rs) as a
+/// paste, using the default `CargoPasteOptions`. This is
+/// handy for asking for help on forums. This method is
+/// synchronous.
+///
+/// The source excerpts are read relative to the command's
+/// current directory, or the process' one if it is unset.
+/// If the command fails, what it wrote to stderr is added
+/// as a pasty of its own, as cargo reports some errors
+/// (a missing manifest, a failed build script) only there.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use std::process::Command;
+/// use pastemyst::interop::*;
+/// use pastemyst::paste::PasteResult;
+///
+/// fn main() -> PasteResult<()> {
+///     let mut command = Command::new("cargo");
+///     command.arg("build");
+///     let paste = paste_cargo_output(command)?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn paste_cargo_output(command: Command) -> PasteResult<PasteObject> {
+    paste_cargo_output_with(command, &CargoPasteOptions::default())
+}
+
+/// The same as `paste_cargo_output` except that
+/// it takes `CargoPasteOptions` to customize
+/// the paste being sent.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use std::process::Command;
+/// use pastemyst::interop::*;
+/// use pastemyst::paste::PasteResult;
+///
+/// fn main() -> PasteResult<()> {
+///     let mut command = Command::new("cargo");
+///     command.arg("check").current_dir("path/to/project");
+///     let options = CargoPasteOptions {
+///         title: Some(String::from("Help! My code doesn't compile")),
+///         include_warnings: false,
+///         ..Default::default()
+///     };
+///     let paste = paste_cargo_output_with(command, &options)?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn paste_cargo_output_with(
+    mut command: Command,
+    options: &CargoPasteOptions,
+) -> PasteResult<PasteObject> {
+    let root = match command.get_current_dir() {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let output = command.arg("--message-format=json").output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut contents = cargo_output_to_paste(&stdout, root, options);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() && !stderr.trim().is_empty() {
+        if options.title.is_none() && !parse_cargo_messages(&stdout).iter().any(CargoDiagnostic::is_error) {
+            contents.title = str!("[cargo] build failed");
+        }
+        contents.pasties.push(PastyObject {
+            _id: str!(""),
+            language: str!(crate::data::language::PLAIN),
+            title: str!("stderr"),
+            code: stderr.into_owned(),
+        });
+    }
+    Ok(paste::create_paste(contents)?)
+}
+
+/// Whether a message only sums up the others, e.g.
+/// "aborting due to 2 previous errors" or the hint
+/// to run `rustc --explain`.
+fn is_summary(message: &CompilerMessage) -> bool {
+    message.spans.is_empty()
+        && (message.level == "failure-note"
+            || message.message.starts_with("aborting due to")
+            || message.message.ends_with("warning emitted")
+            || message.message.ends_with("warnings emitted"))
+}
+
+/// Creates a pasty holding the lines around the
+/// primary span of a diagnostic, with the line
+/// numbers prefixed the same way rustc does.
+/// There's no excerpt if none of those lines
+/// are in the file, e.g. when it changed since
+/// it was compiled.
+fn excerpt(diagnostic: &CargoDiagnostic, root: &std::path::Path, context: usize) -> Option<PastyObject> {
+    let file_name = diagnostic.file_name.as_ref()?;
+    let source = std::fs::read_to_string(root.join(file_name)).ok()?;
+    let first = diagnostic.line_start.saturating_sub(context).max(1);
+    let last = diagnostic.line_end + context;
+    let width = last.to_string().len();
+    let code = source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(number, _)| *number >= first && *number <= last)
+        .map(|(number, line)| format!("{:>width$} | {}", number, line, width = width))
+        .collect::<Vec<String>>()
+        .join("\n");
+    if code.is_empty() { return None; }
+    Some(PastyObject {
+        _id: str!(""),
+        language: str!(crate::data::language::RUST),
+        title: format!("{}:{}", file_name, diagnostic.line_start),
+        code,
+    })
+}
+
+/// A line of cargo's json output, only the
+/// fields that are needed are deserialized.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+/// The diagnostic emitted by rustc which
+/// is wrapped by `CargoMessage`.
+#[derive(Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    rendered: Option<String>,
+    spans: Vec<CompilerSpan>,
+}
+
+/// The location a diagnostic points at.
+#[derive(Deserialize)]
+struct CompilerSpan {
+    file_name: String,
+    line_start: usize,
+    line_end: usize,
+    is_primary: bool,
+}
@@ -0,0 +1,359 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+use serde::de::Error as _;
+use serde_json::{Map, Value};
+
+use crate::error::PasteMystError;
+use crate::paste::{EditHistory, EditType, PasteObject, PastyObject};
+use crate::user::UserObject;
+
+/// A value parsed in lenient mode along with
+/// a warning for every field that had to be
+/// coerced (or defaulted) because its type
+/// wasn't the one documented by pastemyst.
+///
+/// An empty `warnings` list means the response
+/// could have been parsed the regular way.
+#[derive(Clone)]
+pub struct Lenient<T> {
+    /// The parsed value.
+    pub value: T,
+    /// A description of every field that was
+    /// coerced, e.g. `stars: expected a number,
+    /// got "5"`.
+    pub warnings: Vec<String>,
+    /// Whether the response contained invalid
+    /// UTF-8 which was replaced with `U+FFFD`.
+    /// This happens when malformed content was
+    /// uploaded to a pasty.
+    pub replaced_invalid_utf8: bool,
+}
+
+impl<T> Lenient<T> {
+    /// Whether the value was
+    /// parsed without coercion.
+    pub fn is_exact(&self) -> bool { self.warnings.is_empty() && !self.replaced_invalid_utf8 }
+
+    /// Drops the warnings and
+    /// returns the value.
+    pub fn into_value(self) -> T { self.value }
+}
+
+/// Parses a paste the way pastemyst sends it but
+/// without failing when a field has an unexpected
+/// type. Numbers sent as strings (and the other way
+/// around), booleans sent as strings or numbers and
+/// tags sent as a single comma separated string are
+/// coerced. Fields that are missing, null or can't
+/// be coerced safely are set to their default value.
+/// Either way a warning is recorded.
+///
+/// Only the body not being a json object is an error.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::lenient;
+///
+/// let body = r#"{
+///     "_id": "hipfqanx", "ownerId": "", "title": "Hello",
+///     "createdAt": "1612534264", "expiresIn": "never", "deletesAt": 0,
+///     "stars": 2, "isPrivate": false, "isPublic": "false", "tags": "a,b",
+///     "pasties": [{ "_id": "x", "language": "Rust", "title": "", "code": "fn main() {}" }],
+///     "edits": []
+/// }"#;
+/// let paste = lenient::paste_from_str(body).unwrap();
+/// assert_eq!(paste.value.createdAt, 1612534264);
+/// assert_eq!(paste.value.tags, vec!["a", "b"]);
+/// assert_eq!(paste.warnings.len(), 3);
+/// ```
+pub fn paste_from_str(body: &str) -> Result<Lenient<PasteObject>, PasteMystError> {
+    paste_from_value(serde_json::from_str(body)?)
+}
+
+/// The same as `paste_from_str` except that it takes
+/// the raw body of the response. Invalid UTF-8 in it,
+/// usually from malformed content in a pasty, is
+/// replaced with `U+FFFD` instead of failing the
+/// whole call and `replaced_invalid_utf8` is set.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::lenient;
+///
+/// let mut body = br#"{
+///     "_id": "hipfqanx", "ownerId": "", "title": "Hello",
+///     "createdAt": 1612534264, "expiresIn": "never", "deletesAt": 0,
+///     "stars": 2, "isPrivate": false, "isPublic": false, "tags": [],
+///     "pasties": [{ "_id": "x", "language": "Text", "title": "", "code": "INVALID" }],
+///     "edits": []
+/// }"#.to_vec();
+/// let start = body.windows(7).position(|window| window == b"INVALID").unwrap();
+/// body[start] = 0xFF;
+/// let paste = lenient::paste_from_bytes(&body).unwrap();
+/// assert!(paste.replaced_invalid_utf8);
+/// assert_eq!(paste.value.pasties[0].code, "\u{FFFD}NVALID");
+/// ```
+pub fn paste_from_bytes(body: &[u8]) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let text = String::from_utf8_lossy(body);
+    let mut paste = paste_from_str(&text)?;
+    paste.replaced_invalid_utf8 = matches!(text, Cow::Owned(_));
+    Ok(paste)
+}
+
+/// The same as `paste_from_str` except that it
+/// takes an already parsed json value.
+pub fn paste_from_value(value: Value) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let object = match value {
+        Value::Object(object) => object,
+        other => return Err(serde_json::Error::custom(format!("expected a paste object, got {}", other)).into()),
+    };
+    let mut coercer = Coercer { warnings: Vec::new() };
+    let value = coercer.paste(&object);
+    Ok(Lenient { value, warnings: coercer.warnings, replaced_invalid_utf8: false })
+}
+
+/// The fields pastemyst documents for a paste.
+const PASTE_FIELDS: [&str; 12] = [
+    "_id", "ownerId", "title", "createdAt", "expiresIn", "deletesAt",
+    "stars", "isPrivate", "isPublic", "tags", "pasties", "edits",
+];
+/// The fields pastemyst documents for a pasty.
+const PASTY_FIELDS: [&str; 4] = ["_id", "language", "title", "code"];
+/// The fields pastemyst documents for an edit.
+const EDIT_FIELDS: [&str; 6] = ["_id", "editId", "editType", "metadata", "edit", "editedAt"];
+/// The fields pastemyst documents for a user.
+const USER_FIELDS: [&str; 7] = [
+    "_id", "username", "avatarUrl", "defaultLang",
+    "publicProfile", "supporterLength", "contributor",
+];
+
+/// Parses a paste in strict mode, the opposite of
+/// `paste_from_str`. The regular parsing defaults the
+/// fields pastemyst leaves out and ignores the ones it
+/// adds so that the crate keeps working when the API
+/// changes a little, this fails with
+/// `PasteMystError::Json` instead. Useful in tests to
+/// notice such a change.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::lenient;
+/// use pastemyst::paste::PasteObject;
+///
+/// let body = r#"{
+///     "_id": "hipfqanx", "title": "Hello", "createdAt": 1612534264,
+///     "expiresIn": "never", "pasties": [], "edits": [], "views": 7
+/// }"#;
+/// let paste: PasteObject = serde_json::from_str(body).unwrap();
+/// assert_eq!(paste.ownerId, "");
+///
+/// let error = lenient::paste_from_str_strict(body).unwrap_err();
+/// assert!(error.to_string().contains("missing field `ownerId`"));
+/// ```
+pub fn paste_from_str_strict(body: &str) -> Result<PasteObject, PasteMystError> {
+    let value: Value = serde_json::from_str(body)?;
+    check_fields(&value, &PASTE_FIELDS, "")?;
+    for (key, fields) in [("pasties", &PASTY_FIELDS[..]), ("edits", &EDIT_FIELDS[..])] {
+        if let Some(Value::Array(values)) = value.get(key) {
+            for (index, value) in values.iter().enumerate() {
+                check_fields(value, fields, &format!("{}[{}].", key, index))?;
+            }
+        }
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Parses a user in strict mode, failing with
+/// `PasteMystError::Json` if a documented field is
+/// missing or one that isn't documented was sent,
+/// see `paste_from_str_strict`.
+pub fn user_from_str_strict(body: &str) -> Result<UserObject, PasteMystError> {
+    let value: Value = serde_json::from_str(body)?;
+    check_fields(&value, &USER_FIELDS, "")?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Fails if the object is missing one of the
+/// fields or has one that isn't among them.
+fn check_fields(value: &Value, fields: &[&str], path: &str) -> Result<(), serde_json::Error> {
+    let object = match value {
+        Value::Object(object) => object,
+        other => return Err(serde_json::Error::custom(format!("{}: expected an object, got {}", path, other))),
+    };
+    if let Some(field) = fields.iter().find(|field| !object.contains_key(**field)) {
+        return Err(serde_json::Error::custom(format!("missing field `{}{}`", path, field)));
+    }
+    if let Some(key) = object.keys().find(|key| !fields.contains(&key.as_str())) {
+        return Err(serde_json::Error::custom(format!("unknown field `{}{}`", path, key)));
+    }
+    Ok(())
+}
+
+/// Collects the warnings while
+/// the fields are coerced.
+struct Coercer {
+    warnings: Vec<String>,
+}
+
+impl Coercer {
+    fn paste(&mut self, object: &Map<String, Value>) -> PasteObject {
+        PasteObject {
+            _id: self.string(object, "", "_id"),
+            ownerId: self.string(object, "", "ownerId"),
+            title: self.string(object, "", "title"),
+            createdAt: self.unsigned(object, "", "createdAt"),
+            expiresIn: self.string(object, "", "expiresIn"),
+            deletesAt: self.unsigned(object, "", "deletesAt"),
+            stars: self.unsigned(object, "", "stars"),
+            isPrivate: self.boolean(object, "", "isPrivate"),
+            isPublic: self.boolean(object, "", "isPublic"),
+            tags: self.strings(object, "", "tags"),
+            pasties: self
+                .objects(object, "", "pasties")
+                .iter()
+                .enumerate()
+                .map(|(index, pasty)| self.pasty(pasty, &format!("pasties[{}].", index)))
+                .collect(),
+            edits: self
+                .objects(object, "", "edits")
+                .iter()
+                .enumerate()
+                .map(|(index, edit)| self.edit(edit, &format!("edits[{}].", index)))
+                .collect(),
+        }
+    }
+
+    fn pasty(&mut self, object: &Map<String, Value>, path: &str) -> PastyObject {
+        PastyObject {
+            _id: self.string(object, path, "_id"),
+            language: self.string(object, path, "language"),
+            title: self.string(object, path, "title"),
+            code: self.string(object, path, "code"),
+        }
+    }
+
+    fn edit(&mut self, object: &Map<String, Value>, path: &str) -> EditHistory {
+        EditHistory {
+            _id: self.string(object, path, "_id"),
+            editId: self.string(object, path, "editId"),
+            editType: EditType::from(self.signed(object, path, "editType")),
+            metadata: self.strings(object, path, "metadata"),
+            edit: self.string(object, path, "edit"),
+            editedAt: self.signed(object, path, "editedAt"),
+        }
+    }
+
+    fn warn(&mut self, path: &str, key: &str, expected: &str, value: Option<&Value>) {
+        let got = value.map_or_else(|| String::from("nothing"), |value| value.to_string());
+        self.warnings.push(format!("{}{}: expected {}, got {}", path, key, expected, got));
+    }
+
+    fn string(&mut self, object: &Map<String, Value>, path: &str, key: &str) -> String {
+        let value = object.get(key);
+        match value {
+            Some(Value::String(string)) => return string.clone(),
+            Some(Value::Number(number)) => { self.warn(path, key, "a string", value); return number.to_string(); }
+            Some(Value::Bool(boolean)) => { self.warn(path, key, "a string", value); return boolean.to_string(); }
+            _ => self.warn(path, key, "a string", value),
+        }
+        String::new()
+    }
+
+    fn unsigned(&mut self, object: &Map<String, Value>, path: &str, key: &str) -> u64 {
+        let value = object.get(key);
+        let coerced = match value {
+            Some(Value::Number(number)) => match number.as_u64() {
+                Some(number) => return number,
+                None => number.as_f64().filter(|number| *number >= 0.0).map(|number| number as u64),
+            },
+            Some(Value::String(string)) => string.trim().parse().ok(),
+            _ => None,
+        };
+        self.warn(path, key, "a number", value);
+        coerced.unwrap_or_default()
+    }
+
+    fn signed(&mut self, object: &Map<String, Value>, path: &str, key: &str) -> i32 {
+        let value = object.get(key);
+        let coerced = match value {
+            Some(Value::Number(number)) => match number.as_i64().and_then(|number| i32::try_from(number).ok()) {
+                Some(number) => return number,
+                None => None,
+            },
+            Some(Value::String(string)) => string.trim().parse().ok(),
+            _ => None,
+        };
+        self.warn(path, key, "a number", value);
+        coerced.unwrap_or_default()
+    }
+
+    fn boolean(&mut self, object: &Map<String, Value>, path: &str, key: &str) -> bool {
+        let value = object.get(key);
+        let coerced = match value {
+            Some(Value::Bool(boolean)) => return *boolean,
+            Some(Value::String(string)) => string.trim().parse().ok(),
+            Some(Value::Number(number)) => match number.as_u64() {
+                Some(0) => Some(false),
+                Some(1) => Some(true),
+                _ => None,
+            },
+            _ => None,
+        };
+        self.warn(path, key, "a boolean", value);
+        coerced.unwrap_or_default()
+    }
+
+    fn strings(&mut self, object: &Map<String, Value>, path: &str, key: &str) -> Vec<String> {
+        let value = object.get(key);
+        match value {
+            Some(Value::Array(values)) => {
+                let mut strings: Vec<String> = Vec::new();
+                for (index, value) in values.iter().enumerate() {
+                    match value {
+                        Value::String(string) => strings.push(string.clone()),
+                        Value::Number(_) | Value::Bool(_) => {
+                            self.warn(path, &format!("{}[{}]", key, index), "a string", Some(value));
+                            strings.push(value.to_string());
+                        }
+                        _ => self.warn(path, &format!("{}[{}]", key, index), "a string", Some(value)),
+                    }
+                }
+                return strings;
+            }
+            Some(Value::String(string)) => {
+                self.warn(path, key, "a list", value);
+                return string
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+            }
+            _ => self.warn(path, key, "a list", value),
+        }
+        Vec::new()
+    }
+
+    fn objects<'a>(&mut self, object: &'a Map<String, Value>, path: &str, key: &str) -> Vec<&'a Map<String, Value>> {
+        let value = object.get(key);
+        match value {
+            Some(Value::Array(values)) => {
+                let mut objects: Vec<&Map<String, Value>> = Vec::new();
+                for (index, value) in values.iter().enumerate() {
+                    match value {
+                        Value::Object(object) => objects.push(object),
+                        _ => self.warn(path, &format!("{}[{}]", key, index), "an object", Some(value)),
+                    }
+                }
+                objects
+            }
+            _ => {
+                self.warn(path, key, "a list", value);
+                Vec::new()
+            }
+        }
+    }
+}
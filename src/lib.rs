@@ -19,17 +19,51 @@ macro_rules! str {
     {$value:expr} => (String::from($value));
 }
 
-//#[cfg(feature = "time")]
 pub mod time;
 
-//#[cfg(feature = "data")]
 pub mod data;
 
-//#[cfg(feature = "user")]
 pub mod user;
 
-//#[cfg(feature = "paste")]
 pub mod paste;
 
+pub mod error;
+
+pub mod token;
+
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+pub mod client;
+
+pub mod search;
+
+pub mod templates;
+
+pub mod files;
+
+pub mod lenient;
+
+#[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+pub mod worker;
+
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+pub mod transport;
+
+#[cfg(feature = "network")]
+pub mod connection;
+
+pub mod checkpoint;
+
+pub mod request;
+
+pub mod stats;
+
+#[cfg(feature = "tower")]
+pub mod service;
+
+pub mod interop;
+
 #[cfg(feature = "discord")]
 pub mod discord;
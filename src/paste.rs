@@ -1,5 +1,17 @@
 use serde::Deserialize;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[cfg(all(feature = "paste", feature = "network"))]
+use crate::connection::{self, SendRequest};
+use crate::data::{Language, LanguageSpec};
+#[cfg(all(feature = "paste", feature = "network"))]
+use crate::error;
+use crate::error::PasteMystError;
+#[cfg(all(feature = "paste", feature = "network"))]
+use crate::lenient::{self, Lenient};
+use crate::time::ExpiresIn;
+use crate::token::ApiToken;
 
 /// The PasteResult type provided
 /// by this library for ease. It
@@ -15,15 +27,27 @@ use serde::Serialize;
 /// ```
 pub type PasteResult<T, E = Box<dyn std::error::Error>> = std::result::Result<T, E>;
 
-#[allow(dead_code)]
+/// The address of PasteMyst, pastes are
+/// found under it at `/<id>`.
+pub const BASE_URL: &str = "https://paste.myst.rs";
+
+#[cfg(all(feature = "paste", feature = "network"))]
 const ENDPOINT: &str = "https://paste.myst.rs/";
 #[allow(dead_code)]
+#[cfg(all(feature = "paste", feature = "network"))]
 const BASE_ENDPOINT: &str = "https://paste.myst.rs/api/v2/";
 /// This endpoint is temporarily here due to a bug in pastemyst
 /// which does not allow the paste to be end when the last
 /// slash is present.
-const SEND_ENDPOINT: &str = "https://paste.myst.rs/api/v2/paste";
+#[cfg(all(feature = "paste", feature = "network"))]
+pub(crate) const SEND_ENDPOINT: &str = "https://paste.myst.rs/api/v2/paste";
+#[cfg(all(feature = "paste", feature = "network"))]
 const PASTE_ENDPOINT: &str = "https://paste.myst.rs/api/v2/paste/";
+#[cfg(all(feature = "paste", feature = "network"))]
+const RAW_ENDPOINT: &str = "https://paste.myst.rs/raw/";
+/// How many pastes `delete_pastes_async` deletes at once.
+#[cfg(all(feature = "paste", feature = "async"))]
+const DELETE_CONCURRENCY: usize = 8;
 
 /// Gets a paste's data in json format
 /// from [pastemyst](https://paste.myst.rs)
@@ -32,20 +56,31 @@ const PASTE_ENDPOINT: &str = "https://paste.myst.rs/api/v2/paste/";
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::get_paste;
 /// use pastemyst::paste::PasteResult;
 ///
 /// fn main() -> PasteResult<()> {
-///     let foo = get_paste("hipfqanx");
+///     let foo = get_paste("hipfqanx")?;
 ///     println!("{:?}", foo.title);
 ///     Ok(())
 /// }
 /// ```
 ///
-pub fn get_paste(id: &str) -> Result<PasteObject, reqwest::Error> {
-    let info: PasteObject = reqwest::blocking::get(&parse_url(id))?.json()?;
-    Ok(info)
+/// Ids are validated before any request is sent,
+/// a url is not an id:
+///
+/// ```rust
+/// use pastemyst::error::PasteMystError;
+/// use pastemyst::paste::get_paste;
+///
+/// let result = get_paste("https://paste.myst.rs/hipfqanx");
+/// assert!(matches!(result, Err(PasteMystError::InvalidId(_))));
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn get_paste(id: &str) -> Result<PasteObject, PasteMystError> {
+    let result = connection::blocking_http()?.get(parse_url(id)?).send_request()?;
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// Gets a paste's data in json format
@@ -55,7 +90,7 @@ pub fn get_paste(id: &str) -> Result<PasteObject, reqwest::Error> {
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::get_paste_async;
 /// use pastemyst::paste::PasteResult;
 ///
@@ -66,9 +101,114 @@ pub fn get_paste(id: &str) -> Result<PasteObject, reqwest::Error> {
 ///     Ok(())
 /// }
 /// ```
-pub async fn get_paste_async(id: &str) -> Result<PasteObject, reqwest::Error> {
-    let info: PasteObject = reqwest::get(&parse_url(id)).await?.json().await?;
-    Ok(info)
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn get_paste_async(id: &str) -> Result<PasteObject, PasteMystError> {
+    let result = connection::http()?.get(parse_url(id)?).send_request().await?;
+    Ok(error::check_status_async(result).await?.json().await?)
+}
+
+/// Checks whether a paste exists without decoding
+/// it, so a missing paste is `false` instead of an
+/// error. This is a synchronous method.
+///
+/// Private pastes are reported as missing, as
+/// PasteMyst doesn't show them without a token.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     if paste_exists("hipfqanx")? {
+///         println!("The paste is still there");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn paste_exists(id: &str) -> Result<bool, PasteMystError> {
+    let result = connection::blocking_http()?.get(parse_url(id)?).send_request()?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(false); }
+    error::check_status(result)?;
+    Ok(true)
+}
+
+/// Checks whether a paste exists without decoding
+/// it, so a missing paste is `false` instead of an
+/// error. This is an asynchronous method.
+///
+/// Private pastes are reported as missing, as
+/// PasteMyst doesn't show them without a token.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     if paste_exists_async("hipfqanx").await? {
+///         println!("The paste is still there");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn paste_exists_async(id: &str) -> Result<bool, PasteMystError> {
+    let result = connection::http()?.get(parse_url(id)?).send_request().await?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(false); }
+    error::check_status_async(result).await?;
+    Ok(true)
+}
+
+/// Gets the contents of a single pasty as plain
+/// text, without fetching and parsing the whole
+/// paste. This is a synchronous method.
+///
+/// The raw endpoint is the one of the website, it
+/// only serves pastes that aren't private.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let code = get_pasty_raw("hipfqanx", "cys6ypb4")?;
+///     println!("{}", code);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn get_pasty_raw(paste_id: &str, pasty_id: &str) -> Result<String, PasteMystError> {
+    let result = connection::blocking_http()?.get(parse_raw_url(paste_id, pasty_id)?).send_request()?;
+    Ok(error::check_status(result)?.text()?)
+}
+
+/// Gets the contents of a single pasty as plain
+/// text, without fetching and parsing the whole
+/// paste. This is an asynchronous method.
+///
+/// The raw endpoint is the one of the website, it
+/// only serves pastes that aren't private.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let code = get_pasty_raw_async("hipfqanx", "cys6ypb4").await?;
+///     println!("{}", code);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn get_pasty_raw_async(paste_id: &str, pasty_id: &str) -> Result<String, PasteMystError> {
+    let result = connection::http()?.get(parse_raw_url(paste_id, pasty_id)?).send_request().await?;
+    Ok(error::check_status_async(result).await?.text().await?)
 }
 
 /// Gets a private paste's data in json format
@@ -76,26 +216,29 @@ pub async fn get_paste_async(id: &str) -> Result<PasteObject, reqwest::Error> {
 /// synchronously. It returns a `Result`
 /// with a `PasteObject` and error.
 ///
+/// If you don't know whether a paste is private,
+/// `PasteMystClient::get_paste` fetches either.
+///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::get_private_paste;
 /// use pastemyst::paste::PasteResult;
 ///
 /// fn main() -> PasteResult<()> {
-///     let foo = get_private_paste("pasteID", "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+///     let foo = get_private_paste("pasteID", "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings")?;
 ///     println!("{:?}", foo._id);
 ///     Ok(())
 /// }
 /// ```
-pub fn get_private_paste(id: &str, auth_token: &str) -> Result<PasteObject, reqwest::Error> {
-    let info: PasteObject = reqwest::blocking::Client::builder()
-        .build()?
-        .get(&parse_url(id))
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn get_private_paste(id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::blocking_http()?
+        .get(parse_url(id)?)
         .header("Authorization", auth_token)
-        .send()?
-        .json()?;
-    Ok(info)
+        .send_request()?;
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// Gets a private paste's data in json format
@@ -105,63 +248,165 @@ pub fn get_private_paste(id: &str, auth_token: &str) -> Result<PasteObject, reqw
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::get_private_paste_async;
 /// use pastemyst::paste::PasteResult;
 ///
 /// #[tokio::main]
-/// async fn main() -> Result<()> {
+/// async fn main() -> PasteResult<()> {
 ///     let foo = get_private_paste_async("pasteID", "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings").await?;
-///     println!("{}", paste.isPrivate);
+///     println!("{}", foo.isPrivate);
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "paste", feature = "async"))]
 pub async fn get_private_paste_async(
     id: &str,
-    auth_token: &str,
-) -> Result<PasteObject, reqwest::Error> {
-    let info: PasteObject = reqwest::Client::builder()
-        .build()?
-        .get(&parse_url(&id))
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::http()?
+        .get(parse_url(id)?)
+        .header("Authorization", auth_token)
+        .send_request()
+        .await?;
+    Ok(error::check_status_async(result).await?.json().await?)
+}
+
+/// Gets a paste from pastemyst the same way `get_paste`
+/// does, except that fields with an unexpected type
+/// are coerced instead of failing the whole call.
+/// Every coerced field is listed in the warnings of
+/// the returned value, see `lenient::paste_from_str`.
+/// Invalid UTF-8 in the content of a pasty is replaced
+/// as well, see `lenient::paste_from_bytes`.
+/// This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = get_paste_lenient("hipfqanx")?;
+///     for warning in &paste.warnings { println!("{}", warning); }
+///     println!("{}", paste.value.title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn get_paste_lenient(id: &str) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let result = connection::blocking_http()?.get(parse_url(id)?).send_request()?;
+    lenient::paste_from_bytes(&error::check_status(result)?.bytes()?)
+}
+
+/// Gets a paste from pastemyst the same way
+/// `get_paste_async` does, except that fields
+/// with an unexpected type are coerced instead of
+/// failing the whole call. This is an asynchronous
+/// method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = get_paste_lenient_async("hipfqanx").await?;
+///     println!("{} ({} warnings)", paste.value.title, paste.warnings.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn get_paste_lenient_async(id: &str) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let result = connection::http()?.get(parse_url(id)?).send_request().await?;
+    lenient::paste_from_bytes(&error::check_status_async(result).await?.bytes().await?)
+}
+
+/// Gets a private paste from pastemyst the same way
+/// `get_private_paste` does, except that fields with
+/// an unexpected type are coerced instead of failing
+/// the whole call. This is a synchronous method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = get_private_paste_lenient("pasteID", "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings")?;
+///     println!("{}", paste.is_exact());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn get_private_paste_lenient(id: &str, auth_token: impl AsRef<str>) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::blocking_http()?
+        .get(parse_url(id)?)
+        .header("Authorization", auth_token)
+        .send_request()?;
+    lenient::paste_from_bytes(&error::check_status(result)?.bytes()?)
+}
+
+/// Gets a private paste from pastemyst the same way
+/// `get_private_paste_async` does, except that fields
+/// with an unexpected type are coerced instead of
+/// failing the whole call. This is an asynchronous
+/// method.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = get_private_paste_lenient_async("pasteID", "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings").await?;
+///     println!("{}", paste.is_exact());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn get_private_paste_lenient_async(
+    id: &str,
+    auth_token: impl AsRef<str>,
+) -> Result<Lenient<PasteObject>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::http()?
+        .get(parse_url(id)?)
         .header("Authorization", auth_token)
-        .send()
-        .await?
-        .json()
+        .send_request()
         .await?;
-    Ok(info)
+    lenient::paste_from_bytes(&error::check_status_async(result).await?.bytes().await?)
 }
 
 /// Uses the `CreateObject` struct as a parameter for paste
 /// data to be constructed into json format and sent to
 /// [pastemyst](https://paste.myst.rs) in a synchronous manner.
 ///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
+///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::PastyObject;
 /// use pastemyst::paste::*;
 ///
 /// fn main() -> PasteResult<()> {
 ///     let pasties: Vec<PastyObject> = vec![
-///             PastyObject {
-///             _id: None,
-///             language: Some(String::from("autodetect")),
-///             title: Some(String::from("Pasty1")),
-///             code: Some(String::from("Code")),
-///         },
-///         PastyObject {
-///             _id: None,
-///             language: Some(String::from("autodetect")),
-///             title: Some(String::from("Pasty2")),
-///             code: Some(String::from("Code")),
-///         },
+///         PastyBuilder::new().title("Pasty1").code("Code").build(),
+///         PastyBuilder::new().title("Pasty2").code("Code").build(),
 ///     ];
 ///     let data: CreateObject = CreateObject {
 ///         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-///         expiresIn: String::from("1d"),
+///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
 ///         isPrivate: false,
 ///         isPublic: false,
-///         tags: String::from(""),
+///         tags: Vec::new(),
 ///         pasties: pasties,
 ///     };
 ///     let paste = create_paste(data)?;
@@ -169,67 +414,49 @@ pub async fn get_private_paste_async(
 ///     Ok(())
 /// }
 /// ```
-pub fn create_paste(contents: CreateObject) -> Result<PasteObject, reqwest::Error> {
-    let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::blocking::Client::builder()
-        .build()?
-        .post(SEND_ENDPOINT)
-        .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&contents).unwrap())
-        .send()
-        .unwrap();
-    Ok(result.json()?)
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn create_paste(contents: CreateObject) -> Result<PasteObject, PasteMystError> {
+    contents.validate()?;
+    send_create(&contents, None)
 }
 
 /// Uses the `CreateObject` struct as a parameter for paste
 /// data to be constructed into json format and sent to
 /// [pastemyst](https://paste.myst.rs) in an asynchronous manner.
 ///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
+///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::PastyObject;
 /// use pastemyst::paste::*;
 ///
 /// #[tokio::main]
 /// async fn main() -> PasteResult<()> {
 ///     let pasties: Vec<PastyObject> = vec![
-///             PastyObject {
-///             _id: None,
-///             language: Some(String::from("autodetect")),
-///             title: Some(String::from("Pasty1")),
-///             code: Some(String::from("Code")),
-///         },
-///         PastyObject {
-///             _id: None,
-///             language: Some(String::from("autodetect")),
-///             title: Some(String::from("Pasty2")),
-///             code: Some(String::from("Code")),
-///         },
+///         PastyBuilder::new().title("Pasty1").code("Code").build(),
+///         PastyBuilder::new().title("Pasty2").code("Code").build(),
 ///     ];
 ///     let data: CreateObject = CreateObject {
 ///         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-///         expiresIn: String::from("1d"),
+///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
 ///         isPrivate: false,
 ///         isPublic: false,
-///         tags: String::from(""),
+///         tags: Vec::new(),
 ///         pasties: pasties,
 ///     };
-///     let paste = paste::create_paste_async(data).await?;
+///     let paste = create_paste_async(data).await?;
 ///     println!("{:?}", paste._id);
 ///     Ok(())
 /// }
 /// ```
-pub async fn create_paste_async(contents: CreateObject) -> Result<PasteObject, reqwest::Error> {
-    let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::Client::builder()
-        .build()?
-        .post(SEND_ENDPOINT)
-        .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&contents).unwrap())
-        .send()
-        .await?;
-    Ok(result.json().await?)
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn create_paste_async(contents: CreateObject) -> Result<PasteObject, PasteMystError> {
+    contents.validate()?;
+    send_create_async(&contents, None).await
 }
 
 /// Uses the `CreateObject` and `&str` (`auth_token`) to
@@ -239,34 +466,36 @@ pub async fn create_paste_async(contents: CreateObject) -> Result<PasteObject, r
 /// authority to delete that paste. This is a 
 /// synchronous method.
 ///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
+///
 /// ## Examples
 ///
-/// ```rust
-/// use pastemyst::paste::create_private_paste;
-/// use pastemyst::paste::get_paste;
-/// use pastemyst::paste::PasteResult;
+/// ```rust,no_run
+/// use pastemyst::paste::*;
 ///
-/// #[tokio::main]
-/// async fn main() -> Result<()> {
-///     let contents = get_paste("hipfqanx");
-///     let paste = create_private_paste(contents, "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings").await?;
+/// fn main() -> PasteResult<()> {
+///     let contents = CreateObject {
+///         title: String::from("[crates.io/crates/pastemyst] This is a title"),
+///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
+///         isPrivate: true,
+///         isPublic: false,
+///         tags: Vec::new(),
+///         pasties: vec![PastyBuilder::new().title("Pasty1").code("Code").build()],
+///     };
+///     let paste = create_private_paste(contents, "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings")?;
 ///     println!("{}", paste.isPrivate);
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
 pub fn create_private_paste(
     contents: CreateObject,
-    auth_token: &str,
-) -> Result<PasteObject, reqwest::Error> {
-    let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::blocking::Client::builder()
-        .build()?
-        .post(SEND_ENDPOINT)
-        .header("Authorization", auth_token)
-        .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&contents).unwrap())
-        .send()?;
-    Ok(result.json()?)
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    contents.validate()?;
+    send_create(&contents, Some(auth_token.as_ref()))
 }
 
 /// Uses the `CreateObject` struct and a `&str` authorization
@@ -277,58 +506,82 @@ pub fn create_private_paste(
 /// in an asynchronous manner. The paste is send under
 /// the ownership of the account the auth key belongs to.
 ///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
+///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::*;
 /// 
-/// fn main() -> Result<(), reqwest::Error> /*PasteResult<()>*/ {
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
 ///     let pasties: Vec<PastyObject> = vec![
 ///         PastyObject {
-///             _id: None,
-///             language: Some(String::from("autodetect")),
-///             title: Some(String::from("A pasty title")),
-///             code: Some(String::from("fn main() { println!(\"Hello World!\"); }")),
+///             _id: String::from(""),
+///             language: String::from(pastemyst::data::language::RUST),
+///             title: String::from("A pasty title"),
+///             code: String::from("fn main() { println!(\"Hello World!\"); }"),
 ///         },
 ///         PastyObject {
-///             _id: None,
-///             title: Some(String::from("Another pasty title")),
-///             language: Some(String::from("autodetect")),
-///             code: Some(String::from(
+///             _id: String::from(""),
+///             title: String::from("Another pasty title"),
+///             language: String::from(pastemyst::data::language::CLANG),
+///             code: String::from(
 ///                 "#include \"stdio.h\"\n\nint main() {\n\tprintf(\"Hello World!\");\n}",
-///             )),
+///             ),
 ///         },
 ///     ];
 ///     let data: CreateObject = CreateObject {
 ///         title: String::from("[crates.io/crates/pastemyst] This is a title"),
-///         expiresIn: String::from("1d"),
+///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
 ///         isPrivate: false,
 ///         isPublic: false,
-///         tags: String::from(""),
+///         tags: Vec::new(),
 ///         pasties,
 ///     };
-///     let paste = create_private_paste(
+///     let paste = create_private_paste_async(
 ///         data,
 ///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
-///     )?;
+///     ).await?;
 ///     println!("{:#?}", paste.ownerId);
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "paste", feature = "async"))]
 pub async fn create_private_paste_async(
     contents: CreateObject,
-    auth_token: &str,
-) -> Result<PasteObject, reqwest::Error> {
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    contents.validate()?;
+    send_create_async(&contents, Some(auth_token.as_ref())).await
+}
+
+/// Creates a paste, owned by the account of
+/// the token or by no one if there is none.
+#[cfg(all(feature = "paste", feature = "blocking"))]
+fn send_create(contents: &CreateObject, auth_token: Option<&str>) -> Result<PasteObject, PasteMystError> {
     let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::Client::builder()
-        .build()?
+    let mut request = connection::blocking_http()?
         .post(SEND_ENDPOINT)
-        .header("Authorization", auth_token)
         .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&contents).unwrap())
-        .send()
-        .await?;
-    Ok(result.json().await?)
+        .body(serde_json::to_string(contents)?);
+    if let Some(auth_token) = auth_token { request = request.header("Authorization", auth_token); }
+    Ok(error::check_status(request.send_request()?)?.json()?)
+}
+
+/// Creates a paste, owned by the account of
+/// the token or by no one if there is none.
+#[cfg(all(feature = "paste", feature = "async"))]
+async fn send_create_async(contents: &CreateObject, auth_token: Option<&str>) -> Result<PasteObject, PasteMystError> {
+    let content_type = reqwest::header::HeaderValue::from_static("application/json");
+    let mut request = connection::http()?
+        .post(SEND_ENDPOINT)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(serde_json::to_string(contents)?);
+    if let Some(auth_token) = auth_token { request = request.header("Authorization", auth_token); }
+    Ok(error::check_status_async(request.send_request().await?).await?.json().await?)
 }
 
 /// Sends a request to pastemyst to edit a
@@ -340,48 +593,104 @@ pub async fn create_private_paste_async(
 /// be edited if you do not supply the id
 /// (or the correct id) of the pasty. PasteMyst
 /// needs to know which pasty to edit exactly.
+/// Because of this, the paste is fetched first
+/// and `PasteMystError::UnknownPasty` is returned
+/// if a pasty id does not belong to the paste.
+/// Use `edit_paste_with_snapshot` if you have
+/// fetched the paste already.
+///
+/// Pasties without an id are added to the
+/// paste, `append_pasty` does this for you.
+///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
 ///
-/// The API does not allow you to append more
-/// pastes as of this version writing this,
-/// you can only append pastes when editing
-/// within the site itself as the user.
-/// 
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::str;
-/// use pastemyst::paste;
+/// use pastemyst::paste::*;
 ///
-/// fn main() {
-///     let pasties = vec![pastemyst::paste::PastyObject {
+/// fn main() -> PasteResult<()> {
+///     let pasties = vec![PastyObject {
 ///         _id: str!("PastyID"),
 ///         code: String::from("print('Hello World!')"),
 ///         language: str!(pastemyst::data::language::PYTHON),
 ///         title: "Pasty Title".to_string(),
 ///     }];
-///     let edit_object = pastemyst::paste::EditObject {
+///     let edit_object = EditObject {
 ///         isPrivate: false,
 ///         isPublic: false,
-///         pasties: pasties,
-///         tags: str!("Hello, World"),
+///         pasties,
+///         tags: vec![str!("Hello"), str!("World")],
 ///         title: str!("My title")
 ///     };
-///     let paste_result: PasteObject = paste::edit_paste(edit_object,
+///     let paste_result: PasteObject = edit_paste(edit_object,
 ///         "PasteID",
 ///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
 ///     )?;
+///     println!("{}", paste_result.title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn edit_paste(edit_info: EditObject, id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    edit_info.validate_against(&get_private_paste(id, auth_token)?)?;
+    send_edit(edit_info, id, auth_token)
+}
+
+/// The same as `edit_paste` except that the pasty
+/// ids are checked against an already fetched paste,
+/// which saves a request. The id of the snapshot is
+/// the id of the paste being edited. This method is
+/// synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     const TOKEN: &str = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let paste = get_private_paste("PasteID", TOKEN)?;
+///     let mut pasties = paste.pasties.clone();
+///     pasties[0].code = String::from("print('Hello World!')");
+///     let edit_object = EditObject {
+///         isPrivate: paste.isPrivate,
+///         isPublic: paste.isPublic,
+///         pasties,
+///         tags: paste.tags.clone(),
+///         title: paste.title.clone(),
+///     };
+///     let paste_result = edit_paste_with_snapshot(edit_object, &paste, TOKEN)?;
+///     println!("{}", paste_result.title);
+///     Ok(())
 /// }
 /// ```
-pub fn edit_paste(edit_info: EditObject, id: &str, auth_token: &str) -> Result<PasteObject, reqwest::Error> {
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn edit_paste_with_snapshot(
+    edit_info: EditObject,
+    snapshot: &PasteObject,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    edit_info.validate_against(snapshot)?;
+    send_edit(edit_info, &snapshot._id, auth_token)
+}
+
+/// Sends an edit without checking it first.
+#[cfg(all(feature = "paste", feature = "blocking"))]
+fn send_edit(edit_info: EditObject, id: &str, auth_token: &str) -> Result<PasteObject, PasteMystError> {
     let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::blocking::Client::builder()
-        .build()?
-        .patch(&parse_url(&id))
+    let result = connection::blocking_http()?
+        .patch(parse_url(id)?)
         .header("Authorization", auth_token)
         .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&edit_info).unwrap())
-        .send()?;
-    Ok(result.json()?)
+        .body(serde_json::to_string(&edit_info)?)
+        .send_request()?;
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// Sends a request to pastemyst to edit a
@@ -393,187 +702,2164 @@ pub fn edit_paste(edit_info: EditObject, id: &str, auth_token: &str) -> Result<P
 /// be edited if you do not supply the id
 /// (or the correct id) of the pasty. PasteMyst
 /// needs to know which pasty to edit exactly.
+/// Because of this, the paste is fetched first
+/// and `PasteMystError::UnknownPasty` is returned
+/// if a pasty id does not belong to the paste.
+/// Use `edit_paste_with_snapshot` if you have
+/// fetched the paste already.
+///
+/// Pasties without an id are added to the
+/// paste, `append_pasty` does this for you.
+///
+/// If PasteMyst refuses the paste, e.g. because it's
+/// too large or the token is wrong, the status and the
+/// message it sent are returned as `PasteMystError::Api`.
 ///
-/// The API does not allow you to append more
-/// pastes as of this version writing this,
-/// you can only append pastes when editing
-/// within the site itself as the user.
-/// 
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::str;
-/// use pastemyst::paste;
+/// use pastemyst::paste::*;
 ///
 /// #[tokio::main]
-/// async fn main() {
-///     let pasties = vec![pastemyst::paste::PastyObject {
+/// async fn main() -> PasteResult<()> {
+///     let pasties = vec![PastyObject {
 ///         _id: str!("PastyID"),
 ///         code: String::from("print('Hello World!')"),
 ///         language: str!(pastemyst::data::language::PYTHON),
 ///         title: "Pasty Title".to_string(),
 ///     }];
-///     let edit_object = pastemyst::paste::EditObject {
+///     let edit_object = EditObject {
 ///         isPrivate: false,
 ///         isPublic: false,
-///         pasties: pasties,
-///         tags: str!("Hello, World"),
+///         pasties,
+///         tags: vec![str!("Hello"), str!("World")],
 ///         title: str!("My title")
 ///     };
-///     let paste_result: PasteObject = paste::edit_paste(edit_object,
+///     let paste_result: PasteObject = edit_paste_async(edit_object,
 ///         "PasteID",
 ///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
 ///     ).await?;
+///     println!("{}", paste_result.title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn edit_paste_async(edit_info: EditObject, id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    edit_info.validate_against(&get_private_paste_async(id, auth_token).await?)?;
+    send_edit_async(edit_info, id, auth_token).await
+}
+
+/// The same as `edit_paste_async` except that the
+/// pasty ids are checked against an already fetched
+/// paste, which saves a request. The id of the snapshot
+/// is the id of the paste being edited. This method is
+/// asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     const TOKEN: &str = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let paste = get_private_paste_async("PasteID", TOKEN).await?;
+///     let edit_object = EditObject {
+///         isPrivate: paste.isPrivate,
+///         isPublic: paste.isPublic,
+///         pasties: paste.pasties.clone(),
+///         tags: paste.tags.clone(),
+///         title: String::from("A better title"),
+///     };
+///     let paste_result = edit_paste_with_snapshot_async(edit_object, &paste, TOKEN).await?;
+///     println!("{}", paste_result.title);
+///     Ok(())
 /// }
 /// ```
-pub async fn edit_paste_async(edit_info: EditObject, id: &str, auth_token: &str) -> Result<PasteObject, reqwest::Error> {
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn edit_paste_with_snapshot_async(
+    edit_info: EditObject,
+    snapshot: &PasteObject,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    edit_info.validate_against(snapshot)?;
+    send_edit_async(edit_info, &snapshot._id, auth_token).await
+}
+
+/// Sends an edit without checking it first.
+#[cfg(all(feature = "paste", feature = "async"))]
+async fn send_edit_async(edit_info: EditObject, id: &str, auth_token: &str) -> Result<PasteObject, PasteMystError> {
     let content_type = reqwest::header::HeaderValue::from_static("application/json");
-    let result = reqwest::Client::builder()
-        .build()?
-        .patch(&parse_url(&id))
+    let result = connection::http()?
+        .patch(parse_url(id)?)
         .header("Authorization", auth_token)
         .header(reqwest::header::CONTENT_TYPE, content_type)
-        .body(serde_json::to_string(&edit_info).unwrap())
-        .send().await?;
-    Ok(result.json().await?)
+        .body(serde_json::to_string(&edit_info)?)
+        .send_request().await?;
+    Ok(error::check_status_async(result).await?.json().await?)
 }
 
-/// You can only delete pastes on your account, which
-/// means you must also provide the authorization key.
-/// This action is irreversible can the paste cannot
-/// be restored in any way. This methods sends the
-/// request synchronously.
+/// Edits a single pasty of a paste on your account.
+/// PasteMyst only takes edits of the whole paste, so
+/// the paste is fetched first, `edit` is called with
+/// the pasty whose id is `pasty_id` and the paste is
+/// sent back with every pasty. This method is
+/// synchronous.
 ///
-/// This method returns an unsigned 16 bit integer
-/// which is a status code recieved by the PasteMyst
-/// server. If a paste deletes successfully, you
-/// should recieve a status code of `200`. For
-/// a list of all the web status codes, refer to:
-/// https://en.wikipedia.org/wiki/List_of_HTTP_status_codes
+/// `PasteMystError::UnknownPasty` is returned if the
+/// paste has no such pasty. The id of the pasty is
+/// kept even if `edit` changes it.
 ///
-/// ### API Docs
-/// The relevent link to the API Documentation
-/// is: https://paste.myst.rs/api-docs/paste
+/// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::*;
 ///
 /// fn main() -> PasteResult<()> {
-///     let paste_del_result = delete_paste(
+///     let paste = edit_pasty(
 ///         "PasteID",
+///         "PastyID",
+///         |pasty| pasty.code.push_str("\n// Reviewed"),
 ///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
 ///     )?;
-///     if (paste_del_result == 200) { println!("Paste has been deleted successfully."); }
-///     else { println!("Something went wrong and we recieved a status code of {}", paste_del_result); }
+///     println!("{}", paste.pasties[0].code);
 ///     Ok(())
 /// }
 /// ```
-pub fn delete_paste(id: &str, auth_token: &str) -> Result<u16, reqwest::Error> {
-    let result = reqwest::blocking::Client::builder()
-        .build()?
-        .delete(&parse_url(&id))
-        .header("Authorization", auth_token)
-        .send()?;
-    Ok(result.status().as_u16())
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn edit_pasty(
+    paste_id: &str,
+    pasty_id: &str,
+    edit: impl FnOnce(&mut PastyObject),
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(paste_id, auth_token)?;
+    send_edit(edit_with_pasty(&paste, pasty_id, edit)?, paste_id, auth_token)
 }
 
-/// You can only delete pastes on your account, which
-/// means you must also provide the authorization key.
-/// This action is irreversible can the paste cannot
-/// be restored in any way. This methods sends the
-/// request asynchronously.
+/// Edits a single pasty of a paste on your account.
+/// PasteMyst only takes edits of the whole paste, so
+/// the paste is fetched first, `edit` is called with
+/// the pasty whose id is `pasty_id` and the paste is
+/// sent back with every pasty. This method is
+/// asynchronous.
 ///
-/// This method returns an unsigned 16 bit integer
-/// which is a status code recieved by the PasteMyst
-/// server. If a paste deletes successfully, you
-/// should recieve a status code of `200`. For
-/// a list of all the web status codes, refer to:
-/// https://en.wikipedia.org/wiki/List_of_HTTP_status_codes
+/// `PasteMystError::UnknownPasty` is returned if the
+/// paste has no such pasty. The id of the pasty is
+/// kept even if `edit` changes it.
 ///
-/// ### API Docs
-/// The relevent link to the API Documentation
-/// is: https://paste.myst.rs/api-docs/paste
+/// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::paste::*;
 ///
 /// #[tokio::main]
 /// async fn main() -> PasteResult<()> {
-///     let paste_del_result = delete_paste(
+///     let paste = edit_pasty_async(
 ///         "PasteID",
+///         "PastyID",
+///         |pasty| pasty.language = String::from(pastemyst::data::language::RUST),
 ///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
 ///     ).await?;
-///     if (paste_del_result == 200) { println!("Paste has been deleted successfully."); }
-///     else { println!("Something went wrong and we recieved a status code of {}", paste_del_result); }
+///     println!("{}", paste.pasties[0].language);
 ///     Ok(())
 /// }
 /// ```
-pub async fn delete_paste_async(id: &str, auth_token: &str) -> Result<u16, reqwest::Error> {
-    let result = reqwest::Client::builder()
-        .build()?
-        .delete(&parse_url(&id))
-        .header("Authorization", auth_token)
-        .send().await?;
-    Ok(result.status().as_u16())
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn edit_pasty_async(
+    paste_id: &str,
+    pasty_id: &str,
+    edit: impl FnOnce(&mut PastyObject),
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(paste_id, auth_token).await?;
+    send_edit_async(edit_with_pasty(&paste, pasty_id, edit)?, paste_id, auth_token).await
 }
 
-/// Parses the url by combining
-/// the `PASTE_ENDPOINT` with a
-/// provided id.
-fn parse_url(id: &str) -> String { return PASTE_ENDPOINT.to_owned() + &id }
+/// Creates an `EditObject` that keeps everything of
+/// a fetched paste except for the pasty `edit` is
+/// called with.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn edit_with_pasty(
+    paste: &PasteObject,
+    pasty_id: &str,
+    edit: impl FnOnce(&mut PastyObject),
+) -> Result<EditObject, PasteMystError> {
+    let mut edit_info = EditObject::from_paste(paste);
+    let pasty = edit_info
+        .pasties
+        .iter_mut()
+        .find(|pasty| pasty._id == pasty_id)
+        .ok_or_else(|| PasteMystError::UnknownPasty(pasty_id.to_string()))?;
+    edit(pasty);
+    pasty._id = pasty_id.to_string();
+    Ok(edit_info)
+}
 
-/// The paste object recieved when
-/// getting a paste. It contains
-/// both the `PastyObject` and
-/// `EditHistory` in an array.
+/// Adds a pasty to a paste on your account. PasteMyst
+/// only takes edits of the whole paste, so the paste
+/// is fetched first and sent back with its pasties
+/// followed by the new one. This method is synchronous.
 ///
-/// ### API Docs
-/// The relevent link to the API documentation
-/// is: https://paste.myst.rs/api-docs/objects
+/// The id of the new pasty is cleared, PasteMyst
+/// gives it one.
 ///
 /// ## Examples
 ///
-/// ```rust
-/// let _foo: PasteObject = get_paste("hipfqanx");
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = append_pasty(
+///         "PasteID",
+///         PastyBuilder::from_filename("notes.md").code("# Notes"),
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("The paste has {} pasties", paste.pasties.len());
+///     Ok(())
+/// }
 /// ```
-#[derive(Deserialize)]
-#[allow(non_snake_case, dead_code)]
-pub struct PasteObject {
-    /// Id of the paste.
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn append_pasty(
+    paste_id: &str,
+    new_pasty: impl Into<PastyObject>,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(paste_id, auth_token)?;
+    send_edit(edit_with_new_pasty(&paste, new_pasty.into()), paste_id, auth_token)
+}
+
+/// Adds a pasty to a paste on your account. PasteMyst
+/// only takes edits of the whole paste, so the paste
+/// is fetched first and sent back with its pasties
+/// followed by the new one. This method is asynchronous.
+///
+/// The id of the new pasty is cleared, PasteMyst
+/// gives it one.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = append_pasty_async(
+///         "PasteID",
+///         PastyBuilder::new().title("Output").code("Hello, world!"),
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("The paste has {} pasties", paste.pasties.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn append_pasty_async(
+    paste_id: &str,
+    new_pasty: impl Into<PastyObject>,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(paste_id, auth_token).await?;
+    send_edit_async(edit_with_new_pasty(&paste, new_pasty.into()), paste_id, auth_token).await
+}
+
+/// Creates an `EditObject` that keeps everything of a
+/// fetched paste and adds a pasty after its own ones.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn edit_with_new_pasty(paste: &PasteObject, new_pasty: PastyObject) -> EditObject {
+    let mut edit_info = EditObject::from_paste(paste);
+    edit_info.pasties.push(PastyObject { _id: String::new(), ..new_pasty });
+    edit_info
+}
+
+/// Removes a pasty from a paste on your account.
+/// PasteMyst only takes edits of the whole paste, so
+/// the paste is fetched first and sent back without
+/// the pasty. This method is synchronous.
+///
+/// `PasteMystError::UnknownPasty` is returned if the
+/// paste has no such pasty, and
+/// `PasteMystError::LastPasty` if it's the only one
+/// left, as a paste can't be empty. Delete the paste
+/// instead in that case.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::error::PasteMystError;
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     const TOKEN: &str = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     match remove_pasty("PasteID", "PastyID", TOKEN) {
+///         Ok(paste) => println!("{} pasties left", paste.pasties.len()),
+///         Err(PasteMystError::LastPasty(_)) => { delete_paste("PasteID", TOKEN)?.into_result()?; }
+///         Err(error) => return Err(error.into()),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn remove_pasty(paste_id: &str, pasty_id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(paste_id, auth_token)?;
+    send_edit(edit_without_pasty(&paste, pasty_id)?, paste_id, auth_token)
+}
+
+/// Removes a pasty from a paste on your account.
+/// PasteMyst only takes edits of the whole paste, so
+/// the paste is fetched first and sent back without
+/// the pasty. This method is asynchronous.
+///
+/// `PasteMystError::UnknownPasty` is returned if the
+/// paste has no such pasty, and
+/// `PasteMystError::LastPasty` if it's the only one
+/// left, as a paste can't be empty. Delete the paste
+/// instead in that case.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = remove_pasty_async(
+///         "PasteID",
+///         "PastyID",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{} pasties left", paste.pasties.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn remove_pasty_async(
+    paste_id: &str,
+    pasty_id: &str,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(paste_id, auth_token).await?;
+    send_edit_async(edit_without_pasty(&paste, pasty_id)?, paste_id, auth_token).await
+}
+
+/// Creates an `EditObject` that keeps everything
+/// of a fetched paste except for one pasty.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn edit_without_pasty(paste: &PasteObject, pasty_id: &str) -> Result<EditObject, PasteMystError> {
+    if !paste.pasties.iter().any(|pasty| pasty._id == pasty_id) {
+        return Err(PasteMystError::UnknownPasty(pasty_id.to_string()));
+    }
+    if paste.pasties.len() == 1 { return Err(PasteMystError::LastPasty(pasty_id.to_string())); }
+    let mut edit_info = EditObject::from_paste(paste);
+    edit_info.pasties.retain(|pasty| pasty._id != pasty_id);
+    Ok(edit_info)
+}
+
+/// Changes the title of a paste on your account,
+/// everything else is kept as it is. This method
+/// is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = set_paste_title(
+///         "PasteID",
+///         "A better title",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{}", paste.title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn set_paste_title(id: &str, title: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(id, auth_token)?;
+    send_edit(EditObject { title: title.to_string(), ..EditObject::from_paste(&paste) }, id, auth_token)
+}
+
+/// Changes the title of a paste on your account,
+/// everything else is kept as it is. This method
+/// is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = set_paste_title_async(
+///         "PasteID",
+///         "A better title",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{}", paste.title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn set_paste_title_async(id: &str, title: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(id, auth_token).await?;
+    send_edit_async(EditObject { title: title.to_string(), ..EditObject::from_paste(&paste) }, id, auth_token).await
+}
+
+/// Changes the title of a pasty of a paste on your
+/// account, see `edit_pasty`. This method is
+/// synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = set_pasty_title(
+///         "PasteID",
+///         "PastyID",
+///         "main.rs",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{}", paste.pasties[0].title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn set_pasty_title(
+    paste_id: &str,
+    pasty_id: &str,
+    title: &str,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    edit_pasty(paste_id, pasty_id, |pasty| pasty.title = title.to_string(), auth_token)
+}
+
+/// Changes the title of a pasty of a paste on your
+/// account, see `edit_pasty_async`. This method is
+/// asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = set_pasty_title_async(
+///         "PasteID",
+///         "PastyID",
+///         "main.rs",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{}", paste.pasties[0].title);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn set_pasty_title_async(
+    paste_id: &str,
+    pasty_id: &str,
+    title: &str,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    edit_pasty_async(paste_id, pasty_id, |pasty| pasty.title = title.to_string(), auth_token).await
+}
+
+/// You can only delete pastes on your account, which
+/// means you must also provide the authorization key.
+/// This action is irreversible can the paste cannot
+/// be restored in any way. This methods sends the
+/// request synchronously.
+///
+/// This method returns a `DeleteOutcome` telling
+/// whether the paste was deleted, and if not, why.
+/// An error is returned instead when:
+///
+/// - the id is not a valid paste id
+///   (`PasteMystError::InvalidId`),
+/// - the request could not be sent or its
+///   response read (`PasteMystError::Request`),
+/// - PasteMyst refused it for sending too many
+///   requests (`PasteMystError::RateLimited`).
+///
+/// ### API Docs
+/// The relevent link to the API Documentation
+/// is: https://paste.myst.rs/api-docs/paste
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste_del_result = delete_paste(
+///         "PasteID",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     match paste_del_result {
+///         DeleteOutcome::Deleted => println!("Paste has been deleted successfully."),
+///         DeleteOutcome::NotFound => println!("The paste does not exist."),
+///         DeleteOutcome::Unauthorized => println!("The paste is not yours to delete."),
+///         DeleteOutcome::Other { status, message } => println!("Something went wrong ({}): {}", status, message),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn delete_paste(id: &str, auth_token: impl AsRef<str>) -> Result<DeleteOutcome, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::blocking_http()?
+        .delete(parse_url(id)?)
+        .header("Authorization", auth_token)
+        .send_request()?;
+    DeleteOutcome::from_status(error::check_status(result).map(|_| ()))
+}
+
+/// You can only delete pastes on your account, which
+/// means you must also provide the authorization key.
+/// This action is irreversible can the paste cannot
+/// be restored in any way. This methods sends the
+/// request asynchronously.
+///
+/// This method returns a `DeleteOutcome` telling
+/// whether the paste was deleted, and if not, why.
+/// An error is returned instead when:
+///
+/// - the id is not a valid paste id
+///   (`PasteMystError::InvalidId`),
+/// - the request could not be sent or its
+///   response read (`PasteMystError::Request`),
+/// - PasteMyst refused it for sending too many
+///   requests (`PasteMystError::RateLimited`).
+///
+/// ### API Docs
+/// The relevent link to the API Documentation
+/// is: https://paste.myst.rs/api-docs/paste
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste_del_result = delete_paste_async(
+///         "PasteID",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     if paste_del_result.is_deleted() { println!("Paste has been deleted successfully."); }
+///     else { println!("Something went wrong: {:?}", paste_del_result); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn delete_paste_async(id: &str, auth_token: impl AsRef<str>) -> Result<DeleteOutcome, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let result = connection::http()?
+        .delete(parse_url(id)?)
+        .header("Authorization", auth_token)
+        .send_request().await?;
+    DeleteOutcome::from_status(error::check_status_async(result).await.map(|_| ()))
+}
+
+/// Deletes many pastes on your account, one after
+/// the other, and reports what happened to each of
+/// them. A paste that can't be deleted doesn't stop
+/// the others from being deleted, its error is put
+/// in the report instead. This method is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let report = delete_pastes(
+///         &["hipfqanx", "cys6ypb4"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     );
+///     println!("{} deleted, {} already gone", report.deleted.len(), report.not_found.len());
+///     for (id, error) in &report.failed { println!("{}: {}", id, error); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn delete_pastes<I: AsRef<str>>(ids: &[I], auth_token: impl AsRef<str>) -> DeleteReport {
+    let auth_token = auth_token.as_ref();
+    let mut report = DeleteReport::default();
+    for id in ids { report.record(id.as_ref(), delete_paste(id.as_ref(), auth_token)); }
+    report
+}
+
+/// Deletes many pastes on your account, a few at a
+/// time, and reports what happened to each of them.
+/// A paste that can't be deleted doesn't stop the
+/// others from being deleted, its error is put in
+/// the report instead. This method is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let report = delete_pastes_async(
+///         &["hipfqanx", "cys6ypb4"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await;
+///     if !report.is_success() { println!("{} pastes were not deleted", report.failed.len()); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn delete_pastes_async<I: AsRef<str>>(ids: &[I], auth_token: impl AsRef<str>) -> DeleteReport {
+    use futures::stream::{self, StreamExt};
+
+    let auth_token = auth_token.as_ref();
+    let mut deletions = stream::iter(ids)
+        .map(|id| async move { (id.as_ref(), delete_paste_async(id.as_ref(), auth_token).await) })
+        .buffered(DELETE_CONCURRENCY);
+    let mut report = DeleteReport::default();
+    while let Some((id, outcome)) = deletions.next().await { report.record(id, outcome); }
+    report
+}
+
+/// Adds tags to a paste on your account. The paste
+/// is fetched first so that its current tags can be
+/// merged with the new ones, duplicates are dropped,
+/// and the paste is then edited with the result. This
+/// method is synchronous.
+///
+/// Tags are trimmed and compared as is, so `"rust"`
+/// and `" rust "` are considered the same tag.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = add_tags(
+///         "PasteID",
+///         &["rust", "help"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn add_tags(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(id, auth_token)?;
+    let mut new_tags = Tags::from(&paste.tags[..]);
+    new_tags.extend(tags);
+    send_edit(edit_with_tags(paste, new_tags), id, auth_token)
+}
+
+/// Adds tags to a paste on your account. The paste
+/// is fetched first so that its current tags can be
+/// merged with the new ones, duplicates are dropped,
+/// and the paste is then edited with the result. This
+/// method is asynchronous.
+///
+/// Tags are trimmed and compared as is, so `"rust"`
+/// and `" rust "` are considered the same tag.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = add_tags_async(
+///         "PasteID",
+///         &["rust", "help"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn add_tags_async(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(id, auth_token).await?;
+    let mut new_tags = Tags::from(&paste.tags[..]);
+    new_tags.extend(tags);
+    send_edit_async(edit_with_tags(paste, new_tags), id, auth_token).await
+}
+
+/// Removes tags from a paste on your account. The
+/// paste is fetched first, the given tags are taken
+/// out of its current ones and the paste is then
+/// edited with what's left. Tags the paste does not
+/// have are ignored. This method is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = remove_tags(
+///         "PasteID",
+///         &["help"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn remove_tags(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(id, auth_token)?;
+    let mut new_tags = Tags::from(&paste.tags[..]);
+    for tag in tags { new_tags.remove(tag); }
+    send_edit(edit_with_tags(paste, new_tags), id, auth_token)
+}
+
+/// Removes tags from a paste on your account. The
+/// paste is fetched first, the given tags are taken
+/// out of its current ones and the paste is then
+/// edited with what's left. Tags the paste does not
+/// have are ignored. This method is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = remove_tags_async(
+///         "PasteID",
+///         &["help"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn remove_tags_async(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(id, auth_token).await?;
+    let mut new_tags = Tags::from(&paste.tags[..]);
+    for tag in tags { new_tags.remove(tag); }
+    send_edit_async(edit_with_tags(paste, new_tags), id, auth_token).await
+}
+
+/// Adds a single tag to a paste on your account,
+/// see `add_tags`. This method is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = add_tag(
+///         "PasteID",
+///         "reviewed",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn add_tag(id: &str, tag: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    add_tags(id, &[tag], auth_token)
+}
+
+/// Adds a single tag to a paste on your account,
+/// see `add_tags_async`. This method is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = add_tag_async(
+///         "PasteID",
+///         "reviewed",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn add_tag_async(id: &str, tag: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    add_tags_async(id, &[tag], auth_token).await
+}
+
+/// Removes a single tag from a paste on your account,
+/// see `remove_tags`. This method is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = remove_tag(
+///         "PasteID",
+///         "wip",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn remove_tag(id: &str, tag: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    remove_tags(id, &[tag], auth_token)
+}
+
+/// Removes a single tag from a paste on your account,
+/// see `remove_tags_async`. This method is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = remove_tag_async(
+///         "PasteID",
+///         "wip",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn remove_tag_async(id: &str, tag: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    remove_tags_async(id, &[tag], auth_token).await
+}
+
+/// Replaces every tag of a paste on your account.
+/// The tags are trimmed and duplicates are dropped,
+/// an empty list clears them. This method is
+/// synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = set_tags(
+///         "PasteID",
+///         &["rust", "solved"],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{:?}", paste.tags);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn set_tags(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(id, auth_token)?;
+    send_edit(edit_with_tags(paste, Tags::from(tags)), id, auth_token)
+}
+
+/// Replaces every tag of a paste on your account.
+/// The tags are trimmed and duplicates are dropped,
+/// an empty list clears them. This method is
+/// asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = set_tags_async(
+///         "PasteID",
+///         &[],
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     assert!(paste.tags.is_empty());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn set_tags_async(id: &str, tags: &[&str], auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(id, auth_token).await?;
+    send_edit_async(edit_with_tags(paste, Tags::from(tags)), id, auth_token).await
+}
+
+/// Creates an `EditObject` that keeps everything
+/// of a fetched paste except for its tags.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn edit_with_tags(paste: PasteObject, tags: Tags) -> EditObject {
+    EditObject { tags: tags.into_vec(), ..EditObject::from_paste(&paste) }
+}
+
+/// Fetches several pastes from your account and creates a
+/// single paste out of them, held under your account. The
+/// pasties are either kept as separate files or concatenated
+/// into one depending on the `MergeOptions`, which also
+/// decide the title and tags of the new paste and whether
+/// the source pastes are deleted afterwards. This method is
+/// synchronous.
+///
+/// The new paste is private if any of the source pastes
+/// is private. An id given more than once is only merged
+/// once. The sources are only deleted once the new paste
+/// has been created, every one of them is tried and if
+/// any can't be deleted the new paste is returned within
+/// `PasteMystError::CleanupFailed` so that it isn't lost,
+/// its error is a `PasteMystError::DeleteFailed` listing
+/// the sources that were kept. Sources that are already
+/// gone count as deleted.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let options = MergeOptions {
+///         title: Some(String::from("All my snippets")),
+///         delete_sources: true,
+///         ..Default::default()
+///     };
+///     let paste = merge(
+///         &["PasteID1", "PasteID2"],
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn merge(ids: &[&str], options: &MergeOptions, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let ids = unique_ids(ids);
+    let mut pastes: Vec<PasteObject> = Vec::new();
+    for id in &ids { pastes.push(get_private_paste(id, auth_token)?); }
+    let paste = create_private_paste(merge_pastes(&pastes, options), auth_token)?;
+    let deleted = match options.delete_sources {
+        true => delete_pastes(&ids, auth_token).into_result(),
+        false => Ok(()),
+    };
+    cleaned_up(paste, deleted)
+}
+
+/// Fetches several pastes from your account and creates a
+/// single paste out of them, held under your account. The
+/// pasties are either kept as separate files or concatenated
+/// into one depending on the `MergeOptions`, which also
+/// decide the title and tags of the new paste and whether
+/// the source pastes are deleted afterwards. This method is
+/// asynchronous.
+///
+/// The new paste is private if any of the source pastes
+/// is private. An id given more than once is only merged
+/// once. The sources are only deleted once the new paste
+/// has been created, every one of them is tried and if
+/// any can't be deleted the new paste is returned within
+/// `PasteMystError::CleanupFailed` so that it isn't lost,
+/// its error is a `PasteMystError::DeleteFailed` listing
+/// the sources that were kept. Sources that are already
+/// gone count as deleted.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let options = MergeOptions { concatenate: true, ..Default::default() };
+///     let paste = merge_async(
+///         &["PasteID1", "PasteID2"],
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn merge_async(ids: &[&str], options: &MergeOptions, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let ids = unique_ids(ids);
+    let mut pastes: Vec<PasteObject> = Vec::new();
+    for id in &ids { pastes.push(get_private_paste_async(id, auth_token).await?); }
+    let paste = create_private_paste_async(merge_pastes(&pastes, options), auth_token).await?;
+    let deleted = match options.delete_sources {
+        true => delete_pastes_async(&ids, auth_token).await.into_result(),
+        false => Ok(()),
+    };
+    cleaned_up(paste, deleted)
+}
+
+/// The ids without repeats, in the order
+/// they first appear in, so that a paste
+/// given twice is only merged once.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn unique_ids<'a>(ids: &[&'a str]) -> Vec<&'a str> {
+    let mut seen = std::collections::HashSet::new();
+    ids.iter().copied().filter(|id| seen.insert(*id)).collect()
+}
+
+/// Combines fetched pastes into the
+/// `CreateObject` of the merged paste.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn merge_pastes(pastes: &[PasteObject], options: &MergeOptions) -> CreateObject {
+    let mut pasties: Vec<PastyObject> = pastes
+        .iter()
+        .flat_map(|paste| paste.pasties.iter())
+        .map(|pasty| PastyObject { _id: str!(""), ..pasty.clone() })
+        .collect();
+    let title = options.title.clone().unwrap_or_else(|| {
+        pastes.iter().map(|paste| paste.title.as_str()).collect::<Vec<&str>>().join(" + ")
+    });
+    if options.concatenate && !pasties.is_empty() {
+        let language = if pasties.iter().all(|pasty| pasty.language == pasties[0].language) {
+            pasties[0].language.clone()
+        } else { str!(crate::data::language::AUTODETECT) };
+        pasties = vec![PastyObject {
+            _id: str!(""),
+            language,
+            title: title.clone(),
+            code: pasties.iter().map(|pasty| pasty.code.as_str()).collect::<Vec<&str>>().join("\n\n"),
+        }];
+    }
+    let mut tags = Tags::new();
+    if options.merge_tags {
+        for paste in pastes { tags.extend(&paste.tags); }
+    }
+    CreateObject {
+        title,
+        expiresIn: options.expires_in,
+        isPrivate: pastes.iter().any(|paste| paste.isPrivate),
+        isPublic: false,
+        tags: tags.into_vec(),
+        pasties,
+    }
+}
+
+/// Splits a paste on your account into several new pastes,
+/// also held under your account, and returns their ids.
+/// Depending on `SplitBy`, a paste is created for each
+/// pasty or for each chunk of lines of each pasty. The
+/// title, tags, visibility and expiry of the original
+/// paste are copied over. This method is synchronous.
+///
+/// The original paste is left untouched. Chunks without
+/// any code are skipped, and every new paste is validated
+/// before the first one is created. If creating one fails
+/// after others were created, their ids are returned within
+/// `PasteMystError::SplitFailed` so that they aren't lost.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let ids = split(
+///         "PasteID",
+///         SplitBy::Pasty,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     for id in ids { println!("https://paste.myst.rs/{}", id); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn split(id: &str, split_by: SplitBy, auth_token: impl AsRef<str>) -> Result<Vec<String>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste(id, auth_token)?;
+    let mut ids: Vec<String> = Vec::new();
+    for contents in split_paste(&paste, split_by)? {
+        match create_private_paste(contents, auth_token) {
+            Ok(created) => ids.push(created._id),
+            Err(error) => return Err(split_failed(ids, error)),
+        }
+    }
+    Ok(ids)
+}
+
+/// Splits a paste on your account into several new pastes,
+/// also held under your account, and returns their ids.
+/// Depending on `SplitBy`, a paste is created for each
+/// pasty or for each chunk of lines of each pasty. The
+/// title, tags, visibility and expiry of the original
+/// paste are copied over. This method is asynchronous.
+///
+/// The original paste is left untouched. Chunks without
+/// any code are skipped, and every new paste is validated
+/// before the first one is created. If creating one fails
+/// after others were created, their ids are returned within
+/// `PasteMystError::SplitFailed` so that they aren't lost.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let ids = split_async(
+///         "PasteID",
+///         SplitBy::LineCount(100),
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     for id in ids { println!("https://paste.myst.rs/{}", id); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn split_async(id: &str, split_by: SplitBy, auth_token: impl AsRef<str>) -> Result<Vec<String>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste = get_private_paste_async(id, auth_token).await?;
+    let mut ids: Vec<String> = Vec::new();
+    for contents in split_paste(&paste, split_by)? {
+        match create_private_paste_async(contents, auth_token).await {
+            Ok(created) => ids.push(created._id),
+            Err(error) => return Err(split_failed(ids, error)),
+        }
+    }
+    Ok(ids)
+}
+
+/// The error of a split that failed, holding the
+/// ids of the pastes created before it did.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn split_failed(created: Vec<String>, error: PasteMystError) -> PasteMystError {
+    match created.is_empty() {
+        true => error,
+        false => PasteMystError::SplitFailed { created, error: Box::new(error) },
+    }
+}
+
+/// Creates the `CreateObject` of each paste a fetched
+/// paste is split into, skipping chunks without code.
+/// They're all validated so that nothing is created
+/// if one of them can't be.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn split_paste(paste: &PasteObject, split_by: SplitBy) -> Result<Vec<CreateObject>, PasteMystError> {
+    let expires_in = paste.expires_in()?;
+    let mut pastes: Vec<CreateObject> = Vec::new();
+    for pasty in &paste.pasties {
+        let title = format!("{} - {}", paste.title, pasty.title);
+        let mut chunks: Vec<String> = match split_by {
+            SplitBy::Pasty => vec![pasty.code.clone()],
+            SplitBy::LineCount(count) => pasty
+                .code
+                .lines()
+                .collect::<Vec<&str>>()
+                .chunks(count.max(1))
+                .map(|lines| lines.join("\n"))
+                .collect(),
+        };
+        chunks.retain(|code| !code.trim().is_empty());
+        let total = chunks.len();
+        for (index, code) in chunks.into_iter().enumerate() {
+            let title = if total > 1 { format!("{} ({}/{})", title, index + 1, total) } else { title.clone() };
+            pastes.push(CreateObject {
+                title: title.clone(),
+                expiresIn: expires_in,
+                isPrivate: paste.isPrivate,
+                isPublic: paste.isPublic,
+                tags: paste.tags.clone(),
+                pasties: vec![PastyObject { _id: str!(""), title, code, ..pasty.clone() }],
+            });
+        }
+    }
+    if pastes.is_empty() {
+        return Err(PasteMystError::InvalidPaste(str!("the paste has no code to split")));
+    }
+    pastes.iter().try_for_each(CreateObject::validate)?;
+    Ok(pastes)
+}
+
+/// PasteMyst does not allow changing when a paste expires,
+/// so this method re-creates a paste on your account with
+/// the given expiry instead, e.g. to extend its life. It
+/// returns the id of the original paste together with the
+/// new paste. This method is synchronous.
+///
+/// Depending on the `RecreateOptions`, the original paste
+/// is deleted afterwards, or kept with an extra pasty
+/// pointing to where the paste has moved to. If that
+/// fails, the new paste is returned within
+/// `PasteMystError::CleanupFailed` so that it isn't lost.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::time::ExpiresIn;
+///
+/// fn main() -> PasteResult<()> {
+///     let options = RecreateOptions { delete_original: true, ..Default::default() };
+///     let recreated = recreate_with_expiry(
+///         "PasteID",
+///         ExpiresIn::Never,
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{} is now {}", recreated.original_id, recreated.paste._id);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn recreate_with_expiry(
+    id: &str,
+    expires_in: ExpiresIn,
+    options: &RecreateOptions,
+    auth_token: impl AsRef<str>,
+) -> Result<RecreatedPaste, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste(id, auth_token)?;
+    let paste = create_private_paste(create_from_paste(&original, expires_in), auth_token)?;
+    let cleaned = if options.delete_original {
+        delete_paste(id, auth_token).and_then(DeleteOutcome::into_result)
+    } else if options.redirect_note {
+        send_edit(edit_with_redirect(original, &paste._id), id, auth_token).map(drop)
+    } else { Ok(()) };
+    Ok(RecreatedPaste { original_id: id.to_string(), paste: cleaned_up(paste, cleaned)? })
+}
+
+/// PasteMyst does not allow changing when a paste expires,
+/// so this method re-creates a paste on your account with
+/// the given expiry instead, e.g. to extend its life. It
+/// returns the id of the original paste together with the
+/// new paste. This method is asynchronous.
+///
+/// Depending on the `RecreateOptions`, the original paste
+/// is deleted afterwards, or kept with an extra pasty
+/// pointing to where the paste has moved to. If that
+/// fails, the new paste is returned within
+/// `PasteMystError::CleanupFailed` so that it isn't lost.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::time::ExpiresIn;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let options = RecreateOptions { redirect_note: true, ..Default::default() };
+///     let recreated = recreate_with_expiry_async(
+///         "PasteID",
+///         ExpiresIn::OneYear,
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{} is now {}", recreated.original_id, recreated.paste._id);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn recreate_with_expiry_async(
+    id: &str,
+    expires_in: ExpiresIn,
+    options: &RecreateOptions,
+    auth_token: impl AsRef<str>,
+) -> Result<RecreatedPaste, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste_async(id, auth_token).await?;
+    let paste = create_private_paste_async(create_from_paste(&original, expires_in), auth_token).await?;
+    let cleaned = if options.delete_original {
+        delete_paste_async(id, auth_token).await.and_then(DeleteOutcome::into_result)
+    } else if options.redirect_note {
+        send_edit_async(edit_with_redirect(original, &paste._id), id, auth_token).await.map(drop)
+    } else { Ok(()) };
+    Ok(RecreatedPaste { original_id: id.to_string(), paste: cleaned_up(paste, cleaned)? })
+}
+
+/// Copies a paste, yours or anyone else's that you can
+/// see, into a new paste on your account. The title,
+/// expiry and visibility are kept unless the `ForkOptions`
+/// change them, the tags and pasties are always kept.
+/// Returns the new paste. This method is synchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let options = ForkOptions { title: Some(String::from("My copy")), ..Default::default() };
+///     let fork = fork_paste(
+///         "hipfqanx",
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{}", fork.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn fork_paste(id: &str, options: &ForkOptions, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste(id, auth_token)?;
+    create_private_paste(fork_from_paste(&original, options), auth_token)
+}
+
+/// Copies a paste, yours or anyone else's that you can
+/// see, into a new paste on your account. The title,
+/// expiry and visibility are kept unless the `ForkOptions`
+/// change them, the tags and pasties are always kept.
+/// Returns the new paste. This method is asynchronous.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::time::ExpiresIn;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let options = ForkOptions {
+///         expires_in: Some(ExpiresIn::OneWeek),
+///         private: Some(true),
+///         ..Default::default()
+///     };
+///     let fork = fork_paste_async(
+///         "hipfqanx",
+///         &options,
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{}", fork.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn fork_paste_async(
+    id: &str,
+    options: &ForkOptions,
+    auth_token: impl AsRef<str>,
+) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste_async(id, auth_token).await?;
+    create_private_paste_async(fork_from_paste(&original, options), auth_token).await
+}
+
+/// Creates the `CreateObject` of a fork, an expiry
+/// this crate doesn't know is replaced by `never`.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn fork_from_paste(paste: &PasteObject, options: &ForkOptions) -> CreateObject {
+    let contents = create_from_paste(paste, options.expires_in.unwrap_or_else(|| paste.expires_in().unwrap_or_default()));
+    CreateObject {
+        title: options.title.clone().unwrap_or(contents.title),
+        isPrivate: options.private.unwrap_or(contents.isPrivate),
+        isPublic: options.public.unwrap_or(contents.isPublic),
+        ..contents
+    }
+}
+
+/// Creates a `CreateObject` holding the same
+/// contents and metadata as a fetched paste.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn create_from_paste(paste: &PasteObject, expires_in: ExpiresIn) -> CreateObject {
+    CreateObject {
+        title: paste.title.clone(),
+        expiresIn: expires_in,
+        isPrivate: paste.isPrivate,
+        isPublic: paste.isPublic,
+        tags: paste.tags.clone(),
+        pasties: paste
+            .pasties
+            .iter()
+            .map(|pasty| PastyObject { _id: str!(""), ..pasty.clone() })
+            .collect(),
+    }
+}
+
+/// Creates an `EditObject` that keeps a fetched
+/// paste as is and adds a pasty to it pointing
+/// to the paste it has moved to.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn edit_with_redirect(paste: PasteObject, new_id: &str) -> EditObject {
+    let mut edit = EditObject::from_paste(&paste);
+    edit.pasties.push(PastyObject {
+        _id: str!(""),
+        language: str!(crate::data::language::PLAIN),
+        title: str!("Moved"),
+        code: format!("This paste has moved to {}{}", ENDPOINT, new_id),
+    });
+    edit
+}
+
+/// Re-creates a paste on your account without any
+/// authorization, so that the new paste has no owner,
+/// then deletes the original and returns the new paste.
+/// This is meant for when something was shared under
+/// your account by mistake. This method is synchronous.
+///
+/// Keep in mind that pastes without an owner cannot be
+/// private, edited or deleted, so the new paste is
+/// public to anyone with the link and will stay up
+/// until it expires, its expiry is copied over from
+/// the original paste. A private paste is refused with
+/// `PasteMystError::InvalidPaste` for that reason, make
+/// it public with an edit first if that is what you want.
+///
+/// If the original can't be deleted afterwards the new
+/// paste is returned within `PasteMystError::CleanupFailed`,
+/// as there would be no other way to find it.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = anonymize(
+///         "PasteID",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     )?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "blocking"))]
+pub fn anonymize(id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste(id, auth_token)?;
+    let paste = create_paste(anonymous_from_paste(&original)?)?;
+    let deleted = delete_paste(id, auth_token).and_then(DeleteOutcome::into_result);
+    cleaned_up(paste, deleted)
+}
+
+/// Re-creates a paste on your account without any
+/// authorization, so that the new paste has no owner,
+/// then deletes the original and returns the new paste.
+/// This is meant for when something was shared under
+/// your account by mistake. This method is asynchronous.
+///
+/// Keep in mind that pastes without an owner cannot be
+/// private, edited or deleted, so the new paste is
+/// public to anyone with the link and will stay up
+/// until it expires, its expiry is copied over from
+/// the original paste. A private paste is refused with
+/// `PasteMystError::InvalidPaste` for that reason, make
+/// it public with an edit first if that is what you want.
+///
+/// If the original can't be deleted afterwards the new
+/// paste is returned within `PasteMystError::CleanupFailed`,
+/// as there would be no other way to find it.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     let paste = anonymize_async(
+///         "PasteID",
+///         "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings",
+///     ).await?;
+///     println!("{}", paste.url());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async"))]
+pub async fn anonymize_async(id: &str, auth_token: impl AsRef<str>) -> Result<PasteObject, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let original = get_private_paste_async(id, auth_token).await?;
+    let paste = create_paste_async(anonymous_from_paste(&original)?).await?;
+    let deleted = delete_paste_async(id, auth_token).await.and_then(DeleteOutcome::into_result);
+    cleaned_up(paste, deleted)
+}
+
+/// Creates the `CreateObject` of an unowned copy of
+/// a fetched paste, which can be neither private
+/// nor shown on a profile. A private paste is
+/// refused so that it isn't made public.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn anonymous_from_paste(paste: &PasteObject) -> Result<CreateObject, PasteMystError> {
+    if paste.isPrivate {
+        return Err(PasteMystError::InvalidPaste(str!("a private paste would be public once it has no owner")));
+    }
+    Ok(CreateObject {
+        isPrivate: false,
+        isPublic: false,
+        ..create_from_paste(paste, paste.expires_in()?)
+    })
+}
+
+/// Returns the paste created in place of others once
+/// they're removed (or point to it), or
+/// `PasteMystError::CleanupFailed` holding it if
+/// that failed.
+#[cfg(all(feature = "paste", feature = "network"))]
+fn cleaned_up(paste: PasteObject, removed: Result<(), PasteMystError>) -> Result<PasteObject, PasteMystError> {
+    match removed {
+        Ok(()) => Ok(paste),
+        Err(error) => Err(PasteMystError::CleanupFailed { paste: Box::new(paste), error: Box::new(error) }),
+    }
+}
+
+/// Watches the star count of a paste by fetching it every
+/// `interval` and yields a `StarChange` each time the count
+/// differs from the previous fetch, so that you can get
+/// notified when your pastes gain (or lose) attention. The
+/// first fetch happens right away and only sets the count
+/// that later ones are compared with.
+///
+/// The paste is fetched with the token if one is given,
+/// which private pastes need. Errors are yielded as they
+/// happen without ending the stream, the paste is simply
+/// fetched again after the next interval. Drop the stream
+/// to stop watching.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use pastemyst::paste::*;
+/// use pastemyst::token::ApiToken;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> PasteResult<()> {
+///     // Private pastes can only be watched with a token.
+///     let token = ApiToken::from_env().ok();
+///     let mut changes = Box::pin(watch_stars("hipfqanx", Duration::from_secs(60), token.as_ref()));
+///     while let Some(change) = changes.next().await {
+///         let change = change?;
+///         println!("{} -> {} ({:+})", change.previous, change.current, change.delta());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+pub fn watch_stars(
+    id: &str,
+    interval: std::time::Duration,
+    auth_token: Option<&ApiToken>,
+) -> impl futures::Stream<Item = Result<StarChange, PasteMystError>> {
+    let state = (id.to_string(), auth_token.cloned(), None, false);
+    futures::stream::unfold(state, move |(id, auth_token, mut last, started)| async move {
+        if started { connection::sleep(interval).await; }
+        loop {
+            let fetched = match &auth_token {
+                Some(auth_token) => get_private_paste_async(&id, auth_token).await,
+                None => get_paste_async(&id).await,
+            };
+            match fetched {
+                Ok(paste) => match last.replace(paste.stars) {
+                    Some(previous) if previous != paste.stars => {
+                        let change = StarChange { previous, current: paste.stars };
+                        return Some((Ok(change), (id, auth_token, last, true)));
+                    }
+                    _ => {}
+                },
+                Err(error) => return Some((Err(error), (id, auth_token, last, true))),
+            }
+            connection::sleep(interval).await;
+        }
+    })
+}
+
+/// Parses the url by combining
+/// the `PASTE_ENDPOINT` with a
+/// provided id.
+///
+/// Returns `PasteMystError::InvalidId` if the id
+/// contains anything but letters, digits, `-` or
+/// `_`, so that whitespace, slashes or a whole
+/// pasted-in url can't change the request path.
+#[cfg(all(feature = "paste", feature = "network"))]
+pub(crate) fn parse_url(id: &str) -> Result<String, PasteMystError> {
+    Ok(PASTE_ENDPOINT.to_owned() + validate_id(id)?)
+}
+
+/// The url of the raw contents of a pasty,
+/// both ids are checked like `parse_url` does.
+#[cfg(all(feature = "paste", feature = "network"))]
+pub(crate) fn parse_raw_url(paste_id: &str, pasty_id: &str) -> Result<String, PasteMystError> {
+    Ok(format!("{}{}/{}", RAW_ENDPOINT, validate_id(paste_id)?, validate_id(pasty_id)?))
+}
+
+/// Returns the id as is if it's made of
+/// letters, digits, `-` and `_` only.
+fn validate_id(id: &str) -> Result<&str, PasteMystError> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(PasteMystError::InvalidId(id.to_string()));
+    }
+    Ok(id)
+}
+
+/// A paste id checked to be one, read from either
+/// the id itself or a link to the paste. Links can
+/// point to any host, so that links to self-hosted
+/// instances work too, and the pasty they point
+/// to (`#<pasty id>`) is kept.
+///
+/// It dereferences to the id, so it can be given
+/// to any function taking one.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::PasteId;
+///
+/// let id = PasteId::parse("https://paste.myst.rs/hipfqanx#cys6ypb4").unwrap();
+/// assert_eq!(id.as_str(), "hipfqanx");
+/// assert_eq!(id.pasty(), Some("cys6ypb4"));
+///
+/// let id = PasteId::parse("paste.example.com/raw/hipfqanx/cys6ypb4").unwrap();
+/// assert_eq!(id.as_str(), "hipfqanx");
+/// assert_eq!(id.pasty(), Some("cys6ypb4"));
+///
+/// assert_eq!(PasteId::parse(" hipfqanx ").unwrap().as_str(), "hipfqanx");
+/// assert!(PasteId::parse("https://paste.myst.rs/").is_err());
+/// assert!(PasteId::parse("not an id").is_err());
+/// ```
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let id = PasteId::parse("https://paste.myst.rs/hipfqanx")?;
+///     let paste = get_paste(&id)?;
+///     println!("{}", paste.title);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PasteId {
+    id: String,
+    pasty: Option<String>,
+}
+
+impl PasteId {
+    /// Reads a paste id, or the id in a link to a paste.
+    /// The paths `/<id>`, `/raw/<id>/<pasty id>` and
+    /// `/api/v2/paste/<id>` are understood, with or
+    /// without the scheme.
+    ///
+    /// Returns `PasteMystError::InvalidUrl` for a link
+    /// with no paste id in it and
+    /// `PasteMystError::InvalidId` for an id with
+    /// characters a paste id can't have.
+    pub fn parse(input: &str) -> Result<Self, PasteMystError> {
+        let input = input.trim();
+        let (rest, fragment) = match input.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (input, None),
+        };
+        let rest = rest.split('?').next().unwrap_or_default();
+        let rest = rest.split_once("://").map_or(rest, |(_, rest)| rest);
+        let (id, raw_pasty) = match rest.split_once('/') {
+            None => (rest, None),
+            Some((_host, path)) => {
+                let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+                match segments.as_slice() {
+                    [id] | ["api", "v2", "paste", id] => (*id, None),
+                    ["raw", id] => (*id, None),
+                    ["raw", id, pasty] => (*id, Some(*pasty)),
+                    _ => return Err(PasteMystError::InvalidUrl(input.to_string())),
+                }
+            }
+        };
+        let pasty = match raw_pasty.or(fragment) {
+            Some("") | None => None,
+            Some(pasty) => Some(validate_id(pasty)?.to_string()),
+        };
+        Ok(PasteId { id: validate_id(id)?.to_string(), pasty })
+    }
+
+    /// The id of the paste.
+    pub fn as_str(&self) -> &str { &self.id }
+
+    /// The id of the pasty the link
+    /// pointed to, if it did.
+    pub fn pasty(&self) -> Option<&str> { self.pasty.as_deref() }
+}
+
+impl std::str::FromStr for PasteId {
+    type Err = PasteMystError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> { PasteId::parse(input) }
+}
+
+impl std::fmt::Display for PasteId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.id) }
+}
+
+impl std::ops::Deref for PasteId {
+    type Target = str;
+
+    fn deref(&self) -> &str { &self.id }
+}
+
+impl AsRef<str> for PasteId {
+    fn as_ref(&self) -> &str { &self.id }
+}
+
+/// The paste object recieved when
+/// getting a paste. It contains
+/// both the `PastyObject` and
+/// `EditHistory` in an array.
+///
+/// Fields PasteMyst leaves out are set to their
+/// default value and ones this crate doesn't
+/// know are ignored, so that a small change of
+/// the API doesn't break it. Parse it with
+/// `lenient::paste_from_str_strict` to fail
+/// instead.
+///
+/// ### API Docs
+/// The relevent link to the API documentation
+/// is: https://paste.myst.rs/api-docs/objects
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let _foo: PasteObject = get_paste("hipfqanx")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// It can be serialized back to the json PasteMyst
+/// sent, e.g. to keep a copy of it on disk.
+///
+/// ```rust
+/// use pastemyst::paste::PasteObject;
+///
+/// let body = r#"{
+///     "_id": "hipfqanx", "ownerId": "", "title": "Hello",
+///     "createdAt": 1612534264, "expiresIn": "never", "deletesAt": 0,
+///     "stars": 2, "isPrivate": false, "isPublic": false, "tags": ["rust"],
+///     "pasties": [{ "_id": "abc", "language": "Rust", "title": "main.rs", "code": "fn main() {}" }],
+///     "edits": [{
+///         "_id": "e1", "editId": "1", "editType": 3, "metadata": ["abc"],
+///         "edit": "fn main() { }", "editedAt": 1612534300
+///     }]
+/// }"#;
+///
+/// let paste: PasteObject = serde_json::from_str(body).unwrap();
+/// let saved = serde_json::to_string(&paste).unwrap();
+/// let restored: PasteObject = serde_json::from_str(&saved).unwrap();
+/// assert_eq!(format!("{:?}", restored), format!("{:?}", paste));
+/// assert_eq!(
+///     serde_json::to_value(&restored).unwrap(),
+///     serde_json::from_str::<serde_json::Value>(body).unwrap(),
+/// );
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(non_snake_case, dead_code)]
+pub struct PasteObject {
+    /// Id of the paste.
     pub _id: String,
     /// Id of the owner, if it doesn't
     ///  have an owner it's set to "".
+    #[serde(default)]
     pub ownerId: String,
     /// Title of the paste.
+    #[serde(default)]
     pub title: String,
     /// Unix time of when
     /// the paste is created.
+    #[serde(default)]
     pub createdAt: u64,
     /// When the paste will expire,
     /// possible values are
     /// `never`, `1h`, `2h`, `10h`,
     /// `1d`, `2d`, `1w`, `1m`, `1y`.
+    #[serde(default)]
     pub expiresIn: String,
     /// When the paste will be deleted, if
     /// it has no expiry time it's set to 0.
+    #[serde(default)]
     pub deletesAt: u64,
     /// Number of stars the paste received.
+    #[serde(default)]
     pub stars: u64,
     /// If it's private it's only
     /// accessible by the owner.
+    #[serde(default)]
     pub isPrivate: bool,
     /// Is it displayed on the
     /// owner's public profile.
+    #[serde(default)]
     pub isPublic: bool,
     /// List of tags.
+    #[serde(default)]
     pub tags: Vec<String>,
     /// List of pasties/files in
     /// the paste, can't be empty.
     pub pasties: Vec<PastyObject>,
     /// List of edits.
+    #[serde(default)]
     pub edits: Vec<EditHistory>,
 }
 
+impl PasteObject {
+    /// When the paste expires as an `ExpiresIn`,
+    /// `PasteMystError::InvalidExpiry` is returned
+    /// for a value this crate doesn't know.
+    pub fn expires_in(&self) -> Result<ExpiresIn, PasteMystError> { self.expiresIn.parse() }
+
+    /// The link to the paste on PasteMyst.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let paste = get_paste("hipfqanx")?;
+    ///     assert_eq!(paste.url(), "https://paste.myst.rs/hipfqanx");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn url(&self) -> String { self.url_on(BASE_URL) }
+
+    /// The link to the paste on another instance
+    /// of PasteMyst, such as a self-hosted one.
+    pub fn url_on(&self, base_url: &str) -> String { format!("{}/{}", base_url.trim_end_matches('/'), self._id) }
+
+    /// The pasty with the given id, if
+    /// it belongs to the paste.
+    pub fn pasty_by_id(&self, pasty_id: &str) -> Option<&PastyObject> {
+        self.pasties.iter().find(|pasty| pasty._id == pasty_id)
+    }
+
+    /// The first pasty with the given title.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::data::Language;
+    /// use pastemyst::paste::*;
+    ///
+    /// let body = r#"{
+    ///     "_id": "hipfqanx", "pasties": [
+    ///         { "_id": "a", "language": "Rust", "title": "main.rs", "code": "fn main() {}" },
+    ///         { "_id": "b", "language": "Markdown", "title": "README.md", "code": "Hello" },
+    ///         { "_id": "c", "language": "Rust", "title": "lib.rs", "code": "" }
+    ///     ]
+    /// }"#;
+    /// let paste: PasteObject = serde_json::from_str(body).unwrap();
+    ///
+    /// assert_eq!(paste.pasty_by_title("README.md").unwrap()._id, "b");
+    /// assert_eq!(paste.pasty_by_id("c").unwrap().title, "lib.rs");
+    /// assert!(paste.pasty_by_id("d").is_none());
+    /// assert_eq!(paste.iter_pasties().count(), 3);
+    ///
+    /// let rust: Vec<&str> = paste.pasties_with_language(Language::Rust).map(|pasty| pasty.title.as_str()).collect();
+    /// assert_eq!(rust, vec!["main.rs", "lib.rs"]);
+    /// ```
+    pub fn pasty_by_title(&self, title: &str) -> Option<&PastyObject> {
+        self.pasties.iter().find(|pasty| pasty.title == title)
+    }
+
+    /// Iterates over the pasties, in
+    /// the order of the paste.
+    pub fn iter_pasties(&self) -> std::slice::Iter<'_, PastyObject> { self.pasties.iter() }
+
+    /// Iterates over the pasties written in a language,
+    /// a `Language` or one of the names in
+    /// `data::language`. The name is compared
+    /// ignoring case.
+    pub fn pasties_with_language(&self, language: impl Into<LanguageSpec>) -> impl Iterator<Item = &PastyObject> + '_ {
+        let language = language.into();
+        self.pasties.iter().filter(move |pasty| pasty.language.eq_ignore_ascii_case(language.as_str()))
+    }
+
+    /// A SHA-256 of what's in the paste, as lowercase
+    /// hex: its title, tags and the title, language
+    /// and code of each pasty in order. Ids, stars,
+    /// dates and the edit history are left out, so
+    /// the digest of a local copy can be compared to
+    /// the one of the paste on PasteMyst to tell
+    /// whether it needs to be downloaded or uploaded
+    /// again.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::PasteObject;
+    ///
+    /// let body = r#"{
+    ///     "_id": "hipfqanx", "title": "Hello", "stars": 2,
+    ///     "pasties": [{ "_id": "a", "language": "Rust", "title": "main.rs", "code": "fn main() {}" }]
+    /// }"#;
+    /// let paste: PasteObject = serde_json::from_str(body).unwrap();
+    /// let mut copy = paste.clone();
+    /// copy.stars = 3;
+    /// assert_eq!(copy.digest(), paste.digest());
+    ///
+    /// copy.pasties[0].code.push('\n');
+    /// assert_ne!(copy.digest(), paste.digest());
+    /// ```
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        let mut field = |value: &str| {
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value.as_bytes());
+        };
+        field(&self.title);
+        for tag in &self.tags { field(tag); }
+        field("");
+        for pasty in &self.pasties {
+            field(&pasty.title);
+            field(&pasty.language);
+            field(&pasty.code);
+        }
+        hex(&hasher.finalize())
+    }
+
+    /// Writes every pasty to a file in the directory, which
+    /// is created if needed, named by `PastyObject::file_name`.
+    /// Pasties that would end up with the same name get a
+    /// number added, e.g. `notes-2.md`. Returns the paths
+    /// written, in the order of the pasties.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let paste = get_paste("hipfqanx")?;
+    ///     for path in paste.save_to_dir("hipfqanx")? {
+    ///         println!("{}", path.display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn save_to_dir(&self, dir: impl AsRef<std::path::Path>) -> Result<Vec<std::path::PathBuf>, PasteMystError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let mut paths: Vec<std::path::PathBuf> = Vec::new();
+        for pasty in &self.pasties {
+            let name = pasty.file_name();
+            let mut path = dir.join(&name);
+            let mut number = 2;
+            while paths.contains(&path) {
+                let name = std::path::Path::new(&name);
+                let stem = name.file_stem().unwrap_or_default().to_string_lossy();
+                path = dir.join(match name.extension() {
+                    Some(extension) => format!("{}-{}.{}", stem, number, extension.to_string_lossy()),
+                    None => format!("{}-{}", stem, number),
+                });
+                number += 1;
+            }
+            std::fs::write(&path, &pasty.code)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Breaks the paste down by the language of its
+    /// pasties, largest share first, which is what a
+    /// GitHub style language bar needs.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let paste = get_paste("hipfqanx")?;
+    ///     println!("{}", paste.composition());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn composition(&self) -> PasteComposition {
+        let mut languages: Vec<LanguageShare> = Vec::new();
+        for pasty in &self.pasties {
+            match languages.iter_mut().find(|share| share.language == pasty.language) {
+                Some(share) => {
+                    share.pasties += 1;
+                    share.bytes += pasty.code.len();
+                }
+                None => languages.push(LanguageShare {
+                    language: pasty.language.clone(),
+                    pasties: 1,
+                    bytes: pasty.code.len(),
+                    percentage: 0.0,
+                }),
+            }
+        }
+        let total_bytes: usize = languages.iter().map(|share| share.bytes).sum();
+        for share in &mut languages {
+            share.percentage = match total_bytes {
+                0 => share.pasties as f64 * 100.0 / self.pasties.len() as f64,
+                _ => share.bytes as f64 * 100.0 / total_bytes as f64,
+            };
+        }
+        languages.sort_by(|a, b| b.percentage.total_cmp(&a.percentage));
+        PasteComposition { languages }
+    }
+
+    /// A unified diff of what an edit of the paste changed,
+    /// from the old value stored in the edit to the value
+    /// right after it: the one stored by the next edit of
+    /// the same field, or the current one if there is none.
+    /// The file in the headers is the pasty's title, or
+    /// `title` for edits of the paste's title.
+    ///
+    /// `None` is returned if the edit isn't one of the
+    /// paste's or is of a kind this crate doesn't know.
+    /// An edit that changed nothing gives an empty diff.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::PasteObject;
+    ///
+    /// let paste: PasteObject = serde_json::from_str(r#"{
+    ///     "_id": "hipfqanx", "ownerId": "", "title": "Hello", "createdAt": 0,
+    ///     "expiresIn": "never", "deletesAt": 0, "stars": 0, "isPrivate": false,
+    ///     "isPublic": false, "tags": [],
+    ///     "pasties": [{ "_id": "x", "language": "Rust", "title": "main.rs",
+    ///                   "code": "fn main() {\n    println!(\"hi\");\n}" }],
+    ///     "edits": [{ "_id": "e", "editId": "1", "editType": 3, "metadata": ["x"],
+    ///                 "edit": "fn main() {\n}", "editedAt": 0 }]
+    /// }"#).unwrap();
+    /// let diff = paste.diff_for_edit(&paste.edits[0]).unwrap();
+    /// assert_eq!(diff, "--- a/main.rs\n+++ b/main.rs\n@@ -1,2 +1,3 @@\n fn main() {\n+    println!(\"hi\");\n }\n");
+    /// ```
+    pub fn diff_for_edit(&self, edit: &EditHistory) -> Option<String> {
+        let position = self.edits.iter().position(|other| other._id == edit._id)?;
+        let pasty_id = edit.metadata.first().map(String::as_str).unwrap_or_default();
+        let pasty = self.pasties.iter().find(|pasty| pasty._id == pasty_id);
+        let later = self.edits[position + 1..]
+            .iter()
+            .find(|other| other.editType == edit.editType && other.metadata.first() == edit.metadata.first())
+            .map(|other| other.edit.as_str());
+        let current = match edit.editType {
+            EditType::Title => Some(self.title.as_str()),
+            EditType::PastyTitle => pasty.map(|pasty| pasty.title.as_str()),
+            EditType::PastyLanguage => pasty.map(|pasty| pasty.language.as_str()),
+            EditType::PastyContent | EditType::PastyAdded => pasty.map(|pasty| pasty.code.as_str()),
+            EditType::PastyRemoved => None,
+            EditType::Unknown(_) => return None,
+        };
+        let (old, new) = match edit.editType {
+            EditType::PastyAdded => ("", current.unwrap_or_default()),
+            EditType::PastyRemoved => (edit.edit.as_str(), ""),
+            _ => (edit.edit.as_str(), later.or(current).unwrap_or_default()),
+        };
+        let name = match edit.editType {
+            EditType::Title => "title",
+            _ => pasty.map_or(pasty_id, |pasty| pasty.title.as_str()),
+        };
+        Some(unified_diff(old, new, name))
+    }
+
+    /// Every version of the paste, from the one it was
+    /// created as to the current one, rebuilt by undoing
+    /// its edits from the newest back. Edits sharing an
+    /// `editId` were made together and make one version.
+    /// The `edits` of each version only hold the edits
+    /// made up to it.
+    ///
+    /// A removed pasty is added back at the end of the
+    /// pasties, with the title and language the edit's
+    /// metadata holds after its id. Edits of a kind this
+    /// crate doesn't know are skipped.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::PasteObject;
+    ///
+    /// let paste: PasteObject = serde_json::from_str(r#"{
+    ///     "_id": "hipfqanx", "ownerId": "", "title": "Hello", "createdAt": 0,
+    ///     "expiresIn": "never", "deletesAt": 0, "stars": 0, "isPrivate": false,
+    ///     "isPublic": false, "tags": [],
+    ///     "pasties": [{ "_id": "x", "language": "Rust", "title": "main.rs", "code": "fn main() {}" }],
+    ///     "edits": [
+    ///         { "_id": "a", "editId": "1", "editType": 0, "metadata": [], "edit": "Untitled", "editedAt": 10 },
+    ///         { "_id": "b", "editId": "2", "editType": 3, "metadata": ["x"], "edit": "", "editedAt": 20 }
+    ///     ]
+    /// }"#).unwrap();
+    /// let titles: Vec<String> = paste.history_iter().map(|version| version.paste.title).collect();
+    /// assert_eq!(titles, ["Untitled", "Hello", "Hello"]);
+    ///
+    /// let before_code = paste.at_edit("1").unwrap();
+    /// assert_eq!(before_code.pasties[0].code, "");
+    /// assert_eq!(before_code.edits.len(), 1);
+    /// ```
+    pub fn history_iter(&self) -> impl Iterator<Item = PasteVersion> {
+        let mut versions: Vec<PasteVersion> = Vec::new();
+        let mut paste = self.clone();
+        while let Some(last) = paste.edits.last() {
+            let edit_id = last.editId.clone();
+            let edited_at = last.editedAt;
+            let start = paste.edits.iter().rposition(|edit| edit.editId != edit_id).map_or(0, |index| index + 1);
+            let mut previous = paste.clone();
+            for edit in previous.edits.split_off(start).iter().rev() { previous.undo(edit); }
+            versions.push(PasteVersion { edit_id: Some(edit_id), edited_at: Some(edited_at), paste });
+            paste = previous;
+        }
+        versions.push(PasteVersion { edit_id: None, edited_at: None, paste });
+        versions.into_iter().rev()
+    }
+
+    /// The paste as it was right after the edits
+    /// with the given `editId` were made, see
+    /// `history_iter`. `None` is returned if no
+    /// edit of the paste has that id.
+    pub fn at_edit(&self, edit_id: &str) -> Option<PasteObject> {
+        self.history_iter()
+            .find(|version| version.edit_id.as_deref() == Some(edit_id))
+            .map(|version| version.paste)
+    }
+
+    /// Puts back what an edit changed.
+    fn undo(&mut self, edit: &EditHistory) {
+        let pasty_id = edit.metadata.first().cloned().unwrap_or_default();
+        let pasty = self.pasties.iter_mut().find(|pasty| pasty._id == pasty_id);
+        match (edit.editType, pasty) {
+            (EditType::Title, _) => self.title = edit.edit.clone(),
+            (EditType::PastyTitle, Some(pasty)) => pasty.title = edit.edit.clone(),
+            (EditType::PastyLanguage, Some(pasty)) => pasty.language = edit.edit.clone(),
+            (EditType::PastyContent, Some(pasty)) => pasty.code = edit.edit.clone(),
+            (EditType::PastyAdded, _) => self.pasties.retain(|pasty| pasty._id != pasty_id),
+            (EditType::PastyRemoved, None) => self.pasties.push(PastyObject {
+                _id: pasty_id,
+                title: edit.metadata.get(1).cloned().unwrap_or_default(),
+                language: edit.metadata.get(2).cloned().unwrap_or_else(|| str!(crate::data::language::AUTODETECT)),
+                code: edit.edit.clone(),
+            }),
+            _ => {}
+        }
+    }
+}
+
 /// Information about a specific pasty in a paste.
 ///
 /// All fields except `language` are optional but due
@@ -595,26 +2881,222 @@ pub struct PasteObject {
 /// ## Examples
 ///
 /// ```rust
+/// use pastemyst::paste::PastyObject;
+///
 /// let pasty: PastyObject = PastyObject {
-///     _id: None,
-///     language: pastemyst::data::language::JSON,
-///     title: Some(String::from("This is a pasty title")),
-///     code: Some(String::from("{\"This_Is\": \"JSON_Code\"}")),
+///     _id: String::from(""),
+///     language: String::from(pastemyst::data::language::JSON),
+///     title: String::from("This is a pasty title"),
+///     code: String::from("{\"This_Is\": \"JSON_Code\"}"),
 /// };
 /// ```
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case, dead_code)]
 pub struct PastyObject {
     /// Id of the pasty.
+    #[serde(default)]
     pub _id: String,
     /// Language of the pasty.
+    #[serde(default)]
     pub language: String,
     /// title of the pasty.
+    #[serde(default)]
     pub title: String,
     /// contents of the pasty.
     pub code: String,
 }
 
+/// Builds a `PastyObject` without spelling out every
+/// field: the id is left empty, as PasteMyst gives new
+/// pasties one, and the language is autodetected unless
+/// it's set or found from the name of a file.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::data::language;
+/// use pastemyst::paste::PastyBuilder;
+///
+/// let pasty = PastyBuilder::new().title("Notes").code("Hello").build();
+/// assert!(pasty._id.is_empty());
+/// assert_eq!(pasty.language, language::AUTODETECT);
+///
+/// let pasty = PastyBuilder::from_filename("src/main.rs").code("fn main() {}").build();
+/// assert_eq!(pasty.title, "main.rs");
+/// assert_eq!(pasty.language, language::RUST);
+/// ```
+#[derive(Clone)]
+pub struct PastyBuilder {
+    language: String,
+    title: String,
+    code: String,
+}
+
+impl Default for PastyBuilder {
+    fn default() -> Self {
+        PastyBuilder {
+            language: String::from(crate::data::language::AUTODETECT),
+            title: String::new(),
+            code: String::new(),
+        }
+    }
+}
+
+impl PastyBuilder {
+    /// Starts an empty, untitled pasty.
+    pub fn new() -> Self { PastyBuilder::default() }
+
+    /// Starts a pasty titled after the file name (without
+    /// its directories) with the language it implies, see
+    /// `data::language_for_filename`.
+    pub fn from_filename(filename: &str) -> Self { PastyBuilder::new().filename(filename) }
+
+    /// Sets the title of the pasty.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the contents of the pasty.
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = code.into();
+        self
+    }
+
+    /// Sets the language of the pasty, a `Language`
+    /// or one of the names in `data::language`.
+    pub fn language(mut self, language: impl Into<LanguageSpec>) -> Self {
+        self.language = language.into().into();
+        self
+    }
+
+    /// Titles the pasty after the file name and takes
+    /// its language from it, the language is left as it
+    /// was if the file isn't recognized.
+    pub fn filename(mut self, filename: &str) -> Self {
+        let path = std::path::Path::new(filename);
+        self.title = path.file_name().map_or_else(|| filename.to_string(), |name| name.to_string_lossy().into_owned());
+        if let Some(language) = crate::data::language_for_filename(filename) { self.language = language.to_string(); }
+        self
+    }
+
+    /// Turns it into a `PastyObject`.
+    pub fn build(self) -> PastyObject {
+        PastyObject { _id: String::new(), language: self.language, title: self.title, code: self.code }
+    }
+}
+
+impl From<PastyBuilder> for PastyObject {
+    fn from(builder: PastyBuilder) -> Self { builder.build() }
+}
+
+impl PastyObject {
+    /// The language of the pasty as a `Language`,
+    /// `None` if this crate doesn't know it.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::data::Language;
+    /// use pastemyst::paste::PastyBuilder;
+    ///
+    /// let mut pasty = PastyBuilder::new().language(Language::Rust).code("fn main() {}").build();
+    /// assert_eq!(pasty.known_language(), Some(Language::Rust));
+    ///
+    /// pasty.set_language("Carbon");
+    /// assert_eq!(pasty.language, "Carbon");
+    /// assert_eq!(pasty.known_language(), None);
+    /// ```
+    pub fn known_language(&self) -> Option<Language> { self.language.parse().ok() }
+
+    /// Sets the language of the pasty, a `Language`
+    /// or one of the names in `data::language`.
+    pub fn set_language(&mut self, language: impl Into<LanguageSpec>) { self.language = language.into().into(); }
+
+    /// A SHA-256 of the code of the pasty, as
+    /// lowercase hex. It's the same as the hash of
+    /// the file the pasty came from, e.g. the output
+    /// of `sha256sum`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::PastyBuilder;
+    ///
+    /// let pasty = PastyBuilder::new().code("Hello").build();
+    /// assert_eq!(pasty.content_hash(), "185f8db32271fe25f561a6fc938b2e264306ec304eda518007d1764826381969");
+    /// ```
+    pub fn content_hash(&self) -> String { hex(&Sha256::digest(self.code.as_bytes())) }
+
+    /// A file name for the pasty: its title with the
+    /// characters file systems don't allow replaced by
+    /// `_`, followed by the usual extension of its
+    /// language (see `data::extension_for_language`)
+    /// unless the title already has an extension.
+    /// Untitled pasties are named `pasty`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::data::language;
+    /// use pastemyst::paste::PastyBuilder;
+    ///
+    /// let pasty = PastyBuilder::new().title("Hello world").language(language::RUST).build();
+    /// assert_eq!(pasty.file_name(), "Hello world.rs");
+    ///
+    /// let pasty = PastyBuilder::new().title("a/b.txt").language(language::MARKDOWN).build();
+    /// assert_eq!(pasty.file_name(), "a_b.txt");
+    /// ```
+    pub fn file_name(&self) -> String {
+        let name: String = self
+            .title
+            .trim()
+            .chars()
+            .map(|c| if c.is_control() || "/\\:*?\"<>|".contains(c) { '_' } else { c })
+            .collect();
+        let name = match name.trim_matches('.') {
+            "" => String::from("pasty"),
+            _ => name,
+        };
+        match crate::data::extension_for_language(&self.language) {
+            Some(extension) if std::path::Path::new(&name).extension().is_none() => format!("{}.{}", name, extension),
+            _ => name,
+        }
+    }
+
+    /// The link to the pasty within the paste
+    /// `paste_id`, which scrolls to it when opened.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::PastyBuilder;
+    ///
+    /// let mut pasty = PastyBuilder::new().title("main.rs").build();
+    /// pasty._id = String::from("cys6ypb4");
+    /// assert_eq!(pasty.url("hipfqanx"), "https://paste.myst.rs/hipfqanx#cys6ypb4");
+    /// assert_eq!(pasty.raw_url("hipfqanx"), "https://paste.myst.rs/raw/hipfqanx/cys6ypb4");
+    /// assert_eq!(pasty.url_on("https://paste.example.com/", "hipfqanx"), "https://paste.example.com/hipfqanx#cys6ypb4");
+    /// ```
+    pub fn url(&self, paste_id: &str) -> String { self.url_on(BASE_URL, paste_id) }
+
+    /// The link to the pasty within the paste `paste_id`
+    /// on another instance of PasteMyst.
+    pub fn url_on(&self, base_url: &str, paste_id: &str) -> String {
+        format!("{}/{}#{}", base_url.trim_end_matches('/'), paste_id, self._id)
+    }
+
+    /// The link to the plain text of the pasty, which
+    /// is only served for pastes that aren't private.
+    pub fn raw_url(&self, paste_id: &str) -> String { self.raw_url_on(BASE_URL, paste_id) }
+
+    /// The link to the plain text of the pasty on
+    /// another instance of PasteMyst.
+    pub fn raw_url_on(&self, base_url: &str, paste_id: &str) -> String {
+        format!("{}/raw/{}/{}", base_url.trim_end_matches('/'), paste_id, self._id)
+    }
+}
+
 /// Infomation about edits in a pasty in a paste.
 ///
 /// ### API Docs
@@ -623,11 +3105,18 @@ pub struct PastyObject {
 ///
 /// ## Examples
 ///
-/// ```rust
-/// // Get paste from pastemyst
-/// let edits: EditHistory = paste.edits[0];
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     // Get paste from pastemyst
+///     let paste: PasteObject = get_paste("hipfqanx")?;
+///     let edits: &EditHistory = &paste.edits[0];
+///     println!("{}", edits.edit);
+///     Ok(())
+/// }
 /// ```
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case, dead_code)]
 pub struct EditHistory {
     /// Unique id of the edit.
@@ -635,23 +3124,119 @@ pub struct EditHistory {
     /// Id of the edit, multiple edits can
     /// share the same id showing that multiple
     /// fields were changed at the same time.
+    #[serde(default)]
     pub editId: String,
-    /// Type of edit, possible values are
-    /// title(0), pastyTitle(1), pastyLanguage(2),
-    /// pastyContent(3), pastyAdded(4), pastyRemoved(5).
-    pub editType: i32,
+    /// Type of edit, sent by PasteMyst as
+    /// a number, see `EditType`.
+    pub editType: EditType,
     /// Various metadata used internally,
     /// biggest usecase is storing exactly which
     /// pasty was edited.
+    #[serde(default)]
     pub metadata: Vec<String>,
     /// Actual paste edit, it stores old data
     /// before the edit as the current paste
     /// stores the new data
+    #[serde(default)]
     pub edit: String,
     /// Unix time of when the edit was made
+    #[serde(default)]
     pub editedAt: i32,
 }
 
+/// What an edit of a paste changed, read from the
+/// number PasteMyst sends as `editType`. Numbers
+/// this crate doesn't know are kept in `Unknown`
+/// so that newer kinds of edits can still be read.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::EditType;
+///
+/// assert_eq!(EditType::from(3), EditType::PastyContent);
+/// assert_eq!(EditType::from(42), EditType::Unknown(42));
+/// assert_eq!(i32::from(EditType::PastyAdded), 4);
+///
+/// let edit_type: EditType = serde_json::from_str("1").unwrap();
+/// assert_eq!(edit_type, EditType::PastyTitle);
+/// assert_eq!(serde_json::to_string(&edit_type).unwrap(), "1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditType {
+    /// The title of the paste changed.
+    Title,
+    /// The title of a pasty changed.
+    PastyTitle,
+    /// The language of a pasty changed.
+    PastyLanguage,
+    /// The code of a pasty changed.
+    PastyContent,
+    /// A pasty was added.
+    PastyAdded,
+    /// A pasty was removed.
+    PastyRemoved,
+    /// A kind of edit this crate doesn't
+    /// know, with the number that was sent.
+    Unknown(i32),
+}
+
+impl From<i32> for EditType {
+    fn from(edit_type: i32) -> Self {
+        match edit_type {
+            0 => EditType::Title,
+            1 => EditType::PastyTitle,
+            2 => EditType::PastyLanguage,
+            3 => EditType::PastyContent,
+            4 => EditType::PastyAdded,
+            5 => EditType::PastyRemoved,
+            other => EditType::Unknown(other),
+        }
+    }
+}
+
+impl From<EditType> for i32 {
+    fn from(edit_type: EditType) -> Self {
+        match edit_type {
+            EditType::Title => 0,
+            EditType::PastyTitle => 1,
+            EditType::PastyLanguage => 2,
+            EditType::PastyContent => 3,
+            EditType::PastyAdded => 4,
+            EditType::PastyRemoved => 5,
+            EditType::Unknown(other) => other,
+        }
+    }
+}
+
+impl Serialize for EditType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(i32::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for EditType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(EditType::from(i32::deserialize(deserializer)?))
+    }
+}
+
+/// A version of a paste, yielded by
+/// `PasteObject::history_iter`.
+#[derive(Clone)]
+pub struct PasteVersion {
+    /// The `editId` of the edits that made
+    /// this version, `None` for the paste
+    /// as it was created.
+    pub edit_id: Option<String>,
+    /// Unix time of when the edits were
+    /// made, `None` for the paste as it
+    /// was created.
+    pub edited_at: Option<i32>,
+    /// The paste as it was.
+    pub paste: PasteObject,
+}
+
 /// The structure object that holds
 /// the base to create a paste. This
 /// is then sent to pastemyst. All
@@ -665,36 +3250,118 @@ pub struct EditHistory {
 /// ## Examples
 ///
 /// ```rust
-/// let _data: CreateObject = CreateObject {
+/// use pastemyst::paste::*;
+///
+/// let pasties: Vec<PastyObject> = Vec::new();
+/// let data: CreateObject = CreateObject {
 ///     title: String::from("[crates.io/crates/pastemyst] This is a title"),
-///     expiresIn: String::from("1d"),
+///     expiresIn: pastemyst::time::ExpiresIn::OneDay,
 ///     isPrivate: false,
 ///     isPublic: false,
-///     tags: String::from(""),
-///     pasties: pasties,
+///     tags: vec![String::from("rust"), String::from(" help "), String::from("rust")],
+///     pasties,
 /// };
+/// let json = serde_json::to_value(&data).unwrap();
+/// assert_eq!(json["expiresIn"], "1d");
+/// assert_eq!(json["tags"], "rust,help");
 /// ```
-#[derive(Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[allow(non_snake_case, dead_code)]
 pub struct CreateObject {
     /// Title of the paste.
     pub title: String,
     /// When the paste will expire,
-    /// possible values are never, 1h,
-    /// 2h, 10h, 1d, 2d, 1w, 1m, 1y.
-    pub expiresIn: String,
+    /// sent as one of never, 1h, 2h,
+    /// 10h, 1d, 2d, 1w, 1m, 1y.
+    pub expiresIn: ExpiresIn,
     /// If it"s private it"s only
     /// accessible by the owner.
     pub isPrivate: bool,
     /// Is it displayed on the
     /// owner's public profile.
     pub isPublic: bool,
-    /// List of tags, comma separated.
-    pub tags: String,
+    /// List of tags, sent comma separated
+    /// with the blank and repeated ones left
+    /// out.
+    #[serde(with = "comma_separated")]
+    pub tags: Vec<String>,
     /// List of pasties.
     pub pasties: Vec<PastyObject>,
 }
 
+impl CreateObject {
+    /// Checks the paste the way PasteMyst would before it's
+    /// sent, so that a mistake gives a clear error instead
+    /// of a bare `400`. The create functions call it, it can
+    /// also be called on its own. The expiry is always valid
+    /// as it's an `ExpiresIn`.
+    ///
+    /// Returns `PasteMystError::InvalidPaste` saying what's
+    /// wrong if there are no pasties, a pasty has no code or
+    /// the paste is both private and public.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::*;
+    ///
+    /// let mut contents = CreateObject {
+    ///     title: String::from("Hello"),
+    ///     expiresIn: Default::default(),
+    ///     isPrivate: false,
+    ///     isPublic: false,
+    ///     tags: Vec::new(),
+    ///     pasties: vec![PastyBuilder::new().title("main.rs").code("fn main() {}").build()],
+    /// };
+    /// assert!(contents.validate().is_ok());
+    ///
+    /// contents.pasties.push(PastyBuilder::new().title("empty.rs").build());
+    /// assert_eq!(
+    ///     contents.validate().unwrap_err().to_string(),
+    ///     "[pastemyst] Invalid paste: the pasty 'empty.rs' has no code",
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), PasteMystError> {
+        if self.pasties.is_empty() {
+            return Err(PasteMystError::InvalidPaste(str!("a paste needs at least one pasty")));
+        }
+        if let Some((index, pasty)) = self.pasties.iter().enumerate().find(|(_, pasty)| pasty.code.trim().is_empty()) {
+            let reason = match pasty.title.trim() {
+                "" => format!("the pasty at index {} has no code", index),
+                title => format!("the pasty '{}' has no code", title),
+            };
+            return Err(PasteMystError::InvalidPaste(reason));
+        }
+        if self.isPrivate && self.isPublic {
+            return Err(PasteMystError::InvalidPaste(str!("a paste can't be both private and public")));
+        }
+        Ok(())
+    }
+
+    /// Validates the paste and returns the exact json
+    /// body the create functions would send, without
+    /// sending anything. Handy to check the pastes a
+    /// pipeline generates without creating them.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::*;
+    ///
+    /// let contents = PasteBuilder::new()
+    ///     .title("Build log")
+    ///     .pasty(PastyBuilder::new().title("build.log").code("ok"))
+    ///     .build()
+    ///     .unwrap();
+    /// let body = contents.to_request_json().unwrap();
+    /// assert!(body.starts_with(r#"{"title":"Build log","expiresIn":"never""#));
+    /// ```
+    pub fn to_request_json(&self) -> Result<String, PasteMystError> {
+        self.validate()?;
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
 /// The same as `CreateObject` except
 /// that it does not have the `expiresIn`
 /// field which has been removed for
@@ -710,7 +3377,7 @@ pub struct CreateObject {
 ///
 /// To edit a single pasty you will need to provide
 /// all of the original pasties changing the fields
-//// you want. it"s not possible to update a single
+/// you want. it"s not possible to update a single
 /// pasty without providing all of the pasties.
 ///
 /// ### API Docs
@@ -720,16 +3387,18 @@ pub struct CreateObject {
 /// ## Examples
 ///
 /// ```rust
-/// let _data: CreateObject = CreateObject {
+/// use pastemyst::paste::*;
+///
+/// let var_pasties: Vec<PastyObject> = Vec::new();
+/// let _data: EditObject = EditObject {
 ///     title: String::from("[crates.io/crates/pastemyst] This is a title"),
-///     expiresIn: String::from("1d"),
 ///     isPrivate: false,
 ///     isPublic: false,
-///     tags: String::from(""),
+///     tags: Vec::new(),
 ///     pasties: var_pasties,
 /// };
 /// ```
-#[derive(Serialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[allow(non_snake_case, dead_code)]
 pub struct EditObject {
     /// Title of the paste.
@@ -740,8 +3409,954 @@ pub struct EditObject {
     /// Is it displayed on the
     /// owner's public profile.
     pub isPublic: bool,
-    /// List of tags, comma separated.
-    pub tags: String,
+    /// List of tags, sent comma separated
+    /// with the blank and repeated ones left
+    /// out.
+    #[serde(with = "comma_separated")]
+    pub tags: Vec<String>,
     /// List of pasties.
     pub pasties: Vec<PastyObject>,
 }
+
+impl EditObject {
+    /// An edit that keeps the paste as it is, to
+    /// change only the fields you need before
+    /// sending it with `edit_paste`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+    ///     let paste = get_private_paste("pasteID", token)?;
+    ///     let edit = EditObject { title: String::from("New title"), ..EditObject::from_paste(&paste) };
+    ///     edit_paste(edit, &paste._id, token)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_paste(paste: &PasteObject) -> Self {
+        EditObject {
+            title: paste.title.clone(),
+            isPrivate: paste.isPrivate,
+            isPublic: paste.isPublic,
+            tags: paste.tags.clone(),
+            pasties: paste.pasties.clone(),
+        }
+    }
+
+    /// Checks that every pasty with an id belongs to the
+    /// given paste, as PasteMyst silently ignores pasties
+    /// it doesn't know. Pasties without an id are new
+    /// ones and are not checked.
+    ///
+    /// Returns `PasteMystError::UnknownPasty` with the
+    /// first id that does not belong to the paste.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let paste = get_paste("hipfqanx")?;
+    ///     let edit_object = EditObject {
+    ///         isPrivate: false,
+    ///         isPublic: false,
+    ///         pasties: vec![PastyObject {
+    ///             _id: String::from("NotAPastyOfThisPaste"),
+    ///             language: String::from(pastemyst::data::language::PLAIN),
+    ///             title: String::from("Pasty"),
+    ///             code: String::from("Hello"),
+    ///         }],
+    ///         tags: Vec::new(),
+    ///         title: paste.title.clone(),
+    ///     };
+    ///     assert!(edit_object.validate_against(&paste).is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn validate_against(&self, paste: &PasteObject) -> Result<(), PasteMystError> {
+        match self
+            .pasties
+            .iter()
+            .filter(|pasty| !pasty._id.is_empty())
+            .find(|pasty| !paste.pasties.iter().any(|existing| existing._id == pasty._id))
+        {
+            Some(pasty) => Err(PasteMystError::UnknownPasty(pasty._id.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<&PasteObject> for EditObject {
+    fn from(paste: &PasteObject) -> Self { EditObject::from_paste(paste) }
+}
+
+/// Builds a `CreateObject` one field at a time, the
+/// fields that aren't set keep the defaults of pastemyst
+/// (no title, never expires, unlisted and no tags).
+///
+/// `build` checks the paste before returning it, and
+/// `send`/`send_async` create it right away, on the
+/// account of the token if one was given.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::*;
+/// use pastemyst::time::ExpiresIn;
+///
+/// let contents = PasteBuilder::new()
+///     .title("[crates.io/crates/pastemyst] This is a title")
+///     .expires(ExpiresIn::OneDay)
+///     .tag("rust")
+///     .pasty(PastyBuilder::new().title("Pasty1").code("Code"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(contents.expiresIn, ExpiresIn::OneDay);
+/// assert_eq!(contents.tags, vec!["rust"]);
+///
+/// assert!(PasteBuilder::new().title("No pasties").build().is_err());
+/// ```
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = PasteBuilder::new()
+///         .title("Notes")
+///         .private(true)
+///         .token("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings")
+///         .pasty(PastyBuilder::from_filename("notes.md").code("# Notes"))
+///         .send()?;
+///     println!("{}", paste._id);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct PasteBuilder {
+    title: String,
+    expires_in: ExpiresIn,
+    private: bool,
+    public: bool,
+    tags: Tags,
+    pasties: Vec<PastyObject>,
+    token: Option<ApiToken>,
+}
+
+impl PasteBuilder {
+    /// Starts an empty paste.
+    pub fn new() -> Self { PasteBuilder::default() }
+
+    /// Sets the title of the paste.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets when the paste expires.
+    pub fn expires(mut self, expires_in: ExpiresIn) -> Self {
+        self.expires_in = expires_in;
+        self
+    }
+
+    /// Makes the paste only accessible
+    /// by its owner, needs a token.
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+
+    /// Lists the paste on the profile
+    /// of its owner, needs a token.
+    pub fn public(mut self, public: bool) -> Self {
+        self.public = public;
+        self
+    }
+
+    /// Adds a tag, empty and
+    /// repeated tags are ignored.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.insert(tag);
+        self
+    }
+
+    /// Adds several tags at once.
+    pub fn tags<S: AsRef<str>>(mut self, tags: impl IntoIterator<Item = S>) -> Self {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// Adds a pasty, pasties are kept
+    /// in the order they're added in.
+    pub fn pasty(mut self, pasty: impl Into<PastyObject>) -> Self {
+        self.pasties.push(pasty.into());
+        self
+    }
+
+    /// Sets the token of the account the
+    /// paste is created on by `send`.
+    pub fn token(mut self, token: impl Into<ApiToken>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Checks the paste and turns it into a
+    /// `CreateObject`, see `CreateObject::validate`.
+    pub fn build(self) -> Result<CreateObject, PasteMystError> { self.split().map(|(contents, _)| contents) }
+
+    /// Builds the paste and creates it, privately
+    /// if a token was given. This is a synchronous
+    /// method.
+    #[cfg(all(feature = "paste", feature = "blocking"))]
+    pub fn send(self) -> Result<PasteObject, PasteMystError> {
+        match self.split()? {
+            (contents, Some(token)) => create_private_paste(contents, token),
+            (contents, None) => create_paste(contents),
+        }
+    }
+
+    /// Builds the paste and creates it, privately
+    /// if a token was given. This is an asynchronous
+    /// method.
+    #[cfg(all(feature = "paste", feature = "async"))]
+    pub async fn send_async(self) -> Result<PasteObject, PasteMystError> {
+        match self.split()? {
+            (contents, Some(token)) => create_private_paste_async(contents, token).await,
+            (contents, None) => create_paste_async(contents).await,
+        }
+    }
+
+    /// Builds the paste and hands
+    /// back the token along with it.
+    fn split(self) -> Result<(CreateObject, Option<ApiToken>), PasteMystError> {
+        let contents = CreateObject {
+            title: self.title,
+            expiresIn: self.expires_in,
+            isPrivate: self.private,
+            isPublic: self.public,
+            tags: self.tags.into_vec(),
+            pasties: self.pasties,
+        };
+        contents.validate()?;
+        Ok((contents, self.token))
+    }
+}
+
+/// The options used by `merge` and `merge_async`
+/// to combine several pastes into a new one.
+///
+/// By default the pasties are kept as separate files,
+/// the titles of the pastes are joined with `" + "`,
+/// their tags are merged, the new paste never expires
+/// and the source pastes are kept.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::MergeOptions;
+///
+/// let options = MergeOptions {
+///     concatenate: true,
+///     merge_tags: false,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct MergeOptions {
+    /// Whether all the pasties are concatenated
+    /// into a single one instead of being kept
+    /// as separate files.
+    pub concatenate: bool,
+    /// The title of the new paste, if `None`
+    /// the titles of the pastes are joined.
+    pub title: Option<String>,
+    /// Whether the tags of all the pastes are
+    /// kept, if `false` the new paste has none.
+    pub merge_tags: bool,
+    /// When the new paste will expire.
+    pub expires_in: ExpiresIn,
+    /// Whether the source pastes are deleted
+    /// once the new paste is created.
+    pub delete_sources: bool,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        MergeOptions {
+            concatenate: false,
+            title: None,
+            merge_tags: true,
+            expires_in: ExpiresIn::Never,
+            delete_sources: false,
+        }
+    }
+}
+
+/// How `split` and `split_async`
+/// divide a paste into new pastes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitBy {
+    /// One new paste per pasty.
+    Pasty,
+    /// One new paste per chunk of
+    /// the given amount of lines,
+    /// for each pasty.
+    LineCount(usize),
+}
+
+/// The options used by `recreate_with_expiry`
+/// and `recreate_with_expiry_async`. By default
+/// the original paste is kept as is.
+#[derive(Debug, Clone, Default)]
+pub struct RecreateOptions {
+    /// Whether the original paste is deleted
+    /// once the new one is created.
+    pub delete_original: bool,
+    /// Whether a pasty pointing to the new
+    /// paste is added to the original one,
+    /// ignored if it is deleted.
+    pub redirect_note: bool,
+}
+
+/// The options used by `fork_paste` and
+/// `fork_paste_async`, every field left to
+/// `None` is taken from the original paste.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::ForkOptions;
+///
+/// let options = ForkOptions { public: Some(true), ..Default::default() };
+/// assert!(options.title.is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ForkOptions {
+    /// The title of the new paste.
+    pub title: Option<String>,
+    /// When the new paste will expire.
+    pub expires_in: Option<ExpiresIn>,
+    /// Whether the new paste is private.
+    pub private: Option<bool>,
+    /// Whether the new paste is shown
+    /// on your public profile.
+    pub public: Option<bool>,
+}
+
+/// A paste that was re-created by
+/// `recreate_with_expiry`.
+pub struct RecreatedPaste {
+    /// The id of the original paste.
+    pub original_id: String,
+    /// The newly created paste.
+    pub paste: PasteObject,
+}
+
+/// A change in the star count of
+/// a paste, yielded by `watch_stars`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StarChange {
+    /// The star count before the change.
+    pub previous: u64,
+    /// The star count after the change.
+    pub current: u64,
+}
+
+impl StarChange {
+    /// The amount of stars gained,
+    /// negative if stars were lost.
+    pub fn delta(&self) -> i64 { self.current as i64 - self.previous as i64 }
+}
+
+/// The outcome of deleting a paste with
+/// `delete_paste` or `delete_paste_async`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::DeleteOutcome;
+///
+/// let outcome = DeleteOutcome::NotFound;
+/// assert!(!outcome.is_deleted());
+/// assert!(outcome.into_result().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    /// The paste has been deleted.
+    Deleted,
+    /// The paste does not exist,
+    /// it may have expired already.
+    NotFound,
+    /// The token is invalid or the paste
+    /// is not owned by its account.
+    Unauthorized,
+    /// Any other error sent by the server.
+    Other { status: u16, message: String },
+}
+
+impl DeleteOutcome {
+    /// Whether the paste has been deleted.
+    pub fn is_deleted(&self) -> bool { *self == DeleteOutcome::Deleted }
+
+    /// Turns any outcome but `Deleted` into a
+    /// `PasteMystError::Api`, handy when the
+    /// paste must be deleted to carry on.
+    pub fn into_result(self) -> Result<(), PasteMystError> {
+        let (status, message) = match self {
+            DeleteOutcome::Deleted => return Ok(()),
+            DeleteOutcome::NotFound => (404, str!("The paste does not exist")),
+            DeleteOutcome::Unauthorized => (401, str!("Unauthorized")),
+            DeleteOutcome::Other { status, message } => (status, message),
+        };
+        Err(PasteMystError::Api { status, message })
+    }
+
+    /// Creates the outcome from the result of checking
+    /// the status of a response, errors which aren't
+    /// sent by the server and rate limiting are
+    /// passed through.
+    #[cfg(all(feature = "paste", feature = "network"))]
+    pub(crate) fn from_status(result: Result<(), PasteMystError>) -> Result<Self, PasteMystError> {
+        match result {
+            Ok(()) => Ok(DeleteOutcome::Deleted),
+            Err(PasteMystError::Api { status: 404, .. }) => Ok(DeleteOutcome::NotFound),
+            Err(PasteMystError::Api { status: 401, .. })
+            | Err(PasteMystError::Api { status: 403, .. }) => Ok(DeleteOutcome::Unauthorized),
+            Err(PasteMystError::Api { status, message }) => Ok(DeleteOutcome::Other { status, message }),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// What happened to each paste given to `delete_pastes`
+/// or `delete_pastes_async`, the ids are in the order
+/// they were given in.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::DeleteReport;
+///
+/// let report = DeleteReport {
+///     deleted: vec![String::from("hipfqanx")],
+///     not_found: vec![String::from("cys6ypb4")],
+///     failed: Vec::new(),
+/// };
+/// assert!(report.is_success());
+/// assert!(report.into_result().is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct DeleteReport {
+    /// The pastes that have been deleted.
+    pub deleted: Vec<String>,
+    /// The pastes that didn't exist,
+    /// they may have expired already.
+    pub not_found: Vec<String>,
+    /// The pastes that could not be deleted,
+    /// along with the reason why.
+    pub failed: Vec<(String, PasteMystError)>,
+}
+
+impl DeleteReport {
+    /// Whether every paste is gone, either
+    /// deleted now or not found.
+    pub fn is_success(&self) -> bool { self.failed.is_empty() }
+
+    /// Turns the report into a
+    /// `PasteMystError::DeleteFailed` holding
+    /// the failures if there are any.
+    pub fn into_result(self) -> Result<(), PasteMystError> {
+        match self.failed.is_empty() {
+            true => Ok(()),
+            false => Err(PasteMystError::DeleteFailed(self.failed)),
+        }
+    }
+
+    /// Files the outcome of deleting a paste
+    /// under the list it belongs to.
+    #[cfg(all(feature = "paste", feature = "network"))]
+    fn record(&mut self, id: &str, outcome: Result<DeleteOutcome, PasteMystError>) {
+        match outcome {
+            Ok(DeleteOutcome::Deleted) => self.deleted.push(id.to_string()),
+            Ok(DeleteOutcome::NotFound) => self.not_found.push(id.to_string()),
+            Ok(outcome) => self.failed.push((id.to_string(), outcome.into_result().unwrap_err())),
+            Err(error) => self.failed.push((id.to_string(), error)),
+        }
+    }
+}
+
+/// The languages a paste is made of, returned by
+/// `PasteObject::composition`. Displaying it lists
+/// every language with its share, e.g.
+/// `Rust 75.0%, Python 25.0%`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::*;
+///
+/// let composition = PasteComposition {
+///     languages: vec![
+///         LanguageShare { language: String::from("Rust"), pasties: 2, bytes: 300, percentage: 75.0 },
+///         LanguageShare { language: String::from("Python"), pasties: 1, bytes: 100, percentage: 25.0 },
+///     ],
+/// };
+/// assert_eq!(composition.to_string(), "Rust 75.0%, Python 25.0%");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasteComposition {
+    /// One entry per language,
+    /// largest share first.
+    pub languages: Vec<LanguageShare>,
+}
+
+/// The share of a single language in a paste.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageShare {
+    /// The language of the pasties.
+    pub language: String,
+    /// How many pasties are
+    /// written in it.
+    pub pasties: usize,
+    /// The size of their code in bytes.
+    pub bytes: usize,
+    /// Their share of the paste's bytes,
+    /// from `0` to `100`. When every pasty
+    /// is empty, the share of pasties is
+    /// used instead.
+    pub percentage: f64,
+}
+
+impl std::fmt::Display for PasteComposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, share) in self.languages.iter().enumerate() {
+            if index > 0 { write!(f, ", ")?; }
+            write!(f, "{} {:.1}%", share.language, share.percentage)?;
+        }
+        Ok(())
+    }
+}
+
+/// A compact overview of a paste: the shape bot embeds,
+/// list rows and notifications need. Times are turned
+/// into text relative to when the summary was made, the
+/// size is the number of bytes of code of all pasties.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::*;
+///
+/// let paste = PasteObject {
+///     _id: String::from("hipfqanx"),
+///     ownerId: String::from(""),
+///     title: String::from("Hello"),
+///     createdAt: 1612534264,
+///     expiresIn: String::from(pastemyst::time::expires_in::NEVER),
+///     deletesAt: 0,
+///     stars: 0,
+///     isPrivate: false,
+///     isPublic: false,
+///     tags: vec![String::from("rust")],
+///     pasties: vec![PastyObject {
+///         _id: String::from("x"),
+///         language: String::from(pastemyst::data::language::RUST),
+///         title: String::from("main.rs"),
+///         code: String::from("\nfn main() {\n    println!(\"hi\");\n}"),
+///     }],
+///     edits: Vec::new(),
+/// };
+/// let summary = PasteSummary::at(&paste, 1612534264 + 3 * 60 * 60);
+/// assert_eq!(summary.owner, None);
+/// assert_eq!(summary.created, "3 hours ago");
+/// assert_eq!(summary.expiry, "never");
+/// assert_eq!(summary.pasties[0].first_line, "fn main() {");
+/// assert_eq!(summary.size, 34);
+/// assert_eq!(PasteSummary::from(&paste).expiry, "never");
+/// println!("{}", summary);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteSummary {
+    /// Id of the paste.
+    pub id: String,
+    /// Title of the paste.
+    pub title: String,
+    /// Id of the owner, `None` if
+    /// the paste is anonymous.
+    pub owner: Option<String>,
+    /// How long ago the paste was
+    /// created, e.g. `3 hours ago`.
+    pub created: String,
+    /// When the paste expires, e.g.
+    /// `in 2 days` or `never`.
+    pub expiry: String,
+    /// List of tags.
+    pub tags: Vec<String>,
+    /// One entry per pasty, in the
+    /// order of the paste.
+    pub pasties: Vec<PastySummary>,
+    /// The size of the code of all
+    /// pasties in bytes.
+    pub size: usize,
+}
+
+/// A single pasty of a `PasteSummary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastySummary {
+    /// Title of the pasty.
+    pub title: String,
+    /// Language of the pasty.
+    pub language: String,
+    /// The first line of code that isn't
+    /// blank, trimmed and cut short if
+    /// it's too long for a preview.
+    pub first_line: String,
+}
+
+/// How many characters of the first
+/// line a pasty summary keeps.
+const PREVIEW_LENGTH: usize = 80;
+
+impl PasteSummary {
+    /// Summarizes a paste as of `now`, in seconds
+    /// since the unix epoch, the same unit as
+    /// `PasteObject::createdAt`. `From` does the
+    /// same with the current time, but it isn't
+    /// available on wasm where there is no clock.
+    pub fn at(paste: &PasteObject, now: u64) -> Self {
+        let expiry = match paste.deletesAt {
+            0 => String::from("never"),
+            deletes_at if deletes_at <= now => String::from("expired"),
+            deletes_at => format!("in {}", humanize_seconds(deletes_at - now)),
+        };
+        PasteSummary {
+            id: paste._id.clone(),
+            title: paste.title.clone(),
+            owner: Some(paste.ownerId.clone()).filter(|owner| !owner.is_empty()),
+            created: format!("{} ago", humanize_seconds(now.saturating_sub(paste.createdAt))),
+            expiry,
+            tags: paste.tags.clone(),
+            pasties: paste
+                .pasties
+                .iter()
+                .map(|pasty| PastySummary {
+                    title: pasty.title.clone(),
+                    language: pasty.language.clone(),
+                    first_line: preview_line(&pasty.code),
+                })
+                .collect(),
+            size: paste.pasties.iter().map(|pasty| pasty.code.len()).sum(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<&PasteObject> for PasteSummary {
+    fn from(paste: &PasteObject) -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        PasteSummary::at(paste, now)
+    }
+}
+
+impl std::fmt::Display for PasteSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = if self.title.is_empty() { "untitled" } else { &self.title };
+        writeln!(f, "{} ({})", title, self.id)?;
+        write!(f, "by {}", self.owner.as_deref().unwrap_or("anonymous"))?;
+        write!(f, " | created {} | expires {} | {}", self.created, self.expiry, humanize_bytes(self.size))?;
+        if !self.tags.is_empty() { write!(f, "\ntags: {}", self.tags.join(", "))?; }
+        for pasty in &self.pasties {
+            let title = if pasty.title.is_empty() { "untitled" } else { &pasty.title };
+            write!(f, "\n- {} [{}]: {}", title, pasty.language, pasty.first_line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The first line of code that isn't blank,
+/// cut at `PREVIEW_LENGTH` characters.
+fn preview_line(code: &str) -> String {
+    let line = code.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default();
+    match line.char_indices().nth(PREVIEW_LENGTH) {
+        Some((index, _)) => format!("{}…", &line[..index]),
+        None => line.to_string(),
+    }
+}
+
+/// Turns an amount of seconds into the largest
+/// whole unit, e.g. `3 hours` or `1 day`.
+fn humanize_seconds(seconds: u64) -> String {
+    const UNITS: [(u64, &str); 5] = [
+        (365 * 24 * 60 * 60, "year"),
+        (30 * 24 * 60 * 60, "month"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+    let (amount, unit) = UNITS
+        .iter()
+        .find(|(length, _)| seconds >= *length)
+        .map_or((seconds, "second"), |(length, unit)| (seconds / length, *unit));
+    format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" })
+}
+
+/// How many unchanged lines a hunk
+/// of `unified_diff` shows around
+/// the changed ones.
+const DIFF_CONTEXT: usize = 3;
+
+/// How long `unified_diff` looks for the
+/// smallest diff before settling for a
+/// larger one.
+#[cfg(not(target_arch = "wasm32"))]
+const DIFF_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// A line of a diff between two texts.
+#[derive(Clone, Copy, PartialEq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A unified diff of two texts, line by line, with
+/// `name` as the file in both headers. Identical
+/// texts give an empty string.
+fn unified_diff(old: &str, new: &str, name: &str) -> String {
+    let lines = diff_lines(&old.lines().collect::<Vec<_>>(), &new.lines().collect::<Vec<_>>());
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() { return String::new(); }
+
+    // The line numbers in the old and new
+    // text each diff line starts at.
+    let mut positions: Vec<(usize, usize)> = Vec::with_capacity(lines.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            DiffLine::Same(_) => { old_line += 1; new_line += 1; }
+            DiffLine::Removed(_) => old_line += 1,
+            DiffLine::Added(_) => new_line += 1,
+        }
+    }
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for change in changes {
+        let start = change.saturating_sub(DIFF_CONTEXT);
+        let end = (change + 1 + DIFF_CONTEXT).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", name, name);
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        let old_count = hunk.iter().filter(|line| !matches!(line, DiffLine::Added(_))).count();
+        let new_count = hunk.iter().filter(|line| !matches!(line, DiffLine::Removed(_))).count();
+        let (old_start, new_start) = positions[start];
+        // An empty side starts at the line before it.
+        let old_start = if old_count == 0 { old_start } else { old_start + 1 };
+        let new_start = if new_count == 0 { new_start } else { new_start + 1 };
+        diff.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_count, new_start, new_count));
+        for line in hunk {
+            let (prefix, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            diff.push(prefix);
+            diff.push_str(text);
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Diffs two lists of lines with Myers' algorithm in
+/// linear space, removed lines come before the lines
+/// added in their place. A diff taking longer than
+/// `DIFF_TIMEOUT` is finished with a coarser, still
+/// correct, one so that huge pasties can't hang.
+/// There's no clock on wasm, so the diff has no
+/// deadline there.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let mut config = similar::TextDiff::configure();
+    config.algorithm(similar::Algorithm::Myers);
+    #[cfg(not(target_arch = "wasm32"))]
+    config.timeout(DIFF_TIMEOUT);
+    let diff = config.diff_slices(old, new);
+    diff.ops()
+        .iter()
+        .flat_map(|op| op.iter_changes(old, new))
+        .map(|change| match change.tag() {
+            similar::ChangeTag::Equal => DiffLine::Same(change.value()),
+            similar::ChangeTag::Delete => DiffLine::Removed(change.value()),
+            similar::ChangeTag::Insert => DiffLine::Added(change.value()),
+        })
+        .collect()
+}
+
+/// Writes the bytes as lowercase hex.
+fn hex(bytes: &[u8]) -> String { bytes.iter().map(|byte| format!("{:02x}", byte)).collect() }
+
+/// Turns a size in bytes into
+/// text, e.g. `1.5 KiB`.
+fn humanize_bytes(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+/// A set of paste tags which keeps the order they
+/// were added in. Tags are trimmed, empty ones are
+/// ignored and a tag is only ever stored once.
+///
+/// PasteMyst sends tags as a list but expects them
+/// as a comma separated string when creating or
+/// editing a paste, `Tags` handles both: it can be
+/// built from the list of a `PasteObject` or parsed
+/// from a string, and its `Display` implementation
+/// produces the comma separated form.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::Tags;
+///
+/// let mut tags: Tags = "rust, help,rust".parse().unwrap();
+/// tags.insert("beginner");
+/// tags.insert("async,tokio");
+/// tags.remove("help");
+/// assert_eq!(tags.to_string(), "rust,beginner,async,tokio");
+/// assert_eq!(tags.len(), 4);
+/// assert!(tags.contains("rust"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags(Vec<String>);
+
+impl Tags {
+    /// Creates an empty set of tags.
+    pub fn new() -> Self { Tags(Vec::new()) }
+
+    /// Adds a tag, returns `false` if it was
+    /// empty or already present. PasteMyst splits
+    /// tags on commas, so a tag that contains one
+    /// is added as separate tags, e.g. `"a,b"` adds
+    /// `a` and `b`, and `true` is returned if any
+    /// of them was new.
+    pub fn insert(&mut self, tag: &str) -> bool {
+        let mut inserted = false;
+        for tag in tag.split(',').map(str::trim) {
+            if tag.is_empty() || self.contains(tag) { continue; }
+            self.0.push(tag.to_string());
+            inserted = true;
+        }
+        inserted
+    }
+
+    /// Removes a tag, returns `false`
+    /// if it was not present.
+    pub fn remove(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        let length = self.0.len();
+        self.0.retain(|existing| existing != tag);
+        self.0.len() != length
+    }
+
+    /// Whether the tag is present.
+    pub fn contains(&self, tag: &str) -> bool { self.0.iter().any(|existing| existing == tag.trim()) }
+
+    /// The amount of tags.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Whether there are no tags.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Iterates over the tags in the
+    /// order they were added in.
+    pub fn iter(&self) -> std::slice::Iter<'_, String> { self.0.iter() }
+
+    /// Turns the tags into a plain list,
+    /// the same as `PasteObject::tags`.
+    pub fn into_vec(self) -> Vec<String> { self.0 }
+}
+
+impl std::fmt::Display for Tags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(","))
+    }
+}
+
+impl std::str::FromStr for Tags {
+    type Err = std::convert::Infallible;
+
+    fn from_str(tags: &str) -> Result<Self, Self::Err> {
+        let mut result = Tags::new();
+        result.extend(tags.split(','));
+        Ok(result)
+    }
+}
+
+impl<S: AsRef<str>> From<&[S]> for Tags {
+    fn from(tags: &[S]) -> Self {
+        let mut result = Tags::new();
+        result.extend(tags);
+        result
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for Tags {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, tags: I) {
+        for tag in tags { self.insert(tag.as_ref()); }
+    }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter { self.0.iter() }
+}
+
+/// Sends a list of tags the way PasteMyst expects them
+/// when creating or editing a paste, as a single comma
+/// separated string, trimmed and without repeats. Both
+/// that string and a plain list are accepted back, so
+/// that `CreateObject`s saved either way can be read.
+mod comma_separated {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Tags;
+
+    pub fn serialize<S: Serializer>(tags: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Tags::from(tags).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Either {
+            Joined(String),
+            List(Vec<String>),
+        }
+        let tags = match Either::deserialize(deserializer)? {
+            Either::Joined(tags) => tags.parse::<Tags>().unwrap_or_default(),
+            Either::List(tags) => Tags::from(&tags[..]),
+        };
+        Ok(tags.into_vec())
+    }
+}
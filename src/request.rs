@@ -0,0 +1,51 @@
+#[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+use crate::client::{ClientResult, PasteMystClient};
+use crate::paste::{CreateObject, DeleteOutcome, EditObject, PasteObject};
+
+/// A paste operation that can be handed to a
+/// `PasteMystClient` as a value, through its
+/// `tower::Service` implementation or a
+/// `PasteMystWorker`. Each variant maps to
+/// the client method of the same name.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::request::PasteRequest;
+///
+/// let request = PasteRequest::Get { id: String::from("hipfqanx") };
+/// assert!(matches!(request, PasteRequest::Get { .. }));
+/// ```
+#[derive(Clone)]
+pub enum PasteRequest {
+    /// Gets a paste by its id.
+    Get { id: String },
+    /// Creates a new paste.
+    Create(CreateObject),
+    /// Edits the paste with the given id.
+    Edit { id: String, edit: EditObject },
+    /// Deletes the paste with the given id.
+    Delete { id: String },
+}
+
+/// What a `PasteRequest` resulted in.
+pub enum PasteResponse {
+    /// The paste that was fetched,
+    /// created or edited.
+    Paste(PasteObject),
+    /// The outcome of deleting a paste.
+    Deleted(DeleteOutcome),
+}
+
+#[cfg(all(feature = "paste", feature = "async", not(target_arch = "wasm32")))]
+impl PasteRequest {
+    /// Sends the request once through the client.
+    pub(crate) async fn send(&self, client: &PasteMystClient) -> ClientResult<PasteResponse> {
+        match self {
+            PasteRequest::Get { id } => Ok(PasteResponse::Paste(client.get_paste(id).await?)),
+            PasteRequest::Create(contents) => Ok(PasteResponse::Paste(client.create_paste(contents).await?)),
+            PasteRequest::Edit { id, edit } => Ok(PasteResponse::Paste(client.edit_paste(edit, id).await?)),
+            PasteRequest::Delete { id } => Ok(PasteResponse::Deleted(client.delete_paste(id).await?)),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::paste::PasteObject;
+
+/// The criteria used to search through the pastes
+/// of an account with `PasteMystClient::search_self_pastes`.
+/// PasteMyst has no server side search, so the pastes
+/// are fetched and filtered on your end.
+///
+/// Every criterion that is set must match for a paste
+/// to be returned, an empty query matches every paste.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::search::Query;
+///
+/// let query = Query {
+///     title_contains: Some(String::from("bug")),
+///     tags: vec![String::from("rust")],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    /// Text the paste title must contain,
+    /// the comparison ignores case.
+    pub title_contains: Option<String>,
+    /// Tags the paste must all have.
+    pub tags: Vec<String>,
+    /// A language at least one of the
+    /// pasties must be written in, the
+    /// comparison ignores case. It's
+    /// recommended to use the values
+    /// from `pastemyst::data::language`.
+    pub language: Option<String>,
+    /// Unix time the paste must
+    /// have been created after.
+    pub created_after: Option<u64>,
+}
+
+impl Query {
+    /// Whether the given paste
+    /// matches every criterion.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::paste::*;
+    /// use pastemyst::search::Query;
+    ///
+    /// fn main() -> PasteResult<()> {
+    ///     let paste = get_paste("hipfqanx")?;
+    ///     let query = Query { language: Some(String::from("Rust")), ..Default::default() };
+    ///     println!("{}", query.matches(&paste));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn matches(&self, paste: &PasteObject) -> bool {
+        if let Some(title) = &self.title_contains {
+            if !paste.title.to_lowercase().contains(&title.to_lowercase()) { return false; }
+        }
+        if !self.tags.iter().all(|tag| paste.tags.iter().any(|existing| existing == tag)) {
+            return false;
+        }
+        if let Some(language) = &self.language {
+            if !paste.pasties.iter().any(|pasty| pasty.language.eq_ignore_ascii_case(language)) {
+                return false;
+            }
+        }
+        if let Some(created_after) = self.created_after {
+            if paste.createdAt <= created_after { return false; }
+        }
+        true
+    }
+}
+
+/// What pastes are sorted by when listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    /// When the paste was created.
+    Created,
+    /// When the paste was last edited, pastes
+    /// that were never edited use the time
+    /// they were created at.
+    Edited,
+    /// The number of stars of the paste.
+    Stars,
+    /// The title of the paste, ignoring case.
+    Title,
+}
+
+/// The direction pastes are sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest (or oldest) first.
+    Ascending,
+    /// Largest (or newest) first.
+    Descending,
+}
+
+/// Options for sorting, filtering and paging through
+/// listed pastes, so that you don't have to sort the
+/// fetched pastes yourself. They're honored by
+/// `PasteMystClient::get_self_pastes` and
+/// `PasteMystClient::search_self_pastes_with`.
+///
+/// Sorting needs every paste, so the ids listed by
+/// `list_self_pastes` and the streams of `user` come
+/// in the order PasteMyst sends them in. Collect the
+/// pastes and use `apply` to sort those.
+///
+/// The default lists every paste, private ones
+/// included, newest first.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::search::*;
+///
+/// let options = ListOptions {
+///     sort_by: SortBy::Stars,
+///     limit: Some(10),
+///     include_private: false,
+///     ..Default::default()
+/// };
+/// assert_eq!(options.order, SortOrder::Descending);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    /// What the pastes are sorted by.
+    pub sort_by: SortBy,
+    /// The direction they are sorted in.
+    pub order: SortOrder,
+    /// How many pastes are skipped
+    /// after sorting.
+    pub offset: usize,
+    /// The maximum amount of pastes
+    /// returned, `None` for all.
+    pub limit: Option<usize>,
+    /// Whether private pastes are listed.
+    pub include_private: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions {
+            sort_by: SortBy::Created,
+            order: SortOrder::Descending,
+            offset: 0,
+            limit: None,
+            include_private: true,
+        }
+    }
+}
+
+impl ListOptions {
+    /// Filters, sorts and pages through the
+    /// given pastes according to the options.
+    pub fn apply(&self, mut pastes: Vec<PasteObject>) -> Vec<PasteObject> {
+        if !self.include_private { pastes.retain(|paste| !paste.isPrivate); }
+        match self.sort_by {
+            SortBy::Created => pastes.sort_by_key(|paste| paste.createdAt),
+            SortBy::Edited => pastes.sort_by_key(last_edited),
+            SortBy::Stars => pastes.sort_by_key(|paste| paste.stars),
+            SortBy::Title => pastes.sort_by_key(|paste| paste.title.to_lowercase()),
+        }
+        if self.order == SortOrder::Descending { pastes.reverse(); }
+        pastes
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// The unix time a paste was last edited at,
+/// or created at if it was never edited.
+fn last_edited(paste: &PasteObject) -> u64 {
+    paste
+        .edits
+        .iter()
+        .map(|edit| edit.editedAt.max(0) as u64)
+        .max()
+        .unwrap_or(paste.createdAt)
+}
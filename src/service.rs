@@ -0,0 +1,47 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::client::PasteMystClient;
+use crate::error::PasteMystError;
+
+pub use crate::request::{PasteRequest, PasteResponse};
+
+/// Exposes the async operations of the client as a
+/// `tower::Service`, so that standard tower middleware
+/// (rate limiting, buffering, timeouts, load shedding)
+/// can be layered around calls to pastemyst. The client
+/// is always ready, any back pressure comes from the
+/// layers wrapped around it.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::client::*;
+/// use pastemyst::service::*;
+/// use tower_service::Service;
+///
+/// #[tokio::main]
+/// async fn main() -> ClientResult<()> {
+///     let mut client = PasteMystClient::new();
+///     let response = client.call(PasteRequest::Get { id: String::from("hipfqanx") }).await?;
+///     if let PasteResponse::Paste(paste) = response {
+///         println!("{}", paste.title);
+///     }
+///     Ok(())
+/// }
+/// ```
+impl tower_service::Service<PasteRequest> for PasteMystClient {
+    type Response = PasteResponse;
+    type Error = PasteMystError;
+    type Future = Pin<Box<dyn Future<Output = Result<PasteResponse, PasteMystError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PasteRequest) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { request.send(&client).await })
+    }
+}
@@ -0,0 +1,198 @@
+use crate::data::language;
+use crate::paste::{PasteObject, PastyObject};
+
+/// The lines of a pasty (or a whole
+/// paste) split by what they contain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineCounts {
+    /// Lines with code on them, including
+    /// code followed by a comment.
+    pub code: usize,
+    /// Lines that only hold a comment.
+    pub comment: usize,
+    /// Empty or whitespace only lines.
+    pub blank: usize,
+}
+
+impl LineCounts {
+    /// Every line, whatever it contains.
+    pub fn lines(&self) -> usize { self.code + self.comment + self.blank }
+}
+
+impl std::ops::AddAssign for LineCounts {
+    fn add_assign(&mut self, other: LineCounts) {
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+/// The line counts of a single pasty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastyLoc {
+    /// Id of the pasty.
+    pub id: String,
+    /// Title of the pasty.
+    pub title: String,
+    /// Language of the pasty.
+    pub language: String,
+    /// What its lines contain.
+    pub lines: LineCounts,
+}
+
+/// The line counts of every pasty in a
+/// paste, returned by `loc_stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocStats {
+    /// One entry per pasty, in the
+    /// order of the paste.
+    pub pasties: Vec<PastyLoc>,
+}
+
+impl LocStats {
+    /// The line counts of all the
+    /// pasties added together.
+    pub fn total(&self) -> LineCounts {
+        let mut total = LineCounts::default();
+        for pasty in &self.pasties { total += pasty.lines; }
+        total
+    }
+}
+
+/// Counts the code, comment and blank lines of every
+/// pasty in a paste, so that "how big is this paste?"
+/// can be answered with more than its size in bytes.
+///
+/// Comments are recognized with simple rules based on
+/// the language of each pasty (`//` and `/* */` for
+/// C-like languages, `#` for scripting languages and so
+/// on). Comment markers inside strings aren't told apart
+/// and languages without known rules count every non
+/// blank line as code.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::stats;
+///
+/// fn main() -> PasteResult<()> {
+///     let paste = get_paste("hipfqanx")?;
+///     let total = stats::loc_stats(&paste).total();
+///     println!("{} lines of code, {} of comments", total.code, total.comment);
+///     Ok(())
+/// }
+/// ```
+pub fn loc_stats(paste: &PasteObject) -> LocStats {
+    LocStats {
+        pasties: paste
+            .pasties
+            .iter()
+            .map(|pasty| PastyLoc {
+                id: pasty._id.clone(),
+                title: pasty.title.clone(),
+                language: pasty.language.clone(),
+                lines: pasty_loc(pasty),
+            })
+            .collect(),
+    }
+}
+
+/// Counts the code, comment and blank
+/// lines of a single pasty, see `loc_stats`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::PastyObject;
+/// use pastemyst::stats;
+///
+/// let pasty = PastyObject {
+///     _id: String::from(""),
+///     language: String::from(pastemyst::data::language::RUST),
+///     title: String::from("main.rs"),
+///     code: String::from("// Entry point\nfn main() {\n\n    /* say\n       hi */\n    println!(\"hi\"); // greet\n}"),
+/// };
+/// let lines = stats::pasty_loc(&pasty);
+/// assert_eq!((lines.code, lines.comment, lines.blank), (3, 3, 1));
+/// ```
+pub fn pasty_loc(pasty: &PastyObject) -> LineCounts {
+    let syntax = comment_syntax(&pasty.language);
+    let mut counts = LineCounts::default();
+    let mut in_block: Option<&str> = None;
+    for line in pasty.code.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            counts.blank += 1;
+            continue;
+        }
+        if let Some(end) = in_block {
+            counts.comment += 1;
+            if line.contains(end) { in_block = None; }
+            continue;
+        }
+        if let Some((start, end)) = syntax.block {
+            if let Some(rest) = line.strip_prefix(start) {
+                counts.comment += 1;
+                if !rest.contains(end) { in_block = Some(end); }
+                continue;
+            }
+        }
+        if syntax.line.iter().any(|prefix| line.starts_with(prefix)) {
+            counts.comment += 1;
+            continue;
+        }
+        counts.code += 1;
+        if let Some((start, end)) = syntax.block {
+            if let Some(index) = line.rfind(start) {
+                if !line[index + start.len()..].contains(end) { in_block = Some(end); }
+            }
+        }
+    }
+    counts
+}
+
+/// How comments are written in a language.
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+}
+
+/// The comment rules of a language,
+/// by its pastemyst name.
+fn comment_syntax(name: &str) -> CommentSyntax {
+    const C_LIKE: CommentSyntax = CommentSyntax { line: &["//"], block: Some(("/*", "*/")) };
+    const HASH: CommentSyntax = CommentSyntax { line: &["#"], block: None };
+    const DASH: CommentSyntax = CommentSyntax { line: &["--"], block: None };
+    const SEMICOLON: CommentSyntax = CommentSyntax { line: &[";"], block: None };
+    const PERCENT: CommentSyntax = CommentSyntax { line: &["%"], block: None };
+    const MARKUP: CommentSyntax = CommentSyntax { line: &[], block: Some(("<!--", "-->")) };
+    const ML: CommentSyntax = CommentSyntax { line: &[], block: Some(("(*", "*)")) };
+    const NONE: CommentSyntax = CommentSyntax { line: &[], block: None };
+    match name {
+        language::C | language::CPP | language::CSHARP | language::D | language::DART | language::GO
+        | language::GROOVY | language::HAXE | language::JAVA | language::JAVASCRIPT | language::JSX
+        | language::KOTLIN | language::OBJ_C | language::PHP | language::PROTOBUF | language::RUST
+        | language::SCALA | language::SQUIRREL | language::SWIFT | language::TYPESCRIPT
+        | language::TYPESCRIPT_JSX | language::VERILOG | language::SYSTEM_VERILOG | language::LESS
+        | language::SCSS | language::STYLUS => C_LIKE,
+        language::CSS => CommentSyntax { line: &[], block: Some(("/*", "*/")) },
+        language::FSHARP => CommentSyntax { line: &["//"], block: Some(("(*", "*)")) },
+        language::PYTHON | language::CYTHON | language::RUBY | language::SHELL | language::PERL
+        | language::RSCRIPT | language::YAML | language::TOML | language::DOCKER | language::CMAKE
+        | language::JULIA | language::CRYSTAL | language::NGINX | language::PUPPET | language::TCL
+        | language::COFFEE_SCRIPT => HASH,
+        language::POWERSHELL => CommentSyntax { line: &["#"], block: Some(("<#", "#>")) },
+        language::SQL | language::MYSQL | language::MARIA_DB | language::MS_SQL | language::PLSQL
+        | language::SQLITE | language::VHDL | language::EIFFEL => DASH,
+        language::LUA => CommentSyntax { line: &["--"], block: Some(("--[[", "]]")) },
+        language::HASKELL | language::ELM => CommentSyntax { line: &["--"], block: Some(("{-", "-}")) },
+        language::LISP | language::CLOJURE | language::CLOJURE_SCRIPT | language::SCHEME | language::Z80 => SEMICOLON,
+        language::ERLANG | language::LATEX | language::STEX | language::OCTAVE | language::OZ => PERCENT,
+        language::HTML | language::XML | language::MARKDOWN | language::GFM | language::VUE | language::DTD => MARKUP,
+        language::OCAML | language::SML | language::MATHEMATICA => ML,
+        language::VB_NET | language::VBSCRIPT => CommentSyntax { line: &["'"], block: None },
+        language::FORTRAN => CommentSyntax { line: &["!"], block: None },
+        _ => NONE,
+    }
+}
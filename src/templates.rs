@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::error::PasteMystError;
+use crate::paste::{CreateObject, PastyObject};
+
+/// The type provided by the pastemyst lib. It takes
+/// a type `T` and evalutates to that type and a
+/// `Result` like so: `Result<T, E>` where `E` has
+/// the default value of `PasteMystError`. Keep note
+/// that `E` can be overriden.
+pub type TemplateResult<T, E = PasteMystError> = Result<T, E>;
+
+/// A set of templates registered by name. A template is
+/// a regular `CreateObject` whose strings may contain
+/// `{{placeholders}}` that are filled in by `instantiate`.
+/// Write `{{{{` where a literal `{{` is wanted.
+///
+/// The registry is a plain value, so each caller keeps
+/// its own and templates aren't shared between them.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::paste::*;
+/// use pastemyst::templates::Templates;
+///
+/// let mut templates = Templates::new();
+/// templates.register("bug-report", CreateObject {
+///     title: String::from("[bug] {{summary}}"),
+///     expiresIn: pastemyst::time::ExpiresIn::OneMonth,
+///     isPrivate: false,
+///     isPublic: false,
+///     tags: vec![String::from("bug")],
+///     pasties: vec![PastyObject {
+///         _id: String::from(""),
+///         language: String::from(pastemyst::data::language::PLAIN),
+///         title: String::from("Log"),
+///         code: String::from("Version: {{version}}\n\n{{log}}"),
+///     }],
+/// });
+/// assert!(templates.exists("bug-report"));
+/// ```
+#[derive(Clone, Default)]
+pub struct Templates {
+    templates: HashMap<String, CreateObject>,
+}
+
+impl Templates {
+    /// Creates an empty registry.
+    pub fn new() -> Self { Templates::default() }
+
+    /// Registers a template under the given name, replacing
+    /// any template that was registered with the same name.
+    pub fn register(&mut self, name: &str, template: CreateObject) {
+        self.templates.insert(name.to_string(), template);
+    }
+
+    /// Loads a template from a json file, in the same format
+    /// a `CreateObject` is sent to pastemyst in, and registers
+    /// it under the name of the file without its extension,
+    /// e.g. `templates/bug-report.json` is registered as
+    /// `bug-report`. The name is returned.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use pastemyst::templates::*;
+    ///
+    /// fn main() -> TemplateResult<()> {
+    ///     let mut templates = Templates::new();
+    ///     let name = templates.load("templates/bug-report.json")?;
+    ///     println!("Loaded {}", name);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn load(&mut self, path: impl AsRef<Path>) -> TemplateResult<String> {
+        let path = path.as_ref();
+        let template: CreateObject = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.register(&name, template);
+        Ok(name)
+    }
+
+    /// Loads every `.json` file in a directory with `load`
+    /// and returns the names of the registered templates.
+    pub fn load_dir(&mut self, path: impl AsRef<Path>) -> TemplateResult<Vec<String>> {
+        let mut names: Vec<String> = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension() == Some(OsStr::new("json")) {
+                names.push(self.load(&path)?);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Saves a registered template to a json file
+    /// which can be loaded back with `load`.
+    pub fn save(&self, name: &str, path: impl AsRef<Path>) -> TemplateResult<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self.get(name)?)?)?;
+        Ok(())
+    }
+
+    /// Whether a template is registered
+    /// under the given name.
+    pub fn exists(&self, name: &str) -> bool { self.templates.contains_key(name) }
+
+    /// Gets a registered template.
+    ///
+    /// Returns `PasteMystError::TemplateNotFound`
+    /// if there is no such template.
+    pub fn get(&self, name: &str) -> TemplateResult<&CreateObject> {
+        self.templates
+            .get(name)
+            .ok_or_else(|| PasteMystError::TemplateNotFound(name.to_string()))
+    }
+
+    /// Creates a `CreateObject` from a registered template,
+    /// replacing each `{{placeholder}}` in it with the value
+    /// of the variable of the same name. Whitespace around
+    /// the name is ignored, i.e, `{{ version }}` works too,
+    /// and `{{{{` is replaced with a literal `{{`.
+    ///
+    /// Returns `PasteMystError::TemplateNotFound` if there is
+    /// no such template and `PasteMystError::MissingVariable`
+    /// if a placeholder has no value.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::paste::*;
+    /// use pastemyst::templates::Templates;
+    ///
+    /// let mut templates = Templates::new();
+    /// templates.register("incident", CreateObject {
+    ///     title: String::from("Incident on {{ host }}"),
+    ///     expiresIn: pastemyst::time::ExpiresIn::OneWeek,
+    ///     isPrivate: false,
+    ///     isPublic: false,
+    ///     tags: vec![String::from("incident"), String::from("{{host}}")],
+    ///     pasties: vec![PastyObject {
+    ///         _id: String::from(""),
+    ///         language: String::from(pastemyst::data::language::PLAIN),
+    ///         title: String::from("Log"),
+    ///         code: String::from("{{log}}\n\nRendered from {{{{log}}"),
+    ///     }],
+    /// });
+    /// let paste = templates.instantiate("incident", &[("host", "db-1"), ("log", "Disk full")]).unwrap();
+    /// assert_eq!(paste.title, "Incident on db-1");
+    /// assert_eq!(paste.tags, vec!["incident", "db-1"]);
+    /// assert_eq!(paste.pasties[0].code, "Disk full\n\nRendered from {{log}}");
+    /// ```
+    pub fn instantiate(&self, name: &str, variables: &[(&str, &str)]) -> TemplateResult<CreateObject> {
+        render(self.get(name)?, variables)
+    }
+}
+
+/// The same as `Templates::instantiate` except that it
+/// takes the template itself instead of the name it's
+/// registered under.
+pub fn render(template: &CreateObject, variables: &[(&str, &str)]) -> TemplateResult<CreateObject> {
+    let variables: HashMap<&str, &str> = variables.iter().cloned().collect();
+    let mut pasties: Vec<PastyObject> = Vec::new();
+    for pasty in &template.pasties {
+        pasties.push(PastyObject {
+            _id: pasty._id.clone(),
+            language: substitute(&pasty.language, &variables)?,
+            title: substitute(&pasty.title, &variables)?,
+            code: substitute(&pasty.code, &variables)?,
+        });
+    }
+    Ok(CreateObject {
+        title: substitute(&template.title, &variables)?,
+        expiresIn: template.expiresIn,
+        isPrivate: template.isPrivate,
+        isPublic: template.isPublic,
+        tags: template.tags.iter().map(|tag| substitute(tag, &variables)).collect::<TemplateResult<_>>()?,
+        pasties,
+    })
+}
+
+/// Replaces every `{{placeholder}}` in a string with
+/// the value of its variable and every `{{{{` with a
+/// literal `{{`. An opening `{{` without a closing `}}`
+/// is kept as is.
+fn substitute(text: &str, variables: &HashMap<&str, &str>) -> TemplateResult<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        if rest[start..].starts_with("{{{{") {
+            result.push_str("{{");
+            rest = &rest[start + 4..];
+            continue;
+        }
+        let end = match rest[start + 2..].find("}}") {
+            Some(end) => start + 2 + end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+        let name = rest[start + 2..end].trim();
+        let value = variables
+            .get(name)
+            .ok_or_else(|| PasteMystError::MissingVariable(name.to_string()))?;
+        result.push_str(value);
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
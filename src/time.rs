@@ -1,12 +1,20 @@
+#[cfg(all(feature = "time", feature = "network"))]
 use serde::Deserialize;
 
+#[cfg(all(feature = "time", feature = "network"))]
+use crate::connection::{self, SendRequest};
+#[cfg(all(feature = "time", feature = "network"))]
+use crate::error;
+use crate::error::PasteMystError;
+
 /// The type provided by the pastemyst lib. It takes
 /// a type `T` and evalutates to that type and a
 /// `Result` like so: `Result<T, E>` where `E` has
-/// the default value of `reqwest::Error`. Keep note
+/// the default value of `PasteMystError`. Keep note
 /// that `E` can be overriden.
-pub type TimeResult<T, E = reqwest::Error> = Result<T, E>;
+pub type TimeResult<T, E = PasteMystError> = Result<T, E>;
 
+#[cfg(all(feature = "time", feature = "network"))]
 const TIME_ENDPOINT: &str = "https://paste.myst.rs/api/v2/time/expiresInToUnixTime";
 
 /// All the possible values of the
@@ -46,6 +54,122 @@ pub mod expires_in {
     /// a whole year (~8736 hours,
     /// followed by solar calander).
     pub const ONE_YEAR: &str = "1y";
+
+    /// Every value above, from the
+    /// shortest expiry to `NEVER`.
+    pub const ALL: [&str; 9] = [
+        ONE_HOUR, TWO_HOURS, TEN_HOURS, ONE_DAY, TWO_DAYS,
+        ONE_WEEK, ONE_MONTH, ONE_YEAR, NEVER,
+    ];
+
+    /// Whether the given value is one
+    /// PasteMyst accepts as an expiry.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::time::expires_in;
+    ///
+    /// assert!(expires_in::is_valid(expires_in::TEN_HOURS));
+    /// assert!(!expires_in::is_valid("3h"));
+    /// ```
+    pub fn is_valid(expires_in: &str) -> bool { ALL.contains(&expires_in) }
+}
+
+/// The expiration of a paste as a type, each
+/// variant stands for one of the values in
+/// `expires_in` and is sent to PasteMyst as
+/// that value, so that a typo is caught when
+/// compiling instead of by the server.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::time::{expires_in, ExpiresIn};
+///
+/// assert_eq!(ExpiresIn::OneDay.as_str(), expires_in::ONE_DAY);
+/// assert_eq!(ExpiresIn::default().to_string(), "never");
+/// assert_eq!("1w".parse::<ExpiresIn>().unwrap(), ExpiresIn::OneWeek);
+/// assert!("3h".parse::<ExpiresIn>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ExpiresIn {
+    /// The paste will never expire.
+    #[default]
+    Never,
+    /// The paste will expire in an hour.
+    OneHour,
+    /// The paste will expire in two hours.
+    TwoHours,
+    /// The paste will expire in ten hours.
+    TenHours,
+    /// The paste will expire in a day.
+    OneDay,
+    /// The paste will expire in two days.
+    TwoDays,
+    /// The paste will expire in a week.
+    OneWeek,
+    /// The paste will expire in a month.
+    OneMonth,
+    /// The paste will expire in a year.
+    OneYear,
+}
+
+impl ExpiresIn {
+    /// Every variant, from the shortest
+    /// expiry to `Never`.
+    pub const ALL: [ExpiresIn; 9] = [
+        ExpiresIn::OneHour, ExpiresIn::TwoHours, ExpiresIn::TenHours, ExpiresIn::OneDay, ExpiresIn::TwoDays,
+        ExpiresIn::OneWeek, ExpiresIn::OneMonth, ExpiresIn::OneYear, ExpiresIn::Never,
+    ];
+
+    /// The value PasteMyst expects for it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiresIn::Never => expires_in::NEVER,
+            ExpiresIn::OneHour => expires_in::ONE_HOUR,
+            ExpiresIn::TwoHours => expires_in::TWO_HOURS,
+            ExpiresIn::TenHours => expires_in::TEN_HOURS,
+            ExpiresIn::OneDay => expires_in::ONE_DAY,
+            ExpiresIn::TwoDays => expires_in::TWO_DAYS,
+            ExpiresIn::OneWeek => expires_in::ONE_WEEK,
+            ExpiresIn::OneMonth => expires_in::ONE_MONTH,
+            ExpiresIn::OneYear => expires_in::ONE_YEAR,
+        }
+    }
+}
+
+impl std::fmt::Display for ExpiresIn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.as_str()) }
+}
+
+impl std::str::FromStr for ExpiresIn {
+    type Err = PasteMystError;
+
+    fn from_str(expires_in: &str) -> Result<Self, Self::Err> {
+        ExpiresIn::ALL
+            .iter()
+            .copied()
+            .find(|variant| variant.as_str() == expires_in)
+            .ok_or_else(|| PasteMystError::InvalidExpiry(expires_in.to_string()))
+    }
+}
+
+impl AsRef<str> for ExpiresIn {
+    fn as_ref(&self) -> &str { self.as_str() }
+}
+
+impl serde::Serialize for ExpiresIn {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ExpiresIn {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expires_in = String::deserialize(deserializer)?;
+        expires_in.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 /// Synchronously sends a request to pastemyst's time
@@ -55,14 +179,12 @@ pub mod expires_in {
 /// for certian functionality.
 ///
 /// If the `expires_in` field is not valid or recognized,
-/// it does **NOT** send any web requests and emits a warning.
-/// To prevent runtime exceptions, the return value, when unset
-/// i.e, when web requests aren't send, sets the value of the
-/// return integer to 0 regardless.
+/// it does **NOT** send any web requests and returns
+/// `PasteMystError::InvalidExpiry` instead.
 ///
 /// The `created_at` value is an unsigned 64 bit integer meanwhile
-/// `expires_at` is a string. It's recommended to use provided strings
-/// by the library under `pastemyst::time::expires_in`.
+/// `expires_in` is either an `ExpiresIn` or one of the strings
+/// provided by the library under `pastemyst::time::expires_in`.
 ///
 /// ### API Docs
 /// The relevent API documentation for this method is:
@@ -70,7 +192,7 @@ pub mod expires_in {
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::time::*;
 ///
 /// fn main() -> TimeResult<()> {
@@ -79,24 +201,11 @@ pub mod expires_in {
 ///     Ok(())
 /// }
 /// ```
-pub fn expires_into_unix(created_at: u64, expires_in: &str) -> TimeResult<u64> {
-    let mut response: TimeObject = TimeObject { result: 0 };
-    #[allow(unused_assignments)] let mut valid_time: bool = false;
-    match expires_in {
-        expires_in::NEVER => valid_time = true,
-        expires_in::ONE_HOUR => valid_time = true,
-        expires_in::TWO_HOURS => valid_time = true,
-        expires_in::ONE_DAY => valid_time = true,
-        expires_in::TWO_DAYS => valid_time = true,
-        expires_in::ONE_WEEK => valid_time = true,
-        expires_in::ONE_MONTH => valid_time = true,
-        expires_in::ONE_YEAR => valid_time = true,
-        _ => valid_time = false
-    }
-    if valid_time {
-        response = reqwest::blocking::get(&parse_time(created_at, expires_in))?
-            .json().unwrap();
-    } else { println!("[pastemyst] The given expires timestamp is not valid and 0 will be returned."); }
+#[cfg(all(feature = "time", feature = "blocking"))]
+pub fn expires_into_unix(created_at: u64, expires_in: impl AsRef<str>) -> TimeResult<u64> {
+    let expires_in: ExpiresIn = expires_in.as_ref().parse()?;
+    let result = connection::blocking_http()?.get(parse_time(created_at, expires_in)).send_request()?;
+    let response: TimeObject = error::check_status(result)?.json()?;
     Ok(response.result)
 }
 
@@ -107,14 +216,12 @@ pub fn expires_into_unix(created_at: u64, expires_in: &str) -> TimeResult<u64> {
 /// for certian functionality.
 ///
 /// If the `expires_in` field is not valid or recognized,
-/// it does **NOT** send any web requests and emits a warning.
-/// To prevent runtime exceptions, the return value, when unset
-/// i.e, when web requests aren't send, sets the value of the
-/// return integer to 0 regardless.
+/// it does **NOT** send any web requests and returns
+/// `PasteMystError::InvalidExpiry` instead.
 ///
 /// The `created_at` value is an unsigned 64 bit integer meanwhile
-/// `expires_at` is a string. It's recommended to use provided strings
-/// by the library under `pastemyst::time::expires_in`.
+/// `expires_in` is either an `ExpiresIn` or one of the strings
+/// provided by the library under `pastemyst::time::expires_in`.
 ///
 /// ### API Docs
 /// The relevent API documentation for this method is:
@@ -122,37 +229,24 @@ pub fn expires_into_unix(created_at: u64, expires_in: &str) -> TimeResult<u64> {
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::time::*;
 ///
 /// #[tokio::main]
 /// async fn main() -> TimeResult<()> {
-///     let unix_time: u64 = expires_into_unix_async(1337, expires_in::TWO_DAYS).await?;
+///     let unix_time: u64 = expires_into_unix_async(1337, ExpiresIn::TwoDays).await?;
 ///     println!("{}", unix_time.to_string());
 ///     Ok(())
 /// }
 /// ```
-pub async fn expires_into_unix_async(created_at: u64, expires_in: &str) -> TimeResult<u64> {
-    let mut response: TimeObject = TimeObject { result: 0 };
-    #[allow(unused_assignments)] let mut valid_time: bool = false;
-    match expires_in {
-        expires_in::NEVER => valid_time = true,
-        expires_in::ONE_HOUR => valid_time = true,
-        expires_in::TWO_HOURS => valid_time = true,
-        expires_in::ONE_DAY => valid_time = true,
-        expires_in::TWO_DAYS => valid_time = true,
-        expires_in::ONE_WEEK => valid_time = true,
-        expires_in::ONE_MONTH => valid_time = true,
-        expires_in::ONE_YEAR => valid_time = true,
-        _ => valid_time = false
-    }
-    if valid_time {
-        response = reqwest::Client::builder()
-            .build()?
-            .get(&parse_time(created_at, expires_in))
-            .send().await?
-            .json().await?;
-    } else { println!("[pastemyst] The given expires timestamp is not valid and 0 will be returned."); }
+#[cfg(all(feature = "time", feature = "async"))]
+pub async fn expires_into_unix_async(created_at: u64, expires_in: impl AsRef<str>) -> TimeResult<u64> {
+    let expires_in: ExpiresIn = expires_in.as_ref().parse()?;
+    let result = connection::http()?
+        .get(parse_time(created_at, expires_in))
+        .send_request()
+        .await?;
+    let response: TimeObject = error::check_status_async(result).await?.json().await?;
     Ok(response.result)
 }
 
@@ -163,13 +257,16 @@ pub async fn expires_into_unix_async(created_at: u64, expires_in: &str) -> TimeR
 /// and be returned directly instead
 /// of the struct itself for ease.
 /// This is the main reason why this
-/// struct has been kept private.
+/// struct has been kept private to
+/// the crate.
+#[cfg(all(feature = "time", feature = "network"))]
 #[derive(Deserialize)]
-struct TimeObject { result: u64 }
+pub(crate) struct TimeObject { pub(crate) result: u64 }
 
 /// Parses the time module's API path
-fn parse_time(created_at: u64, expires_in: &str) -> String {
-    return format!(
+#[cfg(all(feature = "time", feature = "network"))]
+pub(crate) fn parse_time(created_at: u64, expires_in: ExpiresIn) -> String {
+    format!(
         "{}?createdAt={}&expiresIn={}",
         TIME_ENDPOINT, created_at, expires_in
     )
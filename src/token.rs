@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::PasteMystError;
+
+/// A PasteMyst authorization token that hides itself
+/// when it's printed, so that logging a struct holding
+/// one (or the token itself) doesn't leak it. Every
+/// function requiring a token accepts an `ApiToken` as
+/// well as a plain `&str`, the ones where it's optional
+/// take an `Option<&ApiToken>`.
+///
+/// Serializing it writes the token as it is so that it
+/// can be saved, e.g. in the config file. Only `Debug`
+/// and `Display` hide it, don't serialize a struct
+/// holding one into logs.
+///
+/// You can get a token from your user settings on
+/// [pastemyst](https://paste.myst.rs/user/settings).
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::token::ApiToken;
+///
+/// let token = ApiToken::new("my-secret-token");
+/// assert_eq!(format!("{:?}", token), "ApiToken(***)");
+/// assert_eq!(token.to_string(), "***");
+/// assert_eq!(token.expose(), "my-secret-token");
+/// ```
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::token::ApiToken;
+///
+/// fn main() -> PasteResult<()> {
+///     let token = ApiToken::from_env()?;
+///     let paste = get_private_paste("pasteID", &token)?;
+///     println!("{}", paste.title);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiToken(String);
+
+impl ApiToken {
+    /// The environment variable
+    /// `from_env` reads the token from.
+    pub const ENV_VAR: &'static str = "PASTEMYST_TOKEN";
+
+    /// Wraps a token.
+    pub fn new(token: impl Into<String>) -> Self { ApiToken(token.into()) }
+
+    /// Reads the token from the `PASTEMYST_TOKEN`
+    /// environment variable. Returns
+    /// `PasteMystError::MissingToken` if it isn't
+    /// set or is empty.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::token::ApiToken;
+    ///
+    /// std::env::set_var("PASTEMYST_TOKEN", "my-secret-token");
+    /// let token = ApiToken::from_env().unwrap();
+    /// assert_eq!(token.expose(), "my-secret-token");
+    ///
+    /// std::env::remove_var("PASTEMYST_TOKEN");
+    /// assert!(ApiToken::from_env().is_err());
+    /// ```
+    pub fn from_env() -> Result<Self, PasteMystError> {
+        match std::env::var(ApiToken::ENV_VAR) {
+            Ok(token) if !token.trim().is_empty() => Ok(ApiToken(token.trim().to_string())),
+            _ => Err(PasteMystError::MissingToken),
+        }
+    }
+
+    /// The token itself, to be sent
+    /// in the `Authorization` header.
+    pub fn expose(&self) -> &str { &self.0 }
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "ApiToken(***)") }
+}
+
+impl std::fmt::Display for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "***") }
+}
+
+impl AsRef<str> for ApiToken {
+    fn as_ref(&self) -> &str { &self.0 }
+}
+
+impl From<String> for ApiToken {
+    fn from(token: String) -> Self { ApiToken(token) }
+}
+
+impl From<&str> for ApiToken {
+    fn from(token: &str) -> Self { ApiToken(token.to_string()) }
+}
@@ -0,0 +1,92 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+#[cfg(feature = "endpoints")]
+use crate::error::PasteMystError;
+
+/// The http types transports work with,
+/// re-exported so that you don't have to
+/// depend on the `http` crate yourself.
+pub use http;
+
+/// The error a `Transport` fails with.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by `Transport::send`.
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<http::Response<Vec<u8>>, TransportError>> + Send + 'a>>;
+
+/// The future returned by `Transport::sleep`.
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Sends the requests of a `PasteMystClient` with an http
+/// client of your own choosing instead of reqwest, whose
+/// async client needs a tokio runtime. Implement it on
+/// top of surf, isahc or any other client to use the
+/// crate from async-std, smol or other executors. The
+/// asynchronous free functions of the `paste`, `user`,
+/// `data` and `time` modules are sent through one with
+/// `connection::set_default_transport`.
+///
+/// The request is handed over with its whole body and
+/// the response is expected the same way. Everything
+/// else the client does (tokens, hooks, interceptors,
+/// stats, size limits) works as usual, the waits between
+/// the retries of a rate limited request go through
+/// `Transport::sleep`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::client::*;
+/// use pastemyst::transport::*;
+///
+/// struct MyTransport;
+///
+/// impl Transport for MyTransport {
+///     fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_> {
+///         Box::pin(async move {
+///             // Send `request` with the http client of your executor.
+///             let response = http::Response::builder().status(200).body(Vec::new())?;
+///             Ok(response)
+///         })
+///     }
+/// }
+///
+/// let client = PasteMystClient::from_transport(MyTransport);
+/// ```
+pub trait Transport: Send + Sync {
+    /// Sends the request and
+    /// reads the whole response.
+    fn send(&self, request: http::Request<Vec<u8>>) -> TransportFuture<'_>;
+
+    /// Waits for `duration` before a rate limited request is
+    /// sent again, see `PasteMystClient::with_rate_limit_retries`.
+    /// The default uses `futures-timer`, which works on any
+    /// executor, override it to use the timer of yours
+    /// instead, e.g. `async_std::task::sleep`.
+    fn sleep(&self, duration: Duration) -> SleepFuture { Box::pin(futures_timer::Delay::new(duration)) }
+}
+
+/// Sends a request through the transport, converting
+/// between its types and reqwest's.
+#[cfg(feature = "endpoints")]
+pub(crate) async fn send(transport: &dyn Transport, request: reqwest::Request) -> Result<reqwest::Response, PasteMystError> {
+    let body = match request.body() {
+        Some(body) => match body.as_bytes() {
+            Some(bytes) => bytes.to_vec(),
+            None => {
+                let error = "streamed bodies can't be sent through a custom transport";
+                return Err(PasteMystError::Transport(error.into()));
+            }
+        },
+        None => Vec::new(),
+    };
+    let mut builder = http::Request::builder().method(request.method().clone()).uri(request.url().as_str());
+    for (name, value) in request.headers() {
+        builder = builder.header(name, value);
+    }
+    let request = builder.body(body).map_err(|error| PasteMystError::Transport(error.into()))?;
+    let response = transport.send(request).await.map_err(PasteMystError::Transport)?;
+    Ok(reqwest::Response::from(response))
+}
@@ -1,36 +1,76 @@
-use serde::Deserialize;
+#[cfg(all(feature = "user", feature = "async"))]
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "user", feature = "network"))]
+use crate::connection::{self, SendRequest};
+#[cfg(all(feature = "user", feature = "network"))]
+use crate::error::{self, PasteMystError};
+#[cfg(all(feature = "user", feature = "network"))]
+use crate::paste;
+use crate::paste::PasteObject;
+#[cfg(all(feature = "user", feature = "network"))]
+use crate::paste::PasteId;
+#[cfg(all(feature = "user", feature = "network"))]
+use crate::token::ApiToken;
 
 /// The type provided by the pastemyst lib. It takes
 /// a type `T` and evalutates to that type and a
 /// `Result` like so: `Result<T, E>` where `E` has
-/// the default value of `reqwest::Error`. Keep note
+/// the default value of `PasteMystError`. Keep note
 /// that `E` can be overriden.
-pub type UserResult<T, E = reqwest::Error> = Result<T, E>;
+#[cfg(all(feature = "user", feature = "network"))]
+pub type UserResult<T, E = PasteMystError> = Result<T, E>;
+
+#[cfg(any(
+    all(feature = "user", feature = "network"),
+    all(feature = "paste", feature = "async", not(target_arch = "wasm32")),
+))]
+pub(crate) const USER_ENDPOINT: &str = "https://paste.myst.rs/api/v2/user/";
+
+/// The name to pass to `snapshot` to get
+/// the account the token belongs to.
+pub const SELF: &str = "self";
+
+/// How many paste ids are asked for in each
+/// page of the pastes of the token's account,
+/// PasteMyst doesn't document paging there
+/// and may send them all regardless.
+#[cfg(all(feature = "user", feature = "network"))]
+const SELF_PAGE_SIZE: usize = 50;
 
-const USER_ENDPOINT: &str = "https://paste.myst.rs/api/v2/user/";
+/// How many pastes are fetched at once
+/// when taking a snapshot or listing
+/// public pastes.
+#[cfg(all(feature = "user", feature = "async"))]
+const SNAPSHOT_CONCURRENCY: usize = 8;
 
 /// Gets a user synchronously from [pastemyst](https://paste.myst.rs)
 /// This information is stored in the `UserObject` struct. If a user
-/// does not exist, it will return nothing and emit a console log
-/// warning about the user not existing.
+/// does not exist, it will return nothing and emit a warning about
+/// the user not existing.
 ///
-/// As of now, there is no specific way to disable this warning
-/// unless you compile your own version. This might change in
-/// the future, but for now, a warning will be emmited if
-/// it returns false.
+/// The warning is only emitted with the `tracing` feature, as a
+/// `tracing` event, or the `log` feature, through the `log` facade
+/// under the `pastemyst` target. Without either it is dropped.
 ///
 /// It still returns the struct when a user is not found and emit
 /// a warning as stated earlier except that this method will return
 /// an empty struct i.e, all the strings will be set to `""`, all
 /// the booleans set to false and all numerics set to 0.
 ///
+/// The profile is fetched in a single request, use
+/// `user_exists` if you only need to know whether
+/// the user exists, or `try_get_user` to get `None`
+/// for a user that doesn't exist.
+///
 /// ### API Docs
 /// The relevent API documentation for this method is:
 /// https://paste.myst.rs/api-docs/user
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// fn main() -> UserResult<()> {
@@ -39,50 +79,44 @@ const USER_ENDPOINT: &str = "https://paste.myst.rs/api/v2/user/";
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
 pub fn get_user(username: &str) -> UserResult<UserObject> {
-    let mut result: UserObject = UserObject {
-        _id: str!(""),
-        username: str!(""),
-        avatarUrl: str!(""),
-        defaultLang: str!(""),
-        publicProfile: false,
-        supporterLength: 0,
-        contributor: false
-    };
-    if user_exists(username)? == false {
-        print!("[pastemyst] The user '{}' does not exist and an empty object is returned.\n", username);            
-    } else {
-        result = reqwest::blocking::Client::builder()
-            .build()?
-            .get(&parse_user(username))
-            .send()?
-            .json()?;
+    let result = connection::blocking_http()?
+        .get(parse_user(username))
+        .send_request()?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND {
+        connection::warn(format_args!("The user '{}' does not exist and an empty object is returned.", username));
+        return Ok(UserObject::default());
     }
-    Ok(result)
+    Ok(error::check_status(result)?.json()?)
 }
 
 /// Gets a user asynchronously from [pastemyst](https://paste.myst.rs)
 /// This information is stored in the `UserObject` struct. If a user
-/// does not exist, it will return nothing and emit a console log
-/// warning about the user not existing.
+/// does not exist, it will return nothing and emit a warning about
+/// the user not existing.
 ///
-/// As of now, there is no specific way to disable this warning
-/// unless you compile your own version. This might change in
-/// the future, but for now, a warning will be emmited if
-/// it returns false.
+/// The warning is only emitted with the `tracing` feature, as a
+/// `tracing` event, or the `log` feature, through the `log` facade
+/// under the `pastemyst` target. Without either it is dropped.
 ///
 /// It still returns the struct when a user is not found and emit
 /// a warning as stated earlier except that this method will return
 /// an empty struct i.e, all the strings will be set to `""`, all
 /// the booleans set to false and all numerics set to 0.
 ///
+/// The profile is fetched in a single request, use
+/// `user_exists` if you only need to know whether
+/// the user exists, or `try_get_user` to get `None`
+/// for a user that doesn't exist.
+///
 /// ### API Docs
 /// The relevent API documentation for this method is:
 /// https://paste.myst.rs/api-docs/user
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// #[tokio::main]
@@ -92,26 +126,73 @@ pub fn get_user(username: &str) -> UserResult<UserObject> {
 ///     Ok(())
 /// }
 /// ```
-pub async fn get_user_async(username: &str) -> Result<UserObject, reqwest::Error> {
-    let mut result: UserObject = UserObject {
-        _id: str!(""),
-        username: str!(""),
-        avatarUrl: str!(""),
-        defaultLang: str!(""),
-        publicProfile: false,
-        supporterLength: 0,
-        contributor: false
-    };
-    if user_exists_async(username).await? == false {
-        print!("[pastemyst] The user '{}' does not exist and an empty object is returned.\n", username);            
-    } else {
-        result = reqwest::Client::builder()
-            .build()?
-            .get(&parse_user(username))
-            .send().await?
-            .json().await?;
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn get_user_async(username: &str) -> UserResult<UserObject> {
+    let result = connection::http()?
+        .get(parse_user(username))
+        .send_request()
+        .await?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND {
+        connection::warn(format_args!("The user '{}' does not exist and an empty object is returned.", username));
+        return Ok(UserObject::default());
     }
-    Ok(result)
+    Ok(error::check_status_async(result).await?.json().await?)
+}
+
+/// Gets a user synchronously, `None` is returned if
+/// the user doesn't exist instead of the empty
+/// `UserObject` and warning of `get_user`.
+///
+/// ### API Docs
+/// The relevent API documentation for this method is:
+/// https://paste.myst.rs/api-docs/user
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     match try_get_user("ANF-Studios")? {
+///         Some(user) => println!("{}", user.defaultLang),
+///         None => println!("No such user"),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn try_get_user(username: &str) -> UserResult<Option<UserObject>, PasteMystError> {
+    let result = connection::blocking_http()?.get(parse_user(username)).send_request()?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(None); }
+    Ok(Some(error::check_status(result)?.json()?))
+}
+
+/// Gets a user asynchronously, `None` is returned if
+/// the user doesn't exist instead of the empty
+/// `UserObject` and warning of `get_user_async`.
+///
+/// ### API Docs
+/// The relevent API documentation for this method is:
+/// https://paste.myst.rs/api-docs/user
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     if let Some(user) = try_get_user_async("ANF-Studios").await? {
+///         println!("{}", user.avatarUrl);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn try_get_user_async(username: &str) -> UserResult<Option<UserObject>, PasteMystError> {
+    let result = connection::http()?.get(parse_user(username)).send_request().await?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(None); }
+    Ok(Some(error::check_status_async(result).await?.json().await?))
 }
 
 /// Sends a request to [pastemyst](https://paste.myst.rs)
@@ -119,6 +200,11 @@ pub async fn get_user_async(username: &str) -> Result<UserObject, reqwest::Error
 /// it returns a value of `200` i.e, `true` else `false`.
 /// This methods runs synchronously.
 ///
+/// Any status other than `200` and `404`, e.g. when
+/// PasteMyst is down, is returned as an error rather
+/// than `false`, see `user_existence` to get it as
+/// a value instead.
+///
 /// The return value of this function is not to be confused
 /// with an integer -- this method returns a boolean.
 ///
@@ -128,7 +214,7 @@ pub async fn get_user_async(username: &str) -> Result<UserObject, reqwest::Error
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// fn main() -> UserResult<()> {
@@ -139,7 +225,7 @@ pub async fn get_user_async(username: &str) -> Result<UserObject, reqwest::Error
 /// }
 /// ```
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -150,15 +236,14 @@ pub async fn get_user_async(username: &str) -> Result<UserObject, reqwest::Error
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
 pub fn user_exists(username: &str) -> UserResult<bool> {
-    let result = reqwest::blocking::Client::builder()
-        .build()?
-        .get(&parse_user_get(username))
-        .send()?;
-    let mut user_exists: bool = false;
-    if result.status().as_u16() == 200 { user_exists = true; }
-    else if result.status().as_u16() == 404 { user_exists = false; }
-    Ok(user_exists)
+    let result = connection::blocking_http()?
+        .get(parse_user_get(username))
+        .send_request()?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(false); }
+    error::check_status(result)?;
+    Ok(true)
 }
 
 /// Sends a request to [pastemyst](https://paste.myst.rs)
@@ -166,6 +251,11 @@ pub fn user_exists(username: &str) -> UserResult<bool> {
 /// it returns a value of `200` i.e, `true` else `false`.
 /// This methods runs asynchronously.
 ///
+/// Any status other than `200` and `404`, e.g. when
+/// PasteMyst is down, is returned as an error rather
+/// than `false`, see `user_existence_async` to get it
+/// as a value instead.
+///
 /// The return value of this function is not to be confused
 /// with an integer -- this method returns a boolean.
 ///
@@ -175,60 +265,565 @@ pub fn user_exists(username: &str) -> UserResult<bool> {
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// #[tokio::main]
 /// async fn main() -> UserResult<()> {
 ///     const USERNAME: &str = "ANF-Studios";
-///     let exists: bool = user_exists(USERNAME).await?;
+///     let exists: bool = user_exists_async(USERNAME).await?;
 ///     print!("The user '{}' exists: {}", USERNAME, exists);
 ///     Ok(())
 /// }
 /// ```
 ///
-/// ```rust
+/// ```rust,no_run
 /// use pastemyst::user::*;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     const USERNAME: &str = "ANF-Studios";
-///     if user_exists(USERNAME).await? == true {
+///     if user_exists_async(USERNAME).await? == true {
 ///         println!("{} does indeed exist!", USERNAME);
 ///     } else { println!("{} was not found and does not exist.", USERNAME); }
 ///     Ok(())
 /// }
 /// ```
+#[cfg(all(feature = "user", feature = "async"))]
 pub async fn user_exists_async(username: &str) -> UserResult<bool> {
-    let result = reqwest::Client::builder()
-        .build()?
-        .get(&parse_user_get(username))
-        .send().await?;
-    let mut user_exists: bool = false;
-    if result.status().as_u16() == 200 { user_exists = true; }
-    else if result.status().as_u16() == 404 { user_exists = false; }
-    Ok(user_exists)
+    let result = connection::http()?
+        .get(parse_user_get(username))
+        .send_request().await?;
+    if result.status() == reqwest::StatusCode::NOT_FOUND { return Ok(false); }
+    error::check_status_async(result).await?;
+    Ok(true)
+}
+
+/// Checks whether a user exists synchronously, telling
+/// a user that doesn't exist apart from an unexpected
+/// response such as an outage, see `UserExistence`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<()> {
+///     match user_existence("ANF-Studios")? {
+///         UserExistence::Exists => println!("Found"),
+///         UserExistence::NotFound => println!("No such user"),
+///         UserExistence::Unknown(status) => println!("PasteMyst responded with {}", status),
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn user_existence(username: &str) -> UserResult<UserExistence> {
+    let result = connection::blocking_http()?.get(parse_user_get(username)).send_request()?;
+    Ok(UserExistence::from_status(result.status().as_u16()))
+}
+
+/// Checks whether a user exists asynchronously, telling
+/// a user that doesn't exist apart from an unexpected
+/// response such as an outage, see `UserExistence`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<()> {
+///     let existence = user_existence_async("ANF-Studios").await?;
+///     println!("{:?}", existence);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn user_existence_async(username: &str) -> UserResult<UserExistence> {
+    let result = connection::http()?.get(parse_user_get(username)).send_request().await?;
+    Ok(UserExistence::from_status(result.status().as_u16()))
+}
+
+/// Takes a snapshot of a profile synchronously: the user,
+/// the ids of their pastes and the pastes themselves,
+/// fetched one after the other. This is the building
+/// block for dashboards, backups and stats. Pass
+/// `user::SELF` along with a token to snapshot your
+/// own account.
+///
+/// PasteMyst only lists the pastes of the account a token
+/// belongs to, so the pastes of any other user are left
+/// empty. The API has no endpoint for the public pastes
+/// of a profile either.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::token::ApiToken;
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = ApiToken::new("Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings");
+///     let profile = snapshot(SELF, Some(&token))?;
+///     println!("{} has {} pastes", profile.user.username, profile.pastes.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn snapshot(username: &str, auth_token: Option<&ApiToken>) -> UserResult<ProfileSnapshot, PasteMystError> {
+    let auth_token = auth_token.map(ApiToken::expose);
+    if username == SELF && auth_token.is_none() { return Err(PasteMystError::MissingToken); }
+    let client = connection::blocking_http()?;
+    let mut request = client.get(parse_user(username));
+    if let Some(token) = auth_token { request = request.header("Authorization", token); }
+    let user: UserObject = error::check_status(request.send_request()?)?.json()?;
+    let paste_ids: Vec<String> = match auth_token {
+        Some(token) if username == SELF => {
+            let request = client.get(format!("{}self/pastes", USER_ENDPOINT)).header("Authorization", token);
+            error::check_status(request.send_request()?)?.json()?
+        }
+        _ => Vec::new(),
+    };
+    let token = auth_token.unwrap_or_default();
+    let pastes = paste_ids
+        .iter()
+        .map(|id| paste::get_private_paste(id, token))
+        .collect::<Result<_, _>>()?;
+    Ok(ProfileSnapshot { user, paste_ids, pastes })
+}
+
+/// Takes a snapshot of a profile asynchronously: the user,
+/// the ids of their pastes and the pastes themselves, all
+/// fetched concurrently. Pass `user::SELF` along with a
+/// token to snapshot your own account.
+///
+/// PasteMyst only lists the pastes of the account a token
+/// belongs to, so the pastes of any other user are left
+/// empty. The API has no endpoint for the public pastes
+/// of a profile either.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let profile = snapshot_async("ANF-Studios", None).await?;
+///     println!("{}", profile.user.avatarUrl);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn snapshot_async(
+    username: &str,
+    auth_token: Option<&ApiToken>,
+) -> UserResult<ProfileSnapshot, PasteMystError> {
+    let auth_token = auth_token.map(ApiToken::expose);
+    if username == SELF && auth_token.is_none() { return Err(PasteMystError::MissingToken); }
+    let client = connection::http()?;
+    let fetch_user = async {
+        let mut request = client.get(parse_user(username));
+        if let Some(token) = auth_token { request = request.header("Authorization", token); }
+        let response = error::check_status_async(request.send_request().await?).await?;
+        Ok::<UserObject, PasteMystError>(response.json().await?)
+    };
+    let fetch_paste_ids = async {
+        let token = match auth_token {
+            Some(token) if username == SELF => token,
+            _ => return Ok(Vec::new()),
+        };
+        let request = client.get(format!("{}self/pastes", USER_ENDPOINT)).header("Authorization", token);
+        let response = error::check_status_async(request.send_request().await?).await?;
+        Ok::<Vec<String>, PasteMystError>(response.json().await?)
+    };
+    let (user, paste_ids) = futures::try_join!(fetch_user, fetch_paste_ids)?;
+    let token = auth_token.unwrap_or_default();
+    let pastes = stream::iter(&paste_ids)
+        .map(|id| paste::get_private_paste_async(id, token))
+        .buffered(SNAPSHOT_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(ProfileSnapshot { user, paste_ids, pastes })
+}
+
+/// Gets the ids of the pastes the account of the token
+/// has starred, synchronously. They're the `stars` of
+/// `get_self_user`. Use `get_starred_pastes` to get the
+/// pastes themselves.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     for id in get_starred_paste_ids(token)? { println!("https://paste.myst.rs/{}", id); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn get_starred_paste_ids(auth_token: impl AsRef<str>) -> UserResult<Vec<String>, PasteMystError> {
+    Ok(get_self_user(auth_token)?.stars)
+}
+
+/// Gets the ids of the pastes the account of the token
+/// has starred, asynchronously. They're the `stars`
+/// of `get_self_user_async`. Use
+/// `get_starred_pastes_async` to get the pastes
+/// themselves.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let ids = get_starred_paste_ids_async(token).await?;
+///     println!("{} pastes starred", ids.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn get_starred_paste_ids_async(auth_token: impl AsRef<str>) -> UserResult<Vec<String>, PasteMystError> {
+    Ok(get_self_user_async(auth_token).await?.stars)
+}
+
+/// Gets the pastes the account of the token has starred
+/// synchronously, the pastes are fetched one after the
+/// other in the order PasteMyst lists them in.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     for paste in get_starred_pastes(token)? { println!("{}: {}", paste.url(), paste.title); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn get_starred_pastes(auth_token: impl AsRef<str>) -> UserResult<Vec<PasteObject>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    get_starred_paste_ids(auth_token)?
+        .iter()
+        .map(|id| paste::get_private_paste(id, auth_token))
+        .collect()
+}
+
+/// Gets the pastes the account of the token has starred
+/// asynchronously, the pastes are fetched a few at a time
+/// and kept in the order PasteMyst lists them in.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     for paste in get_starred_pastes_async(token).await? { println!("{}", paste.title); }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn get_starred_pastes_async(auth_token: impl AsRef<str>) -> UserResult<Vec<PasteObject>, PasteMystError> {
+    let auth_token = auth_token.as_ref();
+    let paste_ids = get_starred_paste_ids_async(auth_token).await?;
+    stream::iter(&paste_ids)
+        .map(|id| paste::get_private_paste_async(id, auth_token))
+        .buffered(SNAPSHOT_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+/// Gets the account the token belongs to synchronously,
+/// along with the details PasteMyst only shows to its
+/// owner, see `SelfUserObject`.
+///
+/// ### API Docs
+/// The relevent API documentation for this method is:
+/// https://paste.myst.rs/api-docs/user
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let user = get_self_user(token)?;
+///     println!("{} has starred {} pastes", user.username, user.stars.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn get_self_user(auth_token: impl AsRef<str>) -> UserResult<SelfUserObject, PasteMystError> {
+    let result = connection::blocking_http()?
+        .get(parse_user(SELF))
+        .header("Authorization", auth_token.as_ref())
+        .send_request()?;
+    Ok(error::check_status(result)?.json()?)
+}
+
+/// Gets the account the token belongs to asynchronously,
+/// along with the details PasteMyst only shows to its
+/// owner, see `SelfUserObject`.
+///
+/// ### API Docs
+/// The relevent API documentation for this method is:
+/// https://paste.myst.rs/api-docs/user
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let user = get_self_user_async(token).await?;
+///     println!("Logged in as {}", user.username);
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub async fn get_self_user_async(auth_token: impl AsRef<str>) -> UserResult<SelfUserObject, PasteMystError> {
+    let result = connection::http()?
+        .get(parse_user(SELF))
+        .header("Authorization", auth_token.as_ref())
+        .send_request()
+        .await?;
+    Ok(error::check_status_async(result).await?.json().await?)
+}
+
+/// Walks through the ids of the pastes of the token's
+/// account, synchronously. The ids are asked for a page
+/// at a time, once the previous page has been used, but
+/// `page` and `perPage` aren't documented parameters of
+/// the endpoint: PasteMyst may send every id at once,
+/// so `.take(10)` can still download the whole list,
+/// and one more full request is made to find out that
+/// nothing comes after it. The iteration stops after
+/// the first error.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::user::*;
+///
+/// fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     for id in iter_self_paste_ids(token)?.take(100) {
+///         println!("https://paste.myst.rs/{}", id?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "blocking"))]
+pub fn iter_self_paste_ids(
+    auth_token: impl AsRef<str>,
+) -> UserResult<impl Iterator<Item = Result<PasteId, PasteMystError>>, PasteMystError> {
+    let client = connection::blocking_http()?;
+    let mut pages = SelfPages::new(auth_token.as_ref());
+    let mut ids: std::collections::VecDeque<PasteId> = Default::default();
+    Ok(std::iter::from_fn(move || loop {
+        if let Some(id) = ids.pop_front() { return Some(Ok(id)); }
+        let request = match pages.next_url() {
+            Some(url) => client.get(url).header("Authorization", &pages.auth_token),
+            None => return None,
+        };
+        let page = request
+            .send_request()
+            .map_err(PasteMystError::from)
+            .and_then(error::check_status)
+            .and_then(|response| Ok(response.json::<Vec<String>>()?));
+        match page.and_then(|page| pages.take(page)) {
+            Ok(page) => ids.extend(page),
+            Err(error) => {
+                pages.done = true;
+                return Some(Err(error));
+            }
+        }
+    }))
+}
+
+/// Walks through the ids of the pastes of the token's
+/// account as a `Stream`. The ids are asked for a page
+/// at a time, once the previous page has been used, but
+/// `page` and `perPage` aren't documented parameters of
+/// the endpoint: PasteMyst may send every id at once,
+/// so `.take(10)` can still download the whole list,
+/// and one more full request is made to find out that
+/// nothing comes after it. The ids come in the order PasteMyst lists them in, as
+/// sorting them would need every paste. The stream
+/// ends after the first error.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use futures::{StreamExt, TryStreamExt};
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let ids: Vec<_> = stream_self_paste_ids(token)?.take(100).try_collect().await?;
+///     println!("{} pastes", ids.len());
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub fn stream_self_paste_ids(
+    auth_token: impl AsRef<str>,
+) -> UserResult<impl futures::Stream<Item = Result<PasteId, PasteMystError>>, PasteMystError> {
+    let client = connection::http()?;
+    let pages = SelfPages::new(auth_token.as_ref());
+    let pages = stream::try_unfold((client, pages), |(client, mut pages)| async move {
+        let url = match pages.next_url() {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+        let request = client.get(url).header("Authorization", &pages.auth_token);
+        let response = error::check_status_async(request.send_request().await?).await?;
+        let page = pages.take(response.json().await?)?;
+        Ok::<_, PasteMystError>(Some((page, (client, pages))))
+    });
+    Ok(pages.map_ok(|page| stream::iter(page.into_iter().map(Ok))).try_flatten())
+}
+
+/// Gets every paste of the token's account as a `Stream`,
+/// the building block for backups and searches. The ids
+/// are walked through with `stream_self_paste_ids` and
+/// the pastes are fetched a few at a time as the stream
+/// is polled, in the order PasteMyst lists them in. The
+/// stream ends after the first error. To sort or filter
+/// the pastes, collect them and use `ListOptions::apply`.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use futures::TryStreamExt;
+/// use pastemyst::search::{ListOptions, SortBy};
+/// use pastemyst::user::*;
+///
+/// #[tokio::main]
+/// async fn main() -> UserResult<(), pastemyst::error::PasteMystError> {
+///     let token = "Your PasteMyst Token. Get it from: https://paste.myst.rs/user/settings";
+///     let mut pastes = Box::pin(get_self_pastes_hydrated(token)?);
+///     while let Some(paste) = pastes.try_next().await? {
+///         println!("{}: {}", paste.url(), paste.title);
+///     }
+///
+///     let options = ListOptions { sort_by: SortBy::Stars, limit: Some(5), ..Default::default() };
+///     let pastes: Vec<_> = get_self_pastes_hydrated(token)?.try_collect().await?;
+///     for paste in options.apply(pastes) {
+///         println!("{} ({} stars)", paste.title, paste.stars);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(all(feature = "user", feature = "async"))]
+pub fn get_self_pastes_hydrated(
+    auth_token: impl AsRef<str>,
+) -> UserResult<impl futures::Stream<Item = Result<PasteObject, PasteMystError>>, PasteMystError> {
+    let auth_token = auth_token.as_ref().to_string();
+    let ids = stream_self_paste_ids(auth_token.clone())?;
+    Ok(ids
+        .map_ok(move |id| {
+            let auth_token = auth_token.clone();
+            async move { paste::get_private_paste_async(&id, auth_token).await }
+        })
+        .try_buffered(SNAPSHOT_CONCURRENCY))
+}
+
+/// Keeps track of the pages of the pastes of the
+/// token's account while they're walked through.
+/// PasteMyst may ignore the page and send every
+/// paste at once, the ids already seen are
+/// skipped so that doesn't loop forever.
+#[cfg(all(feature = "user", feature = "network"))]
+struct SelfPages {
+    auth_token: String,
+    page: usize,
+    seen: std::collections::HashSet<String>,
+    done: bool,
+}
+
+#[cfg(all(feature = "user", feature = "network"))]
+impl SelfPages {
+    fn new(auth_token: &str) -> Self {
+        SelfPages { auth_token: auth_token.to_string(), page: 0, seen: Default::default(), done: false }
+    }
+
+    /// The url of the next page, `None`
+    /// once the last one was read.
+    fn next_url(&self) -> Option<String> {
+        if self.done { return None; }
+        Some(format!("{}self/pastes?page={}&perPage={}", USER_ENDPOINT, self.page, SELF_PAGE_SIZE))
+    }
+
+    /// Takes in a page as PasteMyst sent it and returns
+    /// the ids that weren't seen yet. A short page, or
+    /// one without new ids, is the last one.
+    fn take(&mut self, page: Vec<String>) -> Result<Vec<PasteId>, PasteMystError> {
+        self.page += 1;
+        let full = page.len() >= SELF_PAGE_SIZE;
+        let ids: Vec<PasteId> = page
+            .into_iter()
+            .filter(|id| self.seen.insert(id.clone()))
+            .map(|id| PasteId::parse(&id))
+            .collect::<Result<_, _>>()?;
+        if !full || ids.is_empty() { self.done = true; }
+        Ok(ids)
+    }
 }
 
 /// Parses a user `GET` url endpoint.
-fn parse_user(username: &str) -> String { return format!("{}{}", USER_ENDPOINT, username) }
+#[cfg(all(feature = "user", feature = "network"))]
+pub(crate) fn parse_user(username: &str) -> String { format!("{}{}", USER_ENDPOINT, username) }
 /// Parses a user exists url endpoint.
-fn parse_user_get(username: &str) -> String { return format!("{}{}/exists", USER_ENDPOINT, username) }
+#[cfg(all(feature = "user", feature = "network"))]
+pub(crate) fn parse_user_get(username: &str) -> String { format!("{}{}/exists", USER_ENDPOINT, username) }
 
 /// The user object that pastemyst provides.
 /// It has all the public details of a user.
+/// Fields missing from the response are left
+/// empty, see `lenient::user_from_str_strict`
+/// to fail instead.
 ///
 /// ### API Docs
 /// The relevent API documentation for this method is:
 /// https://paste.myst.rs/api-docs/user
-#[derive(Deserialize)]
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::user::UserObject;
+///
+/// let body = r#"{"_id":"anf","username":"ANF-Studios","avatarUrl":"https://paste.myst.rs/static/avatar.png","defaultLang":"Rust","publicProfile":true,"supporterLength":0,"contributor":false}"#;
+/// let user: UserObject = serde_json::from_str(body).unwrap();
+/// assert_eq!(serde_json::to_string(&user).unwrap(), body);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[allow(non_snake_case, dead_code, unused_doc_comments)]
 pub struct UserObject {
     /// Id of the user.
     pub _id: String,
     /// The username of the user.
     pub username: String,
-    /// URL of the avatar image.
+    /// URL of the avatar image. With the `url`
+    /// feature it's checked when the user is read
+    /// and `avatar_url` hands it out as a `url::Url`.
+    #[cfg_attr(feature = "url", serde(deserialize_with = "deserialize_avatar_url"))]
     pub avatarUrl: String,
     /// The default pasty language
     /// of the user.
@@ -244,3 +839,154 @@ pub struct UserObject {
     /// to pastemyst.
     pub contributor: bool,
 }
+
+impl UserObject {
+    /// The link to the profile of the user
+    /// on PasteMyst.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::user::UserObject;
+    ///
+    /// let user = UserObject { username: String::from("ANF-Studios"), ..Default::default() };
+    /// assert_eq!(user.profile_url(), "https://paste.myst.rs/users/ANF-Studios");
+    /// ```
+    pub fn profile_url(&self) -> String { self.profile_url_on(crate::paste::BASE_URL) }
+
+    /// The link to the profile of the user on another
+    /// instance of PasteMyst, such as a self-hosted one.
+    pub fn profile_url_on(&self, base_url: &str) -> String {
+        format!("{}/users/{}", base_url.trim_end_matches('/'), self.username)
+    }
+
+    /// The avatar of the user as a `url::Url`, a link
+    /// relative to PasteMyst is made absolute. `None`
+    /// if the user has no avatar.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use pastemyst::user::UserObject;
+    ///
+    /// let user: UserObject = serde_json::from_str(r#"{"avatarUrl": "/static/avatar.png"}"#).unwrap();
+    /// assert_eq!(user.avatar_url().unwrap().as_str(), "https://paste.myst.rs/static/avatar.png");
+    ///
+    /// assert!(serde_json::from_str::<UserObject>(r#"{"avatarUrl": "http://"}"#).is_err());
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn avatar_url(&self) -> Option<url::Url> {
+        if self.avatarUrl.is_empty() { return None; }
+        parse_avatar_url(&self.avatarUrl).ok()
+    }
+}
+
+/// Parses an avatar url, resolved
+/// against PasteMyst if it's relative.
+#[cfg(feature = "url")]
+fn parse_avatar_url(avatar_url: &str) -> Result<url::Url, url::ParseError> {
+    url::Url::parse(crate::paste::BASE_URL)?.join(avatar_url)
+}
+
+/// Checks that the avatar url of a user is a
+/// valid url, an empty one is let through.
+#[cfg(feature = "url")]
+fn deserialize_avatar_url<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let avatar_url = String::deserialize(deserializer)?;
+    if !avatar_url.is_empty() {
+        parse_avatar_url(&avatar_url)
+            .map_err(|error| serde::de::Error::custom(format!("invalid avatarUrl '{}': {}", avatar_url, error)))?;
+    }
+    Ok(avatar_url)
+}
+
+/// Whether a user exists, returned by `user_existence`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::user::UserExistence;
+///
+/// assert_eq!(UserExistence::from_status(200), UserExistence::Exists);
+/// assert_eq!(UserExistence::from_status(404), UserExistence::NotFound);
+/// assert_eq!(UserExistence::from_status(503), UserExistence::Unknown(503));
+/// assert!(!UserExistence::Unknown(503).exists());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserExistence {
+    /// The user exists.
+    Exists,
+    /// PasteMyst has no such user.
+    NotFound,
+    /// PasteMyst responded with another status, so
+    /// whether the user exists isn't known.
+    Unknown(u16),
+}
+
+impl UserExistence {
+    /// Reads the status PasteMyst responded
+    /// with to the exists endpoint.
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            200 => UserExistence::Exists,
+            404 => UserExistence::NotFound,
+            status => UserExistence::Unknown(status),
+        }
+    }
+
+    /// Whether the user is known to exist.
+    pub fn exists(&self) -> bool { *self == UserExistence::Exists }
+}
+
+/// The account a token belongs to, returned by
+/// `get_self_user`. It has every field of the public
+/// `UserObject`, which it derefs to, and the ones
+/// PasteMyst only sends to the owner of the account.
+///
+/// ## Examples
+///
+/// ```rust
+/// use pastemyst::user::SelfUserObject;
+///
+/// let body = r#"{
+///     "_id": "anf", "username": "ANF-Studios", "defaultLang": "Rust",
+///     "stars": ["hipfqanx"], "serviceIds": { "github": "12345" }
+/// }"#;
+/// let user: SelfUserObject = serde_json::from_str(body).unwrap();
+/// assert_eq!(user.username, "ANF-Studios");
+/// assert_eq!(user.stars, vec!["hipfqanx"]);
+/// assert_eq!(user.serviceIds["github"], "12345");
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(non_snake_case)]
+pub struct SelfUserObject {
+    /// The public details of the user.
+    #[serde(flatten)]
+    pub user: UserObject,
+    /// The ids of the pastes
+    /// the user has starred.
+    pub stars: Vec<String>,
+    /// The ids of the accounts the user logs
+    /// in with, by service, e.g. `github`.
+    pub serviceIds: std::collections::HashMap<String, String>,
+}
+
+impl std::ops::Deref for SelfUserObject {
+    type Target = UserObject;
+
+    fn deref(&self) -> &UserObject { &self.user }
+}
+
+/// Everything known about a profile at one
+/// point in time, returned by `snapshot`.
+pub struct ProfileSnapshot {
+    /// The user the profile belongs to.
+    pub user: UserObject,
+    /// The ids of the pastes of the user,
+    /// empty unless it's your own account.
+    pub paste_ids: Vec<String>,
+    /// The pastes of the user, in the
+    /// same order as `paste_ids`.
+    pub pastes: Vec<PasteObject>,
+}
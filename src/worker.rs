@@ -0,0 +1,372 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use crate::client::{ClientResult, PasteMystClient};
+use crate::error::PasteMystError;
+use crate::paste::{CreateObject, PasteObject};
+
+pub use crate::request::{PasteRequest, PasteResponse};
+
+/// The receiving end of a submitted command,
+/// resolves once the worker is done with it.
+pub type ReplyReceiver = oneshot::Receiver<ClientResult<PasteResponse>>;
+
+/// Called once a paste created with `create_paste_detached`
+/// was delivered, or given up on. It receives the paste that
+/// was sent and the result of sending it.
+pub type DetachedCallback = Arc<dyn Fn(&CreateObject, &ClientResult<PasteObject>) + Send + Sync>;
+
+/// How a `PasteMystWorker` runs the commands it receives.
+pub struct WorkerConfig {
+    /// The client commands are sent through, configure
+    /// rate limiting and the like on it.
+    pub client: PasteMystClient,
+    /// How many commands are run at once.
+    pub concurrency: usize,
+    /// How many times a command is retried after
+    /// a network error or a server error. Creating
+    /// a paste is only retried when it can't have
+    /// reached pastemyst (it couldn't connect or was
+    /// rate limited), as a create that timed out may
+    /// have gone through and sending it again would
+    /// make a duplicate.
+    pub max_retries: u32,
+    /// How long to wait before the first retry,
+    /// doubled after each one.
+    pub retry_delay: Duration,
+    /// A directory pastes created with `create_paste_detached`
+    /// are saved to until they're delivered. Pastes left in it,
+    /// because the process stopped or they kept failing, are
+    /// sent again when a worker is spawned with the same
+    /// directory. `None` keeps them in memory only.
+    pub spool_dir: Option<PathBuf>,
+    /// Called for every detached paste
+    /// once it's delivered or given up on.
+    pub on_detached: Option<DetachedCallback>,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        WorkerConfig {
+            client: PasteMystClient::new(),
+            concurrency: 4,
+            max_retries: 3,
+            retry_delay: Duration::from_secs(1),
+            spool_dir: None,
+            on_detached: None,
+        }
+    }
+}
+
+/// A background task that owns a `PasteMystClient` and runs
+/// the commands submitted through its `WorkerHandle`. Rate
+/// limiting, retries and metrics are taken care of in one
+/// place, which suits long running services where many
+/// tasks talk to pastemyst.
+///
+/// The worker stops once every handle has been dropped
+/// and the commands it received are done.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use pastemyst::paste::*;
+/// use pastemyst::worker::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (handle, worker) = PasteMystWorker::spawn(WorkerConfig::default());
+///     let reply = handle.submit(PasteRequest::Create(CreateObject {
+///         title: String::from("From the worker"),
+///         expiresIn: pastemyst::time::ExpiresIn::OneDay,
+///         isPrivate: false,
+///         isPublic: false,
+///         tags: Vec::new(),
+///         pasties: vec![PastyObject {
+///             _id: String::from(""),
+///             language: String::from(pastemyst::data::language::PLAIN),
+///             title: String::from("Log"),
+///             code: String::from("Hello from a worker"),
+///         }],
+///     }));
+///     if let PasteResponse::Paste(paste) = reply.await?? {
+///         println!("{}", paste.url());
+///     }
+///     drop(handle);
+///     worker.await?;
+///     Ok(())
+/// }
+/// ```
+pub struct PasteMystWorker;
+
+impl PasteMystWorker {
+    /// Spawns the worker on the current tokio runtime and
+    /// returns a handle to submit commands to it along
+    /// with the `JoinHandle` of its task.
+    pub fn spawn(config: WorkerConfig) -> (WorkerHandle, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::unbounded_channel::<Job>();
+        let metrics: Arc<Metrics> = Default::default();
+        let handle = WorkerHandle { sender, metrics: metrics.clone(), spool_dir: config.spool_dir.clone() };
+        // Queued before the handle is returned, so pastes
+        // spooled through it afterwards aren't recovered
+        // (and created) a second time.
+        if let Some(spool_dir) = &config.spool_dir { handle.recover(spool_dir); }
+        let task = tokio::spawn(async move {
+            let jobs = stream::unfold(receiver, |mut receiver| async move {
+                receiver.recv().await.map(|job| (job, receiver))
+            });
+            jobs.for_each_concurrent(config.concurrency.max(1), |job| {
+                let config = &config;
+                let metrics = &metrics;
+                async move {
+                    let result = run_with_retries(config, metrics, &job.command).await;
+                    match result {
+                        Ok(_) => metrics.succeeded.fetch_add(1, Ordering::Relaxed),
+                        Err(_) => metrics.failed.fetch_add(1, Ordering::Relaxed),
+                    };
+                    match job.delivery {
+                        Delivery::Reply(reply) => { let _ = reply.send(result); }
+                        Delivery::Detached(spool_file) => finish_detached(config, &job.command, result, spool_file),
+                    }
+                }
+            })
+            .await;
+        });
+        (handle, task)
+    }
+}
+
+/// Submits commands to a `PasteMystWorker`. Cloning
+/// the handle is cheap, every clone talks to the
+/// same worker.
+#[derive(Clone)]
+pub struct WorkerHandle {
+    sender: mpsc::UnboundedSender<Job>,
+    metrics: Arc<Metrics>,
+    spool_dir: Option<PathBuf>,
+}
+
+impl WorkerHandle {
+    /// Submits a command to the worker and returns a
+    /// receiver for its result. The receiver fails
+    /// if the worker stopped before replying.
+    pub fn submit(&self, command: PasteRequest) -> ReplyReceiver {
+        let (reply, receiver) = oneshot::channel();
+        self.metrics.submitted.fetch_add(1, Ordering::Relaxed);
+        let _ = self.sender.send(Job { command, delivery: Delivery::Reply(reply) });
+        receiver
+    }
+
+    /// Creates a paste without waiting for it, for telemetry
+    /// or crash reports where the caller can't wait on the
+    /// upload. The paste is saved to the spool directory
+    /// first (if the worker has one), retried like any other
+    /// command and reported to the `on_detached` callback.
+    ///
+    /// Pastes that still fail with a retryable error stay in
+    /// the spool directory and are sent again the next time
+    /// a worker is spawned with it. Ones that timed out or
+    /// failed with a server error are reported and removed
+    /// instead, since they may have been created after all.
+    /// Only failing to save the paste is returned as an error.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use pastemyst::paste::*;
+    /// use pastemyst::worker::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = WorkerConfig {
+    ///         spool_dir: Some("crash-reports".into()),
+    ///         on_detached: Some(Arc::new(|_, result| {
+    ///             if let Ok(paste) = result { println!("Reported at {}", paste.url()); }
+    ///         })),
+    ///         ..Default::default()
+    ///     };
+    ///     let (handle, worker) = PasteMystWorker::spawn(config);
+    ///     handle.create_paste_detached(CreateObject {
+    ///         title: String::from("Crash report"),
+    ///         expiresIn: pastemyst::time::ExpiresIn::OneWeek,
+    ///         isPrivate: false,
+    ///         isPublic: false,
+    ///         tags: vec![String::from("crash")],
+    ///         pasties: vec![PastyObject {
+    ///             _id: String::from(""),
+    ///             language: String::from(pastemyst::data::language::PLAIN),
+    ///             title: String::from("Backtrace"),
+    ///             code: String::from("thread 'main' panicked"),
+    ///         }],
+    ///     })?;
+    ///     drop(handle);
+    ///     worker.await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_paste_detached(&self, contents: CreateObject) -> ClientResult<()> {
+        let spool_file = match &self.spool_dir {
+            Some(spool_dir) => Some(spool(spool_dir, &contents)?),
+            None => None,
+        };
+        self.metrics.submitted.fetch_add(1, Ordering::Relaxed);
+        let _ = self.sender.send(Job { command: PasteRequest::Create(contents), delivery: Delivery::Detached(spool_file) });
+        Ok(())
+    }
+
+    /// Submits every paste left in the
+    /// spool directory again, skipping
+    /// files that can't be read.
+    fn recover(&self, spool_dir: &Path) {
+        let entries = match std::fs::read_dir(spool_dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+            if path.extension() != Some(OsStr::new("json")) { continue; }
+            let contents = match std::fs::read_to_string(&path).map(|json| serde_json::from_str::<CreateObject>(&json)) {
+                Ok(Ok(contents)) => contents,
+                _ => continue,
+            };
+            self.metrics.submitted.fetch_add(1, Ordering::Relaxed);
+            let _ = self.sender.send(Job { command: PasteRequest::Create(contents), delivery: Delivery::Detached(Some(path)) });
+        }
+    }
+
+    /// Whether the worker is still
+    /// accepting commands.
+    pub fn is_running(&self) -> bool { !self.sender.is_closed() }
+
+    /// The counters of the worker
+    /// at this point in time.
+    pub fn metrics(&self) -> WorkerMetrics {
+        WorkerMetrics {
+            submitted: self.metrics.submitted.load(Ordering::Relaxed),
+            succeeded: self.metrics.succeeded.load(Ordering::Relaxed),
+            failed: self.metrics.failed.load(Ordering::Relaxed),
+            retries: self.metrics.retries.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The counters of a worker since it was spawned,
+/// returned by `WorkerHandle::metrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerMetrics {
+    /// Commands submitted to the worker.
+    pub submitted: u64,
+    /// Commands that succeeded.
+    pub succeeded: u64,
+    /// Commands that still failed
+    /// after being retried.
+    pub failed: u64,
+    /// Retries made across all commands.
+    pub retries: u64,
+}
+
+/// A command waiting to be run along
+/// with where its result goes.
+struct Job {
+    command: PasteRequest,
+    delivery: Delivery,
+}
+
+/// Where the result of a job goes.
+enum Delivery {
+    /// Back to whoever submitted it.
+    Reply(oneshot::Sender<ClientResult<PasteResponse>>),
+    /// To the `on_detached` callback, the path
+    /// is the file the paste was spooled to.
+    Detached(Option<PathBuf>),
+}
+
+/// The counters shared between
+/// the worker and its handles.
+#[derive(Default)]
+struct Metrics {
+    submitted: AtomicU64,
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    retries: AtomicU64,
+}
+
+/// Runs a command, retrying it with an
+/// exponential backoff while it fails
+/// with a retryable error.
+async fn run_with_retries(config: &WorkerConfig, metrics: &Metrics, command: &PasteRequest) -> ClientResult<PasteResponse> {
+    let mut delay = config.retry_delay;
+    let mut attempt: u32 = 0;
+    loop {
+        match command.send(&config.client).await {
+            Err(error) if attempt < config.max_retries && is_retryable(command, &error) => {
+                attempt += 1;
+                config.client.record_retry();
+                metrics.retries.fetch_add(1, Ordering::Relaxed);
+                let wait = match error {
+                    PasteMystError::RateLimited { retry_after: Some(retry_after) } => retry_after,
+                    _ => delay,
+                };
+                tokio::time::sleep(wait).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Saves a paste to the spool directory
+/// and returns the path of its file.
+fn spool(spool_dir: &Path, contents: &CreateObject) -> ClientResult<PathBuf> {
+    static SPOOLED: AtomicU64 = AtomicU64::new(0);
+    std::fs::create_dir_all(spool_dir)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let path = spool_dir.join(format!("{}-{}.json", now, SPOOLED.fetch_add(1, Ordering::Relaxed)));
+    std::fs::write(&path, serde_json::to_string(contents)?)?;
+    Ok(path)
+}
+
+/// Removes a delivered (or hopeless) paste from
+/// the spool directory and reports it. Pastes
+/// that may still go through are kept.
+fn finish_detached(config: &WorkerConfig, command: &PasteRequest, result: ClientResult<PasteResponse>, spool_file: Option<PathBuf>) {
+    let contents = match command {
+        PasteRequest::Create(contents) => contents,
+        _ => return,
+    };
+    let result = result.map(|reply| match reply {
+        PasteResponse::Paste(paste) => paste,
+        PasteResponse::Deleted(_) => unreachable!("[pastemyst] A create command was answered with a delete"),
+    });
+    let keep = matches!(&result, Err(error) if is_retryable(command, error));
+    if let (Some(path), false) = (spool_file, keep) { let _ = std::fs::remove_file(path); }
+    if let Some(callback) = &config.on_detached { callback(contents, &result); }
+}
+
+/// Whether an error may go away if the command
+/// is sent again. Creating a paste isn't idempotent,
+/// so it's only sent again if the first attempt
+/// can't have created it.
+fn is_retryable(command: &PasteRequest, error: &PasteMystError) -> bool {
+    if let PasteRequest::Create(_) = command {
+        return match error {
+            PasteMystError::Request(error) => error.is_connect(),
+            PasteMystError::Api { status, .. } => *status == 429,
+            PasteMystError::RateLimited { .. } => true,
+            _ => false,
+        };
+    }
+    match error {
+        PasteMystError::Request(error) => error.is_timeout() || error.is_connect() || error.is_request(),
+        PasteMystError::Api { status, .. } => *status == 429 || *status >= 500,
+        PasteMystError::RateLimited { .. } => true,
+        _ => false,
+    }
+}